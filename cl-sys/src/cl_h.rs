@@ -733,8 +733,8 @@ extern "system" {
     //############################### NEW 2.1 #################################
     #[cfg(feature = "opencl_version_2_1")]
     pub fn clGetDeviceAndHostTimer(device: cl_device_id,
-                                   device_timestamp: cl_ulong,
-                                   host_timestamp: cl_ulong) -> cl_int;
+                                   device_timestamp: *mut cl_ulong,
+                                   host_timestamp: *mut cl_ulong) -> cl_int;
 
     // extern CL_API_ENTRY cl_int CL_API_CALL
     // clGetHostTimer(cl_device_id /* device */,
@@ -742,7 +742,7 @@ extern "system" {
     //############################### NEW 2.1 #################################
     #[cfg(feature = "opencl_version_2_1")]
     pub fn clGetHostTimer(device: cl_device_id,
-                          host_timestamp: cl_ulong) -> cl_int;
+                          host_timestamp: *mut cl_ulong) -> cl_int;
 
     // Context APIs:
     pub fn clCreateContext(properties: *const cl_context_properties,
@@ -1047,10 +1047,9 @@ extern "system" {
 
     // extern CL_API_ENTRY cl_int CL_API_CALL
     // clUnloadPlatformCompiler(cl_platform_id /* platform */) CL_API_SUFFIX__VERSION_1_2;
-    // //############################### NEW 1.2 #################################
+    //############################### NEW 1.2 #################################
     #[cfg(feature = "opencl_version_1_2")]
-    // [DISABLED DUE TO PLATFORM INCOMPATABILITY]
-    // pub fn clUnloadPlatformCompiler(platform: cl_platform_id) -> cl_int;
+    pub fn clUnloadPlatformCompiler(platform: cl_platform_id) -> cl_int;
 
     pub fn clGetProgramInfo(program: cl_program,
                         param_name: cl_program_info,
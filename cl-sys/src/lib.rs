@@ -56,7 +56,15 @@ pub use self::cl_d3d10_h::{CL_CONTEXT_D3D10_DEVICE_KHR, cl_d3d10_device_source_k
 pub use self::cl_dx9_media_sharing_h::{CL_CONTEXT_ADAPTER_D3D9_KHR, CL_CONTEXT_ADAPTER_D3D9EX_KHR,
     CL_CONTEXT_ADAPTER_DXVA_KHR};
 
-pub use self::cl_d3d11_h::CL_CONTEXT_D3D11_DEVICE_KHR;
+pub use self::cl_d3d11_h::{CL_CONTEXT_D3D11_DEVICE_KHR, cl_d3d11_device_source_khr,
+    cl_d3d11_device_set_khr, CL_D3D11_DEVICE_KHR, CL_D3D11_DXGI_ADAPTER_KHR,
+    CL_PREFERRED_DEVICES_FOR_D3D11_KHR, CL_ALL_DEVICES_FOR_D3D11_KHR,
+    CL_CONTEXT_D3D11_PREFER_SHARED_RESOURCES_KHR, CL_MEM_D3D11_RESOURCE_KHR,
+    CL_IMAGE_D3D11_SUBRESOURCE_KHR, CL_COMMAND_ACQUIRE_D3D11_OBJECTS_KHR,
+    CL_COMMAND_RELEASE_D3D11_OBJECTS_KHR, clGetDeviceIDsFromD3D11KHR_fn,
+    clCreateFromD3D11BufferKHR_fn, clCreateFromD3D11Texture2DKHR_fn,
+    clCreateFromD3D11Texture3DKHR_fn, clEnqueueAcquireD3D11ObjectsKHR_fn,
+    clEnqueueReleaseD3D11ObjectsKHR_fn};
 
 // Types:
 pub use self::cl_h::{cl_platform_id, cl_device_id, cl_context, cl_command_queue, cl_mem,
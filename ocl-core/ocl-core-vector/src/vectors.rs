@@ -7,7 +7,6 @@
 //!
 //! See crate level documentation for more information.
 //!
-//! [TODO]: Add scalar-widening operations (allowing vec * scl for example).
 //! [TODO]: impl Hash.
 
 // #![allow(unused_imports)]
@@ -285,6 +284,65 @@ macro_rules! impl_int_ops {
             }
         }
 
+        impl Add<$ty> for $name {
+            type Output = $name;
+
+            #[inline(always)]
+            fn add(self, rhs: $ty) -> $name {
+                $name::from([$( self[$idx].wrapping_add(rhs) ),+])
+            }
+        }
+
+        impl Sub<$ty> for $name {
+            type Output = $name;
+
+            #[inline(always)]
+            fn sub(self, rhs: $ty) -> $name {
+                $name::from([$( self[$idx].wrapping_sub(rhs) ),+])
+            }
+        }
+
+        impl Mul<$ty> for $name {
+            type Output = $name;
+
+            #[inline(always)]
+            fn mul(self, rhs: $ty) -> $name {
+                $name::from([$( self[$idx].wrapping_mul(rhs) ),+])
+            }
+        }
+
+        impl Div<$ty> for $name {
+            type Output = $name;
+
+            #[inline(always)]
+            fn div(self, rhs: $ty) -> $name {
+                $name::from([$( self[$idx].wrapping_div(rhs) ),+])
+            }
+        }
+
+        impl Rem<$ty> for $name {
+            type Output = $name;
+
+            #[inline(always)]
+            fn rem(self, rhs: $ty) -> $name {
+                $name::from([$( self[$idx].wrapping_rem(rhs) ),+])
+            }
+        }
+
+        impl $name {
+            /// Returns the component-wise minimum of `self` and `rhs`.
+            #[inline]
+            pub fn min(self, rhs: $name) -> $name {
+                $name::from([$( ::std::cmp::min(self[$idx], rhs[$idx]) ),+])
+            }
+
+            /// Returns the component-wise maximum of `self` and `rhs`.
+            #[inline]
+            pub fn max(self, rhs: $name) -> $name {
+                $name::from([$( ::std::cmp::max(self[$idx], rhs[$idx]) ),+])
+            }
+        }
+
         impl Not for $name {
             type Output = $name;
 
@@ -405,6 +463,71 @@ macro_rules! impl_float_ops {
             }
         }
 
+        impl Add<$ty> for $name {
+            type Output = $name;
+
+            #[inline(always)]
+            fn add(self, rhs: $ty) -> $name {
+                $name::from([$( self[$idx] + rhs ),+])
+            }
+        }
+
+        impl Sub<$ty> for $name {
+            type Output = $name;
+
+            #[inline(always)]
+            fn sub(self, rhs: $ty) -> $name {
+                $name::from([$( self[$idx] - rhs ),+])
+            }
+        }
+
+        impl Mul<$ty> for $name {
+            type Output = $name;
+
+            #[inline(always)]
+            fn mul(self, rhs: $ty) -> $name {
+                $name::from([$( self[$idx] * rhs ),+])
+            }
+        }
+
+        impl Div<$ty> for $name {
+            type Output = $name;
+
+            #[inline(always)]
+            fn div(self, rhs: $ty) -> $name {
+                $name::from([$( self[$idx] / rhs ),+])
+            }
+        }
+
+        impl Rem<$ty> for $name {
+            type Output = $name;
+
+            #[inline(always)]
+            fn rem(self, rhs: $ty) -> $name {
+                $name::from([$( self[$idx] % rhs ),+])
+            }
+        }
+
+        impl $name {
+            /// Returns the component-wise minimum of `self` and `rhs`.
+            ///
+            /// If one component is NaN, the other is returned (as with
+            /// OpenCL's `fmin`).
+            #[inline]
+            pub fn min(self, rhs: $name) -> $name {
+                $name::from([$( self[$idx].min(rhs[$idx]) ),+])
+            }
+
+            /// Returns the component-wise maximum of `self` and `rhs`.
+            ///
+            /// If one component is NaN, the other is returned (as with
+            /// OpenCL's `fmax`).
+            #[inline]
+            pub fn max(self, rhs: $name) -> $name {
+                $name::from([$( self[$idx].max(rhs[$idx]) ),+])
+            }
+        }
+
         impl Neg for $name {
             type Output = $name;
             #[inline(always)]
@@ -501,6 +624,15 @@ macro_rules! impl_common {
             }
         }
 
+        forward_ref_binop! { impl Add, add for $name, $ty }
+
+        impl AddAssign<$ty> for $name {
+            #[inline(always)]
+            fn add_assign(&mut self, rhs: $ty) {
+                *self = *self + rhs;
+            }
+        }
+
         forward_ref_binop! { impl Sub, sub for $name, $name }
 
         impl SubAssign for $name {
@@ -510,6 +642,15 @@ macro_rules! impl_common {
             }
         }
 
+        forward_ref_binop! { impl Sub, sub for $name, $ty }
+
+        impl SubAssign<$ty> for $name {
+            #[inline(always)]
+            fn sub_assign(&mut self, rhs: $ty) {
+                *self = *self - rhs;
+            }
+        }
+
         forward_ref_binop! { impl Mul, mul for $name, $name }
 
         impl MulAssign for $name {
@@ -519,6 +660,15 @@ macro_rules! impl_common {
             }
         }
 
+        forward_ref_binop! { impl Mul, mul for $name, $ty }
+
+        impl MulAssign<$ty> for $name {
+            #[inline(always)]
+            fn mul_assign(&mut self, rhs: $ty) {
+                *self = *self * rhs;
+            }
+        }
+
         forward_ref_binop! { impl Div, div for $name, $name }
 
         impl DivAssign for $name {
@@ -528,6 +678,15 @@ macro_rules! impl_common {
             }
         }
 
+        forward_ref_binop! { impl Div, div for $name, $ty }
+
+        impl DivAssign<$ty> for $name {
+            #[inline(always)]
+            fn div_assign(&mut self, rhs: $ty) {
+                *self = *self / rhs;
+            }
+        }
+
         forward_ref_binop! { impl Rem, rem for $name, $name }
 
         impl RemAssign for $name {
@@ -537,6 +696,15 @@ macro_rules! impl_common {
             }
         }
 
+        forward_ref_binop! { impl Rem, rem for $name, $ty }
+
+        impl RemAssign<$ty> for $name {
+            #[inline(always)]
+            fn rem_assign(&mut self, rhs: $ty) {
+                *self = *self % rhs;
+            }
+        }
+
         forward_ref_unop! { impl Neg, neg for $name }
 
         impl_sum_product!($name);
@@ -4,9 +4,11 @@
 use std::fmt;
 use failure::{Context, Fail, Backtrace};
 use util::UtilError;
-use functions::{ApiError, VersionLowError, ProgramBuildError, ApiWrapperError};
+use functions::{VersionLowError, ProgramBuildError, ApiWrapperError};
 use ::{Status, EmptyInfoResultError};
 
+pub use functions::ApiError;
+
 
 /// Ocl error result type.
 pub type Result<T> = ::std::result::Result<T, Error>;
@@ -70,6 +72,11 @@ impl Error {
         }
     }
 
+    /// Returns the raw error status code for `Status` variants.
+    pub fn status_code(&self) -> Option<i32> {
+        self.api_status().map(|status| status as i32)
+    }
+
     /// Returns the error variant and contents.
     pub fn kind(&self) -> &ErrorKind {
         self.inner.get_context()
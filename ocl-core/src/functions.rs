@@ -20,6 +20,7 @@ use std::thread;
 use std::time::Duration;
 use std::env;
 use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use failure::Fail;
 use ffi::{size_t, c_void};
 use num_traits::FromPrimitive;
@@ -57,6 +58,8 @@ use ::{OclPrm, PlatformId, DeviceId, Context, ContextProperties, ContextInfo,
 
 #[cfg(not(feature="opencl_vendor_mesa"))]
 use ::{GlContextInfo, GlContextInfoResult};
+#[cfg(feature = "opencl_version_2_0")]
+use ::{PipeInfo, PipeInfoResult};
 
 // [TODO]: Do proper auto-detection of available OpenGL context type.
 #[cfg(target_os="macos")]
@@ -64,6 +67,9 @@ const CL_GL_SHARING_EXT: &'static str = "cl_APPLE_gl_sharing";
 #[cfg(not(target_os="macos"))]
 const CL_GL_SHARING_EXT: &'static str = "cl_khr_gl_sharing";
 
+#[cfg(all(feature = "d3d11_interop", windows))]
+const CL_D3D11_SHARING_EXT: &'static str = "cl_khr_d3d11_sharing";
+
 const KERNEL_DEBUG_SLEEP_DURATION_MS: u64 = 150;
 const PLATFORM_IDS_ATTEMPT_TIMEOUT_MS: u64 = 2000;
 const PLATFORM_IDS_ATTEMPT_COUNT: u64 = 5;
@@ -122,6 +128,22 @@ pub extern "C" fn _complete_user_event(src_event_ptr: cl_event, event_status: i3
     }
 }
 
+/// A host function enqueued as a native kernel.
+type NativeKernelFn = Box<dyn FnMut() + Send + 'static>;
+
+/// Calls and frees the boxed `NativeKernelFn` whose pointer is stored in the
+/// first slot of the argument block passed to `clEnqueueNativeKernel`.
+///
+/// The argument block is a copy made by the runtime, the closure it points to
+/// is only ever called once.
+extern "C" fn _call_native_kernel(args: *mut c_void) {
+    unsafe {
+        let func_ptr = *(args as *mut *mut NativeKernelFn);
+        let mut func = Box::from_raw(func_ptr);
+        (*func)();
+    }
+}
+
 //============================================================================
 //============================================================================
 //============================ ERROR HANDLING ================================
@@ -159,6 +181,11 @@ impl ApiError {
     pub fn status(&self) -> Status {
         self.status
     }
+
+    /// Returns the name of the API function which returned this error.
+    pub fn fn_name(&self) -> &'static str {
+        self.fn_name
+    }
 }
 
 impl Fail for ApiError {}
@@ -173,13 +200,19 @@ impl fmt::Display for ApiError {
 
         let status_int = self.status as i32;
 
+        let description_string = if let Some(description) = self.status.description() {
+            format!("\n\n{}  ", description)
+        } else {
+            String::with_capacity(0)
+        };
+
         write!(f, "\n\n\
             ################################ OPENCL ERROR ############################### \
             \n\nError executing function: {}{}  \
-            \n\nStatus error code: {:?} ({})  \
+            \n\nStatus error code: {:?} ({})  {}\
             \n\nPlease visit the following url for more information: \n\n{}{}{}  \n\n\
             ############################################################################# \n",
-            self.fn_name, fn_info_string, self.status, status_int,
+            self.fn_name, fn_info_string, self.status, status_int, description_string,
             SDK_DOCS_URL_PRE, self.fn_name, SDK_DOCS_URL_SUF)
     }
 }
@@ -205,6 +238,36 @@ fn eval_errcode<T, S>(errcode: cl_int, result: T, fn_name: &'static str, fn_info
 }
 
 
+/// A function called with any error which occurs while releasing an OpenCL
+/// object from within a destructor (`Drop::drop`).
+pub type ReleaseErrorHook = fn(&OclCoreError);
+
+static RELEASE_ERROR_HOOK: AtomicUsize = AtomicUsize::new(0);
+
+/// Sets a hook to be called with any error which occurs while releasing an
+/// OpenCL object within a destructor.
+///
+/// Destructors cannot return errors and panicking within one while already
+/// unwinding aborts the process. Release errors are therefore passed to this
+/// hook instead. Passing `None` restores the default behavior of printing
+/// the error to stderr.
+///
+pub fn set_release_error_hook(hook: Option<ReleaseErrorHook>) {
+    RELEASE_ERROR_HOOK.store(hook.map(|h| h as usize).unwrap_or(0), Ordering::SeqCst);
+}
+
+/// Passes a release error which occurred within a destructor to the hook set
+/// with `::set_release_error_hook` or prints it to stderr if none is set.
+pub(crate) fn report_release_error(err: &OclCoreError) {
+    match RELEASE_ERROR_HOOK.load(Ordering::SeqCst) {
+        0 => eprintln!("ocl_core: Error releasing OpenCL object: {}", err),
+        hook_ptr => {
+            let hook: ReleaseErrorHook = unsafe { mem::transmute(hook_ptr) };
+            hook(err);
+        },
+    }
+}
+
 /// An OpenCL program build error.
 #[derive(Debug, Fail)]
 pub enum ProgramBuildError {
@@ -271,6 +334,12 @@ pub(crate) enum ApiFunction {
     GetExtensionFunctionAddressForPlatform,
     CompileProgram,
     LinkProgram,
+    CreateCommandQueueWithProperties,
+    SvmAlloc,
+    GetDeviceAndHostTimer,
+    GetHostTimer,
+    UnloadPlatformCompiler,
+    CreatePipe,
 }
 
 
@@ -305,6 +374,9 @@ pub enum ApiWrapperError {
     CreateContextNoDevicesSpecified,
     #[fail(display = "Buffer length and data length and do not match.")]
     CreateBufferDataLengthMismatch,
+    #[fail(display = "Host data length ({} bytes) is less than the size of the image \
+        as described by its format and descriptor ({} bytes).", data_len_bytes, image_len_bytes)]
+    CreateImageDataLengthMismatch { data_len_bytes: usize, image_len_bytes: usize },
     #[fail(display = "One or more of the devices contained in the list provided to \
         '::create_context` doesn't support the cl_gl_sharing extension and cannot be \
         used to create a context associated with OpenGL. [FIXME: determine recommended \
@@ -328,6 +400,21 @@ pub enum ApiWrapperError {
         'OCL_DEFAULT_DEVICE_TYPE': ('{}') is invalid. Valid types are: 'DEFAULT', 'CPU', \
         'GPU', 'ACCELERATOR', 'CUSTOM', and 'ALL'.", _0)]
     DefaultDeviceTypeInvalidType(String),
+    #[fail(display = "Unable to allocate a shared virtual memory buffer of {} bytes. \
+        Ensure that the flags are supported by each device within the context and that \
+        the size does not exceed 'DeviceInfo::MaxMemAllocSize'.", _0)]
+    SvmAllocFailed(usize),
+    #[fail(display = "Invalid fill pattern size ({} bytes). The size of the pattern \
+        type must be one of 1, 2, 4, 8, 16, 32, 64, or 128 bytes.", _0)]
+    EnqueueFillBufferInvalidPatternSize(usize),
+    #[fail(display = "Source buffer too small for a copy to image: the region requires {} \
+        bytes starting at byte offset {} but the buffer is only {} bytes long.",
+        required_bytes, src_offset_bytes, buffer_len_bytes)]
+    EnqueueCopyBufferToImageSrcTooSmall { src_offset_bytes: usize, required_bytes: usize,
+        buffer_len_bytes: usize },
+    #[fail(display = "The platform associated with the context does not support the \
+        'cl_khr_d3d11_sharing' extension.")]
+    D3d11SharingUnsupported,
 }
 
 
@@ -722,6 +809,43 @@ pub unsafe fn release_device(device: &DeviceId, device_version: Option<&OpenclVe
     eval_errcode(ffi::clReleaseDevice(device.as_ptr()), (), "clReleaseDevice", None::<String>)
 }
 
+/// Returns a synchronized pair of timestamps, `(device_timestamp,
+/// host_timestamp)`, in nanoseconds.
+///
+/// The device timestamp is in the same timebase as event profiling info
+/// (e.g. `ProfilingInfo::Start`) and the host timestamp is in the timebase
+/// returned by `get_host_timer`.
+///
+/// [Version Controlled: OpenCL 2.1+] See module docs for more info.
+#[cfg(feature = "opencl_version_2_1")]
+pub fn get_device_and_host_timer(device: &DeviceId, device_version: Option<&OpenclVersion>)
+        -> OclCoreResult<(u64, u64)> {
+    verify_device_version(device_version, [2, 1], device, ApiFunction::GetDeviceAndHostTimer)?;
+
+    let mut device_timestamp: u64 = 0;
+    let mut host_timestamp: u64 = 0;
+
+    let errcode = unsafe { ffi::clGetDeviceAndHostTimer(device.as_ptr(), &mut device_timestamp,
+        &mut host_timestamp) };
+    eval_errcode(errcode, (device_timestamp, host_timestamp), "clGetDeviceAndHostTimer",
+        None::<String>)
+}
+
+/// Returns the current value of the host clock, in nanoseconds, as seen by
+/// `device`.
+///
+/// [Version Controlled: OpenCL 2.1+] See module docs for more info.
+#[cfg(feature = "opencl_version_2_1")]
+pub fn get_host_timer(device: &DeviceId, device_version: Option<&OpenclVersion>)
+        -> OclCoreResult<u64> {
+    verify_device_version(device_version, [2, 1], device, ApiFunction::GetHostTimer)?;
+
+    let mut host_timestamp: u64 = 0;
+
+    let errcode = unsafe { ffi::clGetHostTimer(device.as_ptr(), &mut host_timestamp) };
+    eval_errcode(errcode, host_timestamp, "clGetHostTimer", None::<String>)
+}
+
 //============================================================================
 //============================= Context APIs  ================================
 //============================================================================
@@ -769,6 +893,10 @@ pub fn create_context<D: ClDeviceIdPtr>(properties: Option<&ContextProperties>,
         None => Vec::<isize>::with_capacity(0),
     };
 
+    if !properties_bytes.is_empty() {
+        ContextProperties::verify_raw(&properties_bytes)?;
+    }
+
     // // [DEBUG]:
     // print!("CREATE_CONTEXT: BYTES: ");
     // util::print_bytes_as_hex(&properties_bytes);
@@ -828,6 +956,10 @@ pub fn create_context_from_type<D: ClDeviceIdPtr>(properties: Option<&ContextPro
         None => Vec::<isize>::with_capacity(0),
     };
 
+    if !properties_bytes.is_empty() {
+        ContextProperties::verify_raw(&properties_bytes)?;
+    }
+
     // [DEBUG]:
     // print!("CREATE_CONTEXT: BYTES: ");
     // util::print_bytes_as_hex(&properties_bytes);
@@ -1054,6 +1186,9 @@ pub fn get_gl_context_info_khr(properties: &ContextProperties, request: GlContex
 //============================================================================
 
 /// Returns a new command queue pointer.
+///
+/// `properties` are passed through as-is. Profiling, in particular, is only
+/// enabled when `CommandQueueProperties::PROFILING_ENABLE` is specified.
 pub fn create_command_queue<C, D>(
             context: C,
             device: D,
@@ -1082,6 +1217,74 @@ pub fn create_command_queue<C, D>(
 
 }
 
+/// Returns a new command queue pointer, created using a list of properties.
+///
+/// Unlike `::create_command_queue`, this allows on-device queues
+/// (`CommandQueueProperties::ON_DEVICE`) to be created with an optional
+/// `queue_size` (in bytes).
+///
+/// Devices with a version below 2.0 automatically fall back to
+/// `clCreateCommandQueue`, in which case requesting an on-device queue or a
+/// queue size will return an error. The device version will be queried if
+/// `device_version` is `None`.
+///
+/// [Version Controlled: OpenCL 2.0+] See module docs for more info.
+#[cfg(feature = "opencl_version_2_0")]
+pub fn create_command_queue_with_properties<C, D>(
+            context: C,
+            device: D,
+            properties: Option<CommandQueueProperties>,
+            queue_size: Option<u32>,
+            device_version: Option<&OpenclVersion>,
+        ) -> OclCoreResult<CommandQueue>
+        where C: ClContextPtr, D: ClDeviceIdPtr
+{
+    let device_id = unsafe { DeviceId::from_raw(device.as_ptr()) };
+    let on_device = properties.map(|p| p.contains(CommandQueueProperties::ON_DEVICE))
+        .unwrap_or(false);
+
+    if let Err(err) = verify_device_version(device_version, [2, 0], &device_id,
+            ApiFunction::CreateCommandQueueWithProperties)
+    {
+        if on_device || queue_size.is_some() { return Err(err); }
+        return create_command_queue(context, device, properties);
+    }
+
+    // Verify that the context is valid:
+    verify_context(context)?;
+
+    // A zero-terminated list of property name/value pairs:
+    let mut props_raw: Vec<ffi::cl_queue_properties> = Vec::with_capacity(5);
+
+    if let Some(p) = properties {
+        props_raw.push(ffi::CL_QUEUE_PROPERTIES as ffi::cl_queue_properties);
+        props_raw.push(p.bits());
+    }
+
+    if let Some(size) = queue_size {
+        props_raw.push(ffi::CL_QUEUE_SIZE as ffi::cl_queue_properties);
+        props_raw.push(size as ffi::cl_queue_properties);
+    }
+
+    let props_ptr = if props_raw.is_empty() {
+        ptr::null()
+    } else {
+        props_raw.push(0);
+        props_raw.as_ptr()
+    };
+
+    let mut errcode: cl_int = 0;
+
+    let cq_ptr = unsafe { ffi::clCreateCommandQueueWithProperties(
+        context.as_ptr(),
+        device.as_ptr(),
+        props_ptr,
+        &mut errcode
+    ) };
+    eval_errcode(errcode, cq_ptr, "clCreateCommandQueueWithProperties", None::<String>)
+        .map(|cq_ptr| unsafe { CommandQueue::from_raw_create_ptr(cq_ptr) })
+}
+
 /// Increments the reference count of a command queue.
 pub unsafe fn retain_command_queue(queue: &CommandQueue) -> OclCoreResult<()> {
     eval_errcode(ffi::clRetainCommandQueue(queue.as_ptr()), (), "clRetainCommandQueue", None::<String>)
@@ -1422,8 +1625,13 @@ pub unsafe fn create_image<C, T>(
 
     let host_ptr = match data {
         Some(d) => {
-            // [FIXME]: CALCULATE CORRECT IMAGE SIZE AND COMPARE WITH FORMAT/DESC
-            // assert!(d.len() == len, "ocl::create_image(): Data length mismatch.");
+            let data_len_bytes = d.len() * mem::size_of::<T>();
+            if let Some(image_len_bytes) = image_len_bytes(format, desc) {
+                if data_len_bytes < image_len_bytes {
+                    return Err(ApiWrapperError::CreateImageDataLengthMismatch {
+                        data_len_bytes, image_len_bytes }.into());
+                }
+            }
             d.as_ptr() as cl_mem
         },
         None => ptr::null_mut(),
@@ -1442,6 +1650,70 @@ pub unsafe fn create_image<C, T>(
         .map(|ptr| Mem::from_raw_create_ptr(ptr))
 }
 
+/// Returns the size in bytes of the host memory required to initialize an
+/// image with the specified format and descriptor or `None` if the pixel size
+/// of the format is unknown.
+fn image_len_bytes(format: &ImageFormat, desc: &ImageDescriptor) -> Option<usize> {
+    let pixel_bytes = format.bytes_per_pixel();
+    if pixel_bytes == 0 { return None; }
+
+    let (rows, slices) = match desc.image_type {
+        MemObjectType::Image1dArray => (1, desc.image_array_size),
+        MemObjectType::Image2d => (desc.image_height, 1),
+        MemObjectType::Image2dArray => (desc.image_height, desc.image_array_size),
+        MemObjectType::Image3d => (desc.image_height, desc.image_depth),
+        _ => (1, 1),
+    };
+
+    let row_pitch = if desc.image_row_pitch == 0 {
+        desc.image_width * pixel_bytes
+    } else {
+        desc.image_row_pitch
+    };
+
+    let slc_pitch = if desc.image_slice_pitch == 0 {
+        row_pitch * rows
+    } else {
+        desc.image_slice_pitch
+    };
+
+    Some(slc_pitch * slices)
+}
+
+/// Returns a new pipe (mem) pointer which stores `max_packets` packets of
+/// `packet_size` bytes each.
+///
+/// `flags` may only contain `MEM_READ_WRITE` and `MEM_HOST_NO_ACCESS` (or be
+/// empty).
+///
+/// [Version Controlled: OpenCL 2.0+] See module docs for more info.
+#[cfg(feature = "opencl_version_2_0")]
+pub fn create_pipe<C>(
+            context: C,
+            flags: MemFlags,
+            packet_size: u32,
+            max_packets: u32,
+            device_versions: Option<&[OpenclVersion]>,
+        ) -> OclCoreResult<Mem>
+        where C: ClContextPtr + ClVersions
+{
+    verify_device_versions(device_versions, [2, 0], &context, ApiFunction::CreatePipe)?;
+
+    let mut errcode: cl_int = 0;
+
+    let pipe_ptr = unsafe { ffi::clCreatePipe(
+        context.as_ptr(),
+        flags.bits() as cl_mem_flags,
+        packet_size,
+        max_packets,
+        ptr::null(),
+        &mut errcode,
+    ) };
+
+    eval_errcode(errcode, pipe_ptr, "clCreatePipe", None::<String>)
+        .map(|ptr| unsafe { Mem::from_raw_create_ptr(ptr) })
+}
+
 /// Increments the reference counter of a mem object.
 pub unsafe fn retain_mem_object(mem: &Mem) -> OclCoreResult<()> {
     eval_errcode(ffi::clRetainMemObject(mem.as_ptr()), (), "clRetainMemObject", None::<String>)
@@ -1572,6 +1844,42 @@ pub fn get_image_info(obj: &Mem, request: ImageInfo) -> OclCoreResult<ImageInfoR
     ImageInfoResult::from_bytes(request, result)
 }
 
+/// Get pipe info.
+///
+/// [Version Controlled: OpenCL 2.0+] See module docs for more info.
+#[cfg(feature = "opencl_version_2_0")]
+pub fn get_pipe_info(obj: &Mem, request: PipeInfo) -> OclCoreResult<PipeInfoResult> {
+    let mut result_size: size_t = 0;
+
+    let errcode = unsafe { ffi::clGetPipeInfo(
+        obj.as_ptr() as cl_mem,
+        request as ffi::cl_pipe_info,
+        0 as size_t,
+        0 as *mut c_void,
+        &mut result_size as *mut size_t,
+    ) };
+
+    eval_errcode(errcode, (), "clGetPipeInfo", None::<String>)?;
+
+    // If result size is zero, return an empty info result directly:
+    if result_size == 0 {
+        return PipeInfoResult::from_bytes(request, vec![]);
+    }
+
+    let mut result: Vec<u8> = iter::repeat(0u8).take(result_size).collect();
+
+    let errcode = unsafe { ffi::clGetPipeInfo(
+        obj.as_ptr() as cl_mem,
+        request as ffi::cl_pipe_info,
+        result_size,
+        result.as_mut_ptr() as *mut _ as *mut c_void,
+        0 as *mut size_t,
+    ) };
+
+    let result = eval_errcode(errcode, result, "clGetPipeInfo", None::<String>)?;
+    PipeInfoResult::from_bytes(request, result)
+}
+
 /// [UNIMPLEMENTED: Please implement me]
 pub fn set_mem_object_destructor_callback() -> OclCoreResult<()> {
     // ffi::clSetMemObjectDestructorCallback(memobj: cl_mem,
@@ -1580,6 +1888,60 @@ pub fn set_mem_object_destructor_callback() -> OclCoreResult<()> {
     unimplemented!();
 }
 
+//============================================================================
+//========================= SVM Allocation APIs ==============================
+//============================================================================
+
+/// Allocates a shared virtual memory (SVM) buffer of `size` bytes which can
+/// be shared by the host and all devices within `context`, returning a
+/// pointer to it.
+///
+/// `flags` may contain `MemFlags::SVM_FINE_GRAIN_BUFFER` and
+/// `MemFlags::SVM_ATOMICS` in addition to the read/write flags. An
+/// `alignment` of zero uses the size of the largest supported data type.
+/// The device versions will be queried if `device_versions` is `None`.
+///
+/// ## Safety
+///
+/// The returned pointer must be freed with `::svm_free` using the same
+/// context and must not be dereferenced by the host while a coarse-grained
+/// buffer is in use by a device (see `::enqueue_svm_map`).
+///
+/// [Version Controlled: OpenCL 2.0+] See module docs for more info.
+#[cfg(feature = "opencl_version_2_0")]
+pub unsafe fn svm_alloc(
+            context: &Context,
+            flags: MemFlags,
+            size: usize,
+            alignment: u32,
+            device_versions: Option<&[OpenclVersion]>,
+        ) -> OclCoreResult<*mut c_void>
+{
+    verify_device_versions(device_versions, [2, 0], context, ApiFunction::SvmAlloc)?;
+
+    let svm_ptr = ffi::clSVMAlloc(context.as_ptr(), flags.bits(), size, alignment);
+
+    if svm_ptr.is_null() {
+        Err(ApiWrapperError::SvmAllocFailed(size).into())
+    } else {
+        Ok(svm_ptr)
+    }
+}
+
+/// Frees a shared virtual memory buffer previously allocated with
+/// `::svm_alloc`.
+///
+/// ## Safety
+///
+/// `svm_ptr` must have been allocated using `context` and must not be in
+/// use by any enqueued command.
+///
+/// [Version Controlled: OpenCL 2.0+] See module docs for more info.
+#[cfg(feature = "opencl_version_2_0")]
+pub unsafe fn svm_free(context: &Context, svm_ptr: *mut c_void) {
+    ffi::clSVMFree(context.as_ptr(), svm_ptr)
+}
+
 //============================================================================
 //============================= Sampler APIs =================================
 //============================================================================
@@ -1955,16 +2317,21 @@ pub fn link_program<D: ClDeviceIdPtr, C: ClContextPtr>(
         .map(|ptr| unsafe { Program::from_raw_create_ptr(ptr) })
 }
 
-// [DISABLED DUE TO PLATFORM INCOMPATABILITY]
-// /// [UNTESTED]
-// /// Unloads a platform compiler.
-// ///
-// /// [Version Controlled: OpenCL 1.2+] See module docs for more info.
-// pub fn unload_platform_compiler(platform: &PlatformId,
-//          device_version: Option<&OpenclVersion>) -> OclCoreResult<()> {
-//     unsafe { eval_errcode("clUnloadPlatformCompiler", "",
-//         ffi::clUnloadPlatformCompiler(platform.as_ptr())) }
-// }
+/// Unloads a platform compiler, releasing the resources it has allocated.
+///
+/// This is a hint to the implementation. Subsequent program builds will
+/// reload the compiler as necessary.
+///
+/// [Version Controlled: OpenCL 1.2+] See module docs for more info.
+#[cfg(feature = "opencl_version_1_2")]
+pub fn unload_platform_compiler(platform: &PlatformId,
+        platform_version: Option<&OpenclVersion>) -> OclCoreResult<()> {
+    verify_platform_version(platform_version, [1, 2], platform,
+        ApiFunction::UnloadPlatformCompiler)?;
+
+    let errcode = unsafe { ffi::clUnloadPlatformCompiler(platform.as_ptr()) };
+    eval_errcode(errcode, (), "clUnloadPlatformCompiler", None::<String>)
+}
 
 
 fn get_program_info_raw(program: &Program, request: ProgramInfo) -> OclCoreResult<Vec<u8>> {
@@ -2093,13 +2460,35 @@ pub fn create_kernel<S: AsRef<str>>(program: &Program, name: S) -> OclCoreResult
     }
 }
 
-/// [UNIMPLEMENTED: Please implement me]
-pub fn create_kernels_in_program() -> OclCoreResult<()> {
-    // ffi::clCreateKernelsInProgram(program: cl_program,
-    //                             num_kernels: cl_uint,
-    //                             kernels: *mut cl_kernel,
-    //                             num_kernels_ret: *mut cl_uint) -> cl_int;
-    unimplemented!();
+/// Returns a new kernel for each kernel function within `program`.
+///
+/// The order of the returned kernels is implementation defined. Use
+/// `get_kernel_info(.., KernelInfo::FunctionName)` to identify each.
+pub fn create_kernels_in_program(program: &Program) -> OclCoreResult<Vec<Kernel>> {
+    let mut num_kernels: cl_uint = 0;
+
+    let errcode = unsafe { ffi::clCreateKernelsInProgram(
+        program.as_ptr(),
+        0,
+        ptr::null_mut(),
+        &mut num_kernels,
+    ) };
+    eval_errcode(errcode, (), "clCreateKernelsInProgram", None::<String>)?;
+
+    if num_kernels == 0 { return Ok(Vec::new()); }
+
+    let mut kernel_ptrs: Vec<cl_kernel> = iter::repeat(ptr::null_mut())
+        .take(num_kernels as usize).collect();
+
+    let errcode = unsafe { ffi::clCreateKernelsInProgram(
+        program.as_ptr(),
+        num_kernels,
+        kernel_ptrs.as_mut_ptr(),
+        ptr::null_mut(),
+    ) };
+    eval_errcode(errcode, (), "clCreateKernelsInProgram", None::<String>)?;
+
+    Ok(kernel_ptrs.into_iter().map(|ptr| unsafe { Kernel::from_raw_create_ptr(ptr) }).collect())
 }
 
 /// Increments a kernel reference counter.
@@ -2144,6 +2533,28 @@ pub fn set_kernel_arg(kernel: &Kernel, index: u32, arg_val: ArgVal)
     }
 }
 
+/// Sets a kernel argument to a pointer into a shared virtual memory buffer.
+///
+/// ## Safety
+///
+/// `svm_ptr` must point into a buffer allocated with `::svm_alloc` which
+/// outlives every execution of `kernel` using it.
+///
+/// [Version Controlled: OpenCL 2.0+] See module docs for more info.
+#[cfg(feature = "opencl_version_2_0")]
+pub unsafe fn set_kernel_arg_svm_pointer(kernel: &Kernel, index: u32, svm_ptr: *const c_void)
+        -> OclCoreResult<()>
+{
+    let err = ffi::clSetKernelArgSVMPointer(kernel.as_ptr(), index, svm_ptr);
+
+    if err != Status::CL_SUCCESS as i32 {
+        let name = get_kernel_name(kernel)?;
+        eval_errcode(err, (), "clSetKernelArgSVMPointer", Some(name))
+    } else {
+        Ok(())
+    }
+}
+
 /// Get kernel info.
 pub fn get_kernel_info(obj: &Kernel, request: KernelInfo) -> OclCoreResult<KernelInfoResult> {
     let mut result_size: size_t = 0;
@@ -2380,6 +2791,25 @@ pub fn set_user_event_status<'e,E: ClEventPtrRef<'e>>(event: &'e E,
     }
 }
 
+/// Sets the execution status of a user event object to an error (negative)
+/// value, causing all commands waiting on it to terminate.
+///
+/// `error_code` must be negative.
+///
+pub fn set_user_event_error<'e,E: ClEventPtrRef<'e>>(event: &'e E, error_code: cl_int)
+            -> OclCoreResult<()>
+{
+    if error_code >= 0 {
+        return Err(format!("::set_user_event_error: The error code ({}) must be negative.",
+            error_code).into());
+    }
+
+    unsafe {
+        eval_errcode(ffi::clSetUserEventStatus(*event.as_ptr_ref(), error_code),
+            (), "clSetUserEventStatus", None::<String>)
+    }
+}
+
 /// Sets a callback function which is called as soon as the `callback_trigger`
 /// status is reached.
 ///
@@ -2714,6 +3144,11 @@ pub fn enqueue_fill_buffer<T, M, En, Ewl>(
             ApiFunction::EnqueueFillBuffer)?;
 
     let pattern_size = mem::size_of::<T>();
+
+    if !pattern_size.is_power_of_two() || pattern_size > 128 {
+        return Err(ApiWrapperError::EnqueueFillBufferInvalidPatternSize(pattern_size).into());
+    }
+
     let offset_bytes = offset * mem::size_of::<T>();
     let size_bytes = len * mem::size_of::<T>();
 
@@ -2834,10 +3269,13 @@ pub fn enqueue_acquire_gl_objects<En, Ewl>(
     let (wait_list_len, wait_list_ptr, new_event_ptr) =
         resolve_event_ptrs(wait_list, new_event);
 
+    // An empty list must be passed as null:
+    let buffers_ptr = if buffers.is_empty() { ptr::null() } else { buffers.as_ptr() as *const cl_mem };
+
     let errcode = unsafe { clEnqueueAcquireGLObjects(
         command_queue.as_ptr(),
         buffers.len() as u32,
-        buffers.as_ptr() as *const cl_mem,
+        buffers_ptr,
         wait_list_len,
         wait_list_ptr,
         new_event_ptr
@@ -2863,10 +3301,13 @@ pub fn enqueue_release_gl_objects<En, Ewl>(
     let (wait_list_len, wait_list_ptr, new_event_ptr) =
         resolve_event_ptrs(wait_list, new_event);
 
+    // An empty list must be passed as null:
+    let buffers_ptr = if buffers.is_empty() { ptr::null() } else { buffers.as_ptr() as *const cl_mem };
+
     let errcode = unsafe { clEnqueueReleaseGLObjects(
         command_queue.as_ptr(),
         buffers.len() as u32,
-        buffers.as_ptr() as *const cl_mem,
+        buffers_ptr,
         wait_list_len,
         wait_list_ptr,
         new_event_ptr
@@ -3087,10 +3528,11 @@ pub fn enqueue_copy_image_to_buffer<T, M, En, Ewl>(
 
 /// Enqueues a command to copy a buffer object to an image object.
 ///
+/// Returns an error if `src_buffer` does not contain enough data, starting
+/// at `src_offset`, to fill `region` of `dst_image`.
+///
 /// [SDK Docs](https://www.khronos.org/registry/cl/sdk/1.2/docs/man/xhtml/clEnqueueCopyBufferToImage.html)
 ///
-// [UNTESTED]
-//
 pub fn enqueue_copy_buffer_to_image<T, M, En, Ewl>(
             command_queue: &CommandQueue,
             src_buffer: M,
@@ -3105,6 +3547,22 @@ pub fn enqueue_copy_buffer_to_image<T, M, En, Ewl>(
 {
     let src_offset_bytes = src_offset * mem::size_of::<T>();
 
+    // Not all drivers check that the source buffer is large enough:
+    let element_size = match get_image_info(dst_image.as_mem(), ImageInfo::ElementSize)? {
+        ImageInfoResult::ElementSize(s) => s,
+        _ => unreachable!(),
+    };
+    let buffer_len_bytes = match get_mem_object_info(src_buffer.as_mem(), MemInfo::Size)? {
+        MemInfoResult::Size(s) => s,
+        _ => unreachable!(),
+    };
+    let required_bytes = region[0] * region[1] * region[2] * element_size;
+
+    if src_offset_bytes > buffer_len_bytes || required_bytes > buffer_len_bytes - src_offset_bytes {
+        return Err(ApiWrapperError::EnqueueCopyBufferToImageSrcTooSmall { src_offset_bytes,
+            required_bytes, buffer_len_bytes }.into());
+    }
+
     let (wait_list_len, wait_list_ptr, new_event_ptr)
         = resolve_event_ptrs(wait_list, new_event);
 
@@ -3463,19 +3921,64 @@ pub unsafe fn enqueue_task<En: ClNullEventPtr, Ewl: ClWaitListPtr>(
     eval_errcode(errcode, (), "clEnqueueTask", kernel_name)
 }
 
-/// [UNIMPLEMENTED: Please implement me]
-pub fn enqueue_native_kernel() -> OclCoreResult<()> {
-    // ffi::clEnqueueNativeKernel(command_queue: cl_command_queue,
-    //                          user_func: extern fn (*mut c_void),
-    //                          args: *mut c_void,
-    //                          cb_args: size_t,
-    //                          num_mem_objects: cl_uint,
-    //                          mem_list: *const cl_mem,
-    //                          args_mem_loc: *const *const c_void,
-    //                          num_events_in_wait_list: cl_uint,
-    //                          event_wait_list: *const cl_event,
-    //                          event: *mut cl_event) -> cl_int;
-    unimplemented!();
+/// Enqueues a command to execute a host function (closure) as a native
+/// kernel.
+///
+/// `mem_objects` are made available to (and synchronized with) the command
+/// in the same manner as memory objects passed as arguments to any other
+/// kernel. The closure is called exactly once and dropped afterwards.
+///
+/// The device associated with `command_queue` must support
+/// `DeviceExecCapabilities::NATIVE_KERNEL` (see
+/// `DeviceInfo::ExecutionCapabilities`).
+///
+/// [SDK Docs](https://www.khronos.org/registry/cl/sdk/1.2/docs/man/xhtml/clEnqueueNativeKernel.html)
+pub fn enqueue_native_kernel<F, En, Ewl>(
+            command_queue: &CommandQueue,
+            func: F,
+            mem_objects: &[Mem],
+            wait_list: Option<Ewl>,
+            new_event: Option<En>,
+        ) -> OclCoreResult<()>
+        where F: FnMut() + Send + 'static, En: ClNullEventPtr, Ewl: ClWaitListPtr
+{
+    let (wait_list_len, wait_list_ptr, new_event_ptr) =
+        resolve_event_ptrs(wait_list, new_event);
+
+    let func: NativeKernelFn = Box::new(func);
+    let func_ptr = Box::into_raw(Box::new(func));
+
+    // The argument block holds the closure pointer followed by one slot per
+    // memory object. The runtime copies the block and replaces each memory
+    // object slot with a pointer to the corresponding global memory.
+    let mem_list: Vec<cl_mem> = mem_objects.iter().map(|m| m.as_ptr()).collect();
+    let mut args: Vec<*mut c_void> = Vec::with_capacity(1 + mem_list.len());
+    args.push(func_ptr as *mut c_void);
+    args.extend(mem_list.iter().map(|&m| m as *mut c_void));
+
+    let args_mem_loc: Vec<*const c_void> = (1..args.len())
+        .map(|i| &args[i] as *const *mut c_void as *const c_void)
+        .collect();
+
+    let errcode = unsafe { ffi::clEnqueueNativeKernel(
+        command_queue.as_ptr(),
+        Some(_call_native_kernel),
+        args.as_mut_ptr() as *mut c_void,
+        args.len() * mem::size_of::<*mut c_void>(),
+        mem_list.len() as cl_uint,
+        if mem_list.is_empty() { ptr::null() } else { mem_list.as_ptr() },
+        if args_mem_loc.is_empty() { ptr::null() } else { args_mem_loc.as_ptr() },
+        wait_list_len,
+        wait_list_ptr,
+        new_event_ptr,
+    ) };
+
+    if errcode != 0 {
+        // The callback will never run, reclaim the closure:
+        unsafe { let _ = Box::from_raw(func_ptr); }
+    }
+
+    eval_errcode(errcode, (), "clEnqueueNativeKernel", None::<String>)
 }
 
 /// Enqueues a marker command which waits for either a list of events to
@@ -3611,6 +4114,255 @@ pub unsafe fn get_extension_function_address_for_platform(
     }
 }
 
+/// Enqueues a command which maps a region of a shared virtual memory buffer
+/// for access by the host.
+///
+/// Host access to a coarse-grained SVM buffer is only valid between a
+/// completed map and its corresponding `::enqueue_svm_unmap`.
+///
+/// ## Safety
+///
+/// `svm_ptr` and `size` must describe a region of a buffer allocated with
+/// `::svm_alloc` using the context associated with `command_queue`.
+///
+/// [Version Controlled: OpenCL 2.0+] See module docs for more info.
+#[cfg(feature = "opencl_version_2_0")]
+pub unsafe fn enqueue_svm_map<En, Ewl>(
+            command_queue: &CommandQueue,
+            block: bool,
+            map_flags: MapFlags,
+            svm_ptr: *mut c_void,
+            size: usize,
+            wait_list: Option<Ewl>,
+            new_event: Option<En>,
+        ) -> OclCoreResult<()>
+        where En: ClNullEventPtr, Ewl: ClWaitListPtr
+{
+    let (wait_list_len, wait_list_ptr, new_event_ptr) =
+        resolve_event_ptrs(wait_list, new_event);
+
+    let errcode = ffi::clEnqueueSVMMap(
+        command_queue.as_ptr(),
+        block as cl_uint,
+        map_flags.bits(),
+        svm_ptr,
+        size,
+        wait_list_len,
+        wait_list_ptr,
+        new_event_ptr,
+    );
+
+    eval_errcode(errcode, (), "clEnqueueSVMMap", None::<String>)
+}
+
+/// Enqueues a command which unmaps a shared virtual memory buffer region
+/// previously mapped with `::enqueue_svm_map`.
+///
+/// ## Safety
+///
+/// `svm_ptr` must have been previously mapped using `::enqueue_svm_map`.
+///
+/// [Version Controlled: OpenCL 2.0+] See module docs for more info.
+#[cfg(feature = "opencl_version_2_0")]
+pub unsafe fn enqueue_svm_unmap<En, Ewl>(
+            command_queue: &CommandQueue,
+            svm_ptr: *mut c_void,
+            wait_list: Option<Ewl>,
+            new_event: Option<En>,
+        ) -> OclCoreResult<()>
+        where En: ClNullEventPtr, Ewl: ClWaitListPtr
+{
+    let (wait_list_len, wait_list_ptr, new_event_ptr) =
+        resolve_event_ptrs(wait_list, new_event);
+
+    let errcode = ffi::clEnqueueSVMUnmap(
+        command_queue.as_ptr(),
+        svm_ptr,
+        wait_list_len,
+        wait_list_ptr,
+        new_event_ptr,
+    );
+
+    eval_errcode(errcode, (), "clEnqueueSVMUnmap", None::<String>)
+}
+
+//============================================================================
+//========================== D3D11 Sharing APIs ==============================
+//============================================================================
+
+/// Returns the platform associated with `context`, falling back to the
+/// platform of its first device when none was specified upon creation.
+#[cfg(all(feature = "d3d11_interop", windows))]
+fn d3d11_context_platform<C>(context: C) -> OclCoreResult<PlatformId>
+        where C: ClContextPtr
+{
+    if let Some(platform) = get_context_platform(context)? {
+        return Ok(platform);
+    }
+
+    let device = match get_context_info(context, ContextInfo::Devices)? {
+        ContextInfoResult::Devices(devices) => match devices.first() {
+            Some(&device) => device,
+            None => return Err(ApiWrapperError::CreateContextNoDevicesSpecified.into()),
+        },
+        _ => unreachable!(),
+    };
+
+    match get_device_info(&device, DeviceInfo::Platform)? {
+        DeviceInfoResult::Platform(platform) => Ok(platform),
+        _ => unreachable!(),
+    }
+}
+
+/// Resolves the `cl_khr_d3d11_sharing` extension function named `fn_name`
+/// for the platform associated with `context`.
+///
+/// `F` must be the matching `ffi::clXxxD3D11KHR_fn` type.
+#[cfg(all(feature = "d3d11_interop", windows))]
+unsafe fn d3d11_extension_fn<C, F>(context: C, fn_name: &str) -> OclCoreResult<F>
+        where C: ClContextPtr, F: Copy
+{
+    debug_assert!(mem::size_of::<F>() == mem::size_of::<*mut c_void>());
+    let platform = d3d11_context_platform(context)?;
+
+    match get_platform_info(&platform, PlatformInfo::Extensions)? {
+        PlatformInfoResult::Extensions(ref exts) if exts.contains(CL_D3D11_SHARING_EXT) => (),
+        _ => return Err(ApiWrapperError::D3d11SharingUnsupported.into()),
+    }
+
+    let fn_ptr = get_extension_function_address_for_platform(&platform, fn_name, None)?;
+    Ok(mem::transmute_copy(&fn_ptr))
+}
+
+/// Creates an OpenCL buffer object from a Direct3D 11 buffer
+/// (`ID3D11Buffer*`).
+///
+/// The context must have been created with an associated Direct3D 11
+/// device (`ContextProperties::d3d11_device`).
+///
+/// ## Safety
+///
+/// `resource` must be a valid `ID3D11Buffer*` created by the Direct3D 11
+/// device associated with `context`.
+///
+/// [Feature: `d3d11_interop`, Windows only]
+#[cfg(all(feature = "d3d11_interop", windows))]
+pub unsafe fn create_from_d3d11_buffer<C>(
+            context: C,
+            flags: MemFlags,
+            resource: *mut c_void,
+        ) -> OclCoreResult<Mem>
+        where C: ClContextPtr
+{
+    let create_fn: ffi::clCreateFromD3D11BufferKHR_fn =
+        d3d11_extension_fn(context, "clCreateFromD3D11BufferKHR")?;
+    let mut errcode: cl_int = 0;
+
+    let buf_ptr = create_fn(
+        context.as_ptr(),
+        flags.bits() as cl_mem_flags,
+        resource,
+        &mut errcode);
+
+    eval_errcode(errcode, buf_ptr, "clCreateFromD3D11BufferKHR", None::<String>)
+        .map(|ptr| Mem::from_raw_create_ptr(ptr))
+}
+
+/// Creates an OpenCL 2D image object from a subresource of a Direct3D 11
+/// 2D texture (`ID3D11Texture2D*`).
+///
+/// ## Safety
+///
+/// `resource` must be a valid `ID3D11Texture2D*` created by the Direct3D 11
+/// device associated with `context`.
+///
+/// [Feature: `d3d11_interop`, Windows only]
+#[cfg(all(feature = "d3d11_interop", windows))]
+pub unsafe fn create_from_d3d11_texture2d<C>(
+            context: C,
+            flags: MemFlags,
+            resource: *mut c_void,
+            subresource: u32,
+        ) -> OclCoreResult<Mem>
+        where C: ClContextPtr
+{
+    let create_fn: ffi::clCreateFromD3D11Texture2DKHR_fn =
+        d3d11_extension_fn(context, "clCreateFromD3D11Texture2DKHR")?;
+    let mut errcode: cl_int = 0;
+
+    let image_ptr = create_fn(
+        context.as_ptr(),
+        flags.bits() as cl_mem_flags,
+        resource,
+        subresource,
+        &mut errcode);
+
+    eval_errcode(errcode, image_ptr, "clCreateFromD3D11Texture2DKHR", None::<String>)
+        .map(|ptr| Mem::from_raw_create_ptr(ptr))
+}
+
+/// Acquires OpenCL memory objects (buffers and images) created from
+/// Direct3D 11 resources.
+///
+/// [Feature: `d3d11_interop`, Windows only]
+#[cfg(all(feature = "d3d11_interop", windows))]
+pub fn enqueue_acquire_d3d11_objects<En, Ewl>(
+            command_queue: &CommandQueue,
+            mem_objects: &[Mem],
+            wait_list: Option<Ewl>,
+            new_event: Option<En>,
+        ) -> OclCoreResult<()>
+        where En: ClNullEventPtr, Ewl: ClWaitListPtr
+{
+    let acquire_fn: ffi::clEnqueueAcquireD3D11ObjectsKHR_fn = unsafe {
+        d3d11_extension_fn(command_queue, "clEnqueueAcquireD3D11ObjectsKHR")?
+    };
+
+    let (wait_list_len, wait_list_ptr, new_event_ptr) =
+        resolve_event_ptrs(wait_list, new_event);
+
+    let errcode = acquire_fn(
+        command_queue.as_ptr(),
+        mem_objects.len() as u32,
+        mem_objects.as_ptr() as *const cl_mem,
+        wait_list_len,
+        wait_list_ptr,
+        new_event_ptr
+    );
+    eval_errcode(errcode, (), "clEnqueueAcquireD3D11ObjectsKHR", None::<String>)
+}
+
+/// Releases OpenCL memory objects (buffers and images) created from
+/// Direct3D 11 resources.
+///
+/// [Feature: `d3d11_interop`, Windows only]
+#[cfg(all(feature = "d3d11_interop", windows))]
+pub fn enqueue_release_d3d11_objects<En, Ewl>(
+            command_queue: &CommandQueue,
+            mem_objects: &[Mem],
+            wait_list: Option<Ewl>,
+            new_event: Option<En>,
+        ) -> OclCoreResult<()>
+        where En: ClNullEventPtr, Ewl: ClWaitListPtr
+{
+    let release_fn: ffi::clEnqueueReleaseD3D11ObjectsKHR_fn = unsafe {
+        d3d11_extension_fn(command_queue, "clEnqueueReleaseD3D11ObjectsKHR")?
+    };
+
+    let (wait_list_len, wait_list_ptr, new_event_ptr) =
+        resolve_event_ptrs(wait_list, new_event);
+
+    let errcode = release_fn(
+        command_queue.as_ptr(),
+        mem_objects.len() as u32,
+        mem_objects.as_ptr() as *const cl_mem,
+        wait_list_len,
+        wait_list_ptr,
+        new_event_ptr
+    );
+    eval_errcode(errcode, (), "clEnqueueReleaseD3D11ObjectsKHR", None::<String>)
+}
+
 //============================================================================
 //============================================================================
 //=========================== DERIVED FUNCTIONS ==============================
@@ -3709,6 +4461,17 @@ pub fn wait_for_event<'e, E: ClEventPtrRef<'e>>(event: &'e E) -> OclCoreResult<(
 
 /// Returns the status of `event`.
 pub fn event_status<'e, E: ClEventPtrRef<'e>>(event: &'e E) -> OclCoreResult<CommandExecutionStatus> {
+    let status_int = try!(event_status_code(event));
+
+    CommandExecutionStatus::from_i32(status_int).ok_or_else(|| OclCoreError::from("Error converting \
+        'clGetEventInfo' status output."))
+}
+
+/// Returns the raw execution status of `event`.
+///
+/// Unlike `::event_status`, a negative value (the error code of a command
+/// which terminated abnormally) is returned as-is rather than as an error.
+pub fn event_status_code<'e, E: ClEventPtrRef<'e>>(event: &'e E) -> OclCoreResult<cl_int> {
     let mut status_int: cl_int = 0;
 
     let errcode = unsafe {
@@ -3720,10 +4483,7 @@ pub fn event_status<'e, E: ClEventPtrRef<'e>>(event: &'e E) -> OclCoreResult<Com
             ptr::null_mut(),
         )
     };
-    try!(eval_errcode(errcode, (), "clGetEventInfo", None::<String>));
-
-    CommandExecutionStatus::from_i32(status_int).ok_or_else(|| OclCoreError::from("Error converting \
-        'clGetEventInfo' status output."))
+    eval_errcode(errcode, status_int, "clGetEventInfo", None::<String>)
 }
 
 /// Returns true if an event is complete, false if not complete.
@@ -3753,19 +4513,25 @@ pub fn event_is_complete<'e, E: ClEventPtrRef<'e>>(event: &'e E) -> OclCoreResul
 
 
 
+/// Reference counts above this value are treated as implausible by
+/// `verify_context`.
+const VERIFY_CONTEXT_REF_COUNT_MAX: cl_uint = 1 << 24;
+
 /// Verifies that the `context` is in fact a context object pointer.
 ///
+/// Queries `CL_CONTEXT_REFERENCE_COUNT` (a small, fixed-size result) and
+/// treats any error as an invalid context.
+///
 /// ## Assumptions
 ///
 /// Some (most?/all?) OpenCL implementations do not correctly error if non-
-/// context pointers are passed. This function relies on the fact that passing
-/// the `CL_CONTEXT_DEVICES` as the `param_name` to `clGetContextInfo` will
-/// (at least on my AMD implementation) often return a huge result size if
-/// `context` is not actually a `cl_context` pointer due to the fact that it's
-/// reading from some random memory location on non-context structs. Also
-/// checks for zero because a context must have at least one device (true?).
-/// Should probably choose a value lower than 10kB because it seems unlikely
-/// any result would be that big but w/e.
+/// context pointers are passed and may instead read from some random memory
+/// location on non-context structs. A reference count of zero (a released
+/// context) or one which is implausibly large is therefore treated as a
+/// spurious success, in which case this function falls back to querying the
+/// size of the `CL_CONTEXT_DEVICES` result, which (at least on some AMD
+/// implementations) is often huge or zero for invalid contexts (see
+/// `get_context_info`).
 ///
 /// [UPDATE]: This function may no longer be necessary now that the core
 /// pointers have wrappers but it still prevents a hard to track down bug so
@@ -3775,14 +4541,26 @@ pub fn event_is_complete<'e, E: ClEventPtrRef<'e>>(event: &'e E) -> OclCoreResul
 pub fn verify_context<C>(context: C) -> OclCoreResult<()>
         where C: ClContextPtr
 {
-    // context_info(context, ffi::CL_CONTEXT_REFERENCE_COUNT)
     if cfg!(release) {
-        Ok(())
+        return Ok(());
+    }
+
+    let mut ref_count: cl_uint = 0;
+
+    let errcode = unsafe { ffi::clGetContextInfo(
+        context.as_ptr(),
+        ffi::CL_CONTEXT_REFERENCE_COUNT,
+        mem::size_of::<cl_uint>() as size_t,
+        &mut ref_count as *mut cl_uint as *mut c_void,
+        0 as *mut size_t,
+    ) };
+
+    eval_errcode(errcode, (), "clGetContextInfo", Some("CL_CONTEXT_REFERENCE_COUNT"))?;
+
+    if ref_count == 0 || ref_count > VERIFY_CONTEXT_REF_COUNT_MAX {
+        get_context_info_unparsed(context, ContextInfo::Devices).map(|_| ())
     } else {
-        match get_context_info(context, ContextInfo::Devices) {
-            Err(err) => Err(err),
-            _ => Ok(()),
-        }
+        Ok(())
     }
 }
 
@@ -132,7 +132,7 @@ pub use self::types::structs::{self, OpenclVersion, ContextProperties, ImageForm
 
 pub use self::types::enums::{EmptyInfoResultError, PlatformInfoResult, DeviceInfoResult,
     ContextInfoResult, GlContextInfoResult, CommandQueueInfoResult, MemInfoResult, ImageInfoResult,
-    SamplerInfoResult, ProgramInfoResult, ProgramBuildInfoResult, KernelInfoResult,
+    PipeInfoResult, SamplerInfoResult, ProgramInfoResult, ProgramBuildInfoResult, KernelInfoResult,
     KernelArgInfoResult, KernelWorkGroupInfoResult, EventInfoResult, ProfilingInfoResult};
 
 pub use self::functions::{get_platform_ids, get_platform_info, get_device_ids, get_device_info,
@@ -144,21 +144,24 @@ pub use self::functions::{get_platform_ids, get_platform_info, get_device_ids, g
     set_mem_object_destructor_callback, create_sampler, retain_sampler, release_sampler,
     get_sampler_info, create_program_with_source, create_program_with_binary,
     create_program_with_built_in_kernels, retain_program, release_program, build_program,
-    compile_program, link_program, create_build_program, get_program_info, get_program_build_info,
+    compile_program, link_program, unload_platform_compiler, create_build_program,
+    get_program_info, get_program_build_info,
     create_kernel, create_kernels_in_program, retain_kernel, release_kernel, set_kernel_arg,
     get_kernel_info, get_kernel_arg_info, get_kernel_work_group_info, wait_for_events,
     get_event_info, create_user_event, retain_event, release_event, set_user_event_status,
-    set_event_callback, get_event_profiling_info, flush, finish, enqueue_read_buffer,
-    enqueue_read_buffer_rect, enqueue_write_buffer, enqueue_write_buffer_rect, enqueue_copy_buffer,
-    enqueue_copy_buffer_rect,
+    set_user_event_error, set_event_callback, get_event_profiling_info, flush, finish,
+    enqueue_read_buffer, enqueue_read_buffer_rect, enqueue_write_buffer, enqueue_write_buffer_rect,
+    enqueue_copy_buffer, enqueue_copy_buffer_rect,
     enqueue_read_image, enqueue_write_image, enqueue_fill_image, enqueue_copy_image,
     enqueue_copy_image_to_buffer, enqueue_copy_buffer_to_image, enqueue_map_buffer,
     enqueue_map_image, enqueue_unmap_mem_object, enqueue_migrate_mem_objects, enqueue_kernel,
     enqueue_task, enqueue_native_kernel, enqueue_marker_with_wait_list,
     enqueue_barrier_with_wait_list, get_extension_function_address_for_platform, wait_for_event,
-    event_status, default_platform_idx, program_build_err, verify_context, default_platform,
+    event_status, event_status_code, default_platform_idx, program_build_err, verify_context,
+    default_platform,
     default_device_type, device_versions, event_is_complete, _dummy_event_callback,
-    _complete_user_event, get_context_platform, get_device_info_raw};
+    _complete_user_event, get_context_platform, get_device_info_raw, set_release_error_hook,
+    ReleaseErrorHook};
 
 #[cfg(not(feature="opencl_vendor_mesa"))]
 pub use self::functions::{
@@ -167,6 +170,12 @@ pub use self::functions::{
     create_from_gl_texture_2d, create_from_gl_texture_3d, enqueue_fill_buffer,
     enqueue_acquire_gl_objects, enqueue_release_gl_objects};
 
+#[cfg(all(feature = "d3d11_interop", windows))]
+pub use self::functions::{create_from_d3d11_buffer, create_from_d3d11_texture2d,
+    enqueue_acquire_d3d11_objects, enqueue_release_d3d11_objects};
+
+pub use self::types::half::{f16, Half4, Half8};
+
 pub use traits::{OclPrm, OclNum, OclScl};
 
 #[cfg(feature = "ocl-core-vector")]
@@ -187,8 +196,12 @@ pub use self::vector::{
 #[cfg(feature = "ocl-core-vector")]
 pub use traits::OclVec;
 
+#[cfg(feature = "opencl_version_2_0")]
+pub use self::functions::{create_command_queue_with_properties, svm_alloc, svm_free,
+    set_kernel_arg_svm_pointer, enqueue_svm_map, enqueue_svm_unmap, create_pipe, get_pipe_info};
+
 #[cfg(feature = "opencl_version_2_1")]
-pub use self::functions::{create_program_with_il};
+pub use self::functions::{create_program_with_il, get_device_and_host_timer, get_host_timer};
 
 
 
@@ -371,6 +384,28 @@ impl Default for DeviceType {
     #[inline] fn default() -> DeviceType { DeviceType::ALL }
 }
 
+impl std::fmt::Display for DeviceType {
+    /// Formats the set flags as, for example, `GPU | DEFAULT`.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.contains(DeviceType::ALL) { return write!(f, "ALL"); }
+
+        let names = [(DeviceType::CPU, "CPU"), (DeviceType::GPU, "GPU"),
+            (DeviceType::ACCELERATOR, "ACCELERATOR"), (DeviceType::CUSTOM, "CUSTOM"),
+            (DeviceType::DEFAULT, "DEFAULT")];
+
+        let mut set = names.iter().filter(|&&(flag, _)| self.contains(flag)).map(|&(_, n)| n);
+
+        match set.next() {
+            Some(first) => {
+                write!(f, "{}", first)?;
+                for name in set { write!(f, " | {}", name)?; }
+                Ok(())
+            },
+            None => write!(f, "NONE"),
+        }
+    }
+}
+
 pub const DEVICE_TYPE_DEFAULT: DeviceType = DeviceType::DEFAULT;
 pub const DEVICE_TYPE_CPU: DeviceType = DeviceType::CPU;
 pub const DEVICE_TYPE_GPU: DeviceType = DeviceType::GPU;
@@ -479,6 +514,8 @@ bitflags! {
         const HOST_WRITE_ONLY = 1 << 7;
         const HOST_READ_ONLY = 1 << 8;
         const HOST_NO_ACCESS = 1 << 9;
+        const SVM_FINE_GRAIN_BUFFER = 1 << 10;
+        const SVM_ATOMICS = 1 << 11;
     }
 }
 
@@ -493,6 +530,54 @@ impl MemFlags {
     #[inline] pub fn host_write_only(self) -> MemFlags { self | MemFlags::HOST_WRITE_ONLY }
     #[inline] pub fn host_read_only(self) -> MemFlags { self | MemFlags::HOST_READ_ONLY }
     #[inline] pub fn host_no_access(self) -> MemFlags { self | MemFlags::HOST_NO_ACCESS }
+    #[inline] pub fn svm_fine_grain_buffer(self) -> MemFlags { self |
+        MemFlags::SVM_FINE_GRAIN_BUFFER }
+    #[inline] pub fn svm_atomics(self) -> MemFlags { self | MemFlags::SVM_ATOMICS }
+
+    /// Returns `READ_WRITE | ALLOC_HOST_PTR`.
+    #[inline] pub fn read_write_alloc() -> MemFlags { MemFlags::new().read_write().alloc_host_ptr() }
+    /// Returns `READ_ONLY | ALLOC_HOST_PTR`.
+    #[inline] pub fn read_only_alloc() -> MemFlags { MemFlags::new().read_only().alloc_host_ptr() }
+    /// Returns `WRITE_ONLY | ALLOC_HOST_PTR`.
+    #[inline] pub fn write_only_alloc() -> MemFlags { MemFlags::new().write_only().alloc_host_ptr() }
+    /// Returns `READ_ONLY | HOST_WRITE_ONLY`, suitable for input buffers only
+    /// ever written by the host.
+    #[inline] pub fn read_only_host_write() -> MemFlags {
+        MemFlags::new().read_only().host_write_only() }
+    /// Returns `WRITE_ONLY | HOST_READ_ONLY`, suitable for output buffers only
+    /// ever read by the host.
+    #[inline] pub fn write_only_host_read() -> MemFlags {
+        MemFlags::new().write_only().host_read_only() }
+
+    /// Returns an error if any mutually exclusive flags are set together.
+    ///
+    /// At most one of `READ_WRITE`, `WRITE_ONLY`, and `READ_ONLY` may be set,
+    /// `USE_HOST_PTR` may not be combined with `ALLOC_HOST_PTR` or
+    /// `COPY_HOST_PTR`, and at most one of `HOST_WRITE_ONLY`,
+    /// `HOST_READ_ONLY`, and `HOST_NO_ACCESS` may be set.
+    pub fn verify(&self) -> Result<()> {
+        let exclusive_sets: [(&str, MemFlags); 2] = [
+            ("READ_WRITE, WRITE_ONLY, READ_ONLY",
+                MemFlags::READ_WRITE | MemFlags::WRITE_ONLY | MemFlags::READ_ONLY),
+            ("HOST_WRITE_ONLY, HOST_READ_ONLY, HOST_NO_ACCESS",
+                MemFlags::HOST_WRITE_ONLY | MemFlags::HOST_READ_ONLY | MemFlags::HOST_NO_ACCESS),
+        ];
+
+        for &(names, set) in exclusive_sets.iter() {
+            if (*self & set).bits().count_ones() > 1 {
+                return Err(format!("MemFlags::verify: Only one of '{}' may be set \
+                    (flags: {:?}).", names, self).into());
+            }
+        }
+
+        if self.contains(MemFlags::USE_HOST_PTR) &&
+                self.intersects(MemFlags::ALLOC_HOST_PTR | MemFlags::COPY_HOST_PTR) {
+            return Err(format!("MemFlags::verify: 'USE_HOST_PTR' cannot be combined with \
+                'ALLOC_HOST_PTR' or 'COPY_HOST_PTR' (flags: {:?}).", self).into());
+        }
+
+        Ok(())
+    }
 }
 
 impl Default for MemFlags {
@@ -509,6 +594,8 @@ pub const MEM_COPY_HOST_PTR: MemFlags = MemFlags::COPY_HOST_PTR;
 pub const MEM_HOST_WRITE_ONLY: MemFlags = MemFlags::HOST_WRITE_ONLY;
 pub const MEM_HOST_READ_ONLY: MemFlags = MemFlags::HOST_READ_ONLY;
 pub const MEM_HOST_NO_ACCESS: MemFlags = MemFlags::HOST_NO_ACCESS;
+pub const MEM_SVM_FINE_GRAIN_BUFFER: MemFlags = MemFlags::SVM_FINE_GRAIN_BUFFER;
+pub const MEM_SVM_ATOMICS: MemFlags = MemFlags::SVM_ATOMICS;
 
 
 bitflags! {
@@ -534,8 +621,15 @@ bitflags! {
 
 impl MapFlags {
     #[inline] pub fn new() -> MapFlags { MapFlags::empty() }
+    /// Adds `READ`.
     #[inline] pub fn read(self) -> MapFlags { self | MapFlags::READ }
+    /// Adds `WRITE`. The mapped region contains the current contents of the
+    /// memory object, making it suitable for modifying only part of it.
     #[inline] pub fn write(self) -> MapFlags { self | MapFlags::WRITE }
+    /// Adds `WRITE_INVALIDATE_REGION`. The prior contents of the mapped
+    /// region are discarded (not copied to the host) and are undefined until
+    /// overwritten. Faster than `::write` when the entire region will be
+    /// overwritten.
     #[inline] pub fn write_invalidate_region(self) -> MapFlags { self |
         MapFlags::WRITE_INVALIDATE_REGION }
 }
@@ -653,8 +747,27 @@ enum_from_primitive! {
         CL_INVALID_DEVICE_PARTITION_COUNT               = -68,
         CL_INVALID_PIPE_SIZE                            = -69,
         CL_INVALID_DEVICE_QUEUE                         = -70,
+        CL_INVALID_SPEC_ID                              = -71,
+        CL_MAX_SIZE_RESTRICTION_EXCEEDED                = -72,
         CL_INVALID_GL_SHAREGROUP_REFERENCE_KHR          = -1000,
         CL_PLATFORM_NOT_FOUND_KHR                       = -1001,
+        CL_INVALID_D3D10_DEVICE_KHR                     = -1002,
+        CL_INVALID_D3D10_RESOURCE_KHR                   = -1003,
+        CL_D3D10_RESOURCE_ALREADY_ACQUIRED_KHR          = -1004,
+        CL_D3D10_RESOURCE_NOT_ACQUIRED_KHR              = -1005,
+        CL_INVALID_D3D11_DEVICE_KHR                     = -1006,
+        CL_INVALID_D3D11_RESOURCE_KHR                   = -1007,
+        CL_D3D11_RESOURCE_ALREADY_ACQUIRED_KHR          = -1008,
+        CL_D3D11_RESOURCE_NOT_ACQUIRED_KHR              = -1009,
+        CL_INVALID_DX9_MEDIA_ADAPTER_KHR                = -1010,
+        CL_INVALID_DX9_MEDIA_SURFACE_KHR                = -1011,
+        CL_DX9_MEDIA_SURFACE_ALREADY_ACQUIRED_KHR       = -1012,
+        CL_DX9_MEDIA_SURFACE_NOT_ACQUIRED_KHR           = -1013,
+        CL_DEVICE_PARTITION_FAILED_EXT                  = -1057,
+        CL_INVALID_PARTITION_COUNT_EXT                  = -1058,
+        CL_INVALID_PARTITION_NAME_EXT                   = -1059,
+        CL_EGL_RESOURCE_NOT_ACQUIRED_KHR                = -1092,
+        CL_INVALID_EGL_OBJECT_KHR                       = -1093,
         CL_NV_INVALID_MEM_ACCESS                        = -9999,
     }
 }
@@ -665,6 +778,44 @@ impl std::fmt::Display for Status {
     }
 }
 
+impl Status {
+    /// Returns a short, human-readable explanation of the more frequently
+    /// encountered status codes.
+    pub fn description(&self) -> Option<&'static str> {
+        match *self {
+            Status::CL_DEVICE_NOT_FOUND => Some("No OpenCL devices matching the requested \
+                device type were found."),
+            Status::CL_DEVICE_NOT_AVAILABLE => Some("The device is currently unavailable \
+                (it may be in use by another process or disabled)."),
+            Status::CL_MEM_OBJECT_ALLOCATION_FAILURE => Some("The device failed to allocate \
+                memory for a buffer or image. Try reducing its size."),
+            Status::CL_OUT_OF_RESOURCES => Some("The device ran out of resources. This is \
+                often caused by out-of-bounds memory accesses within a kernel or by \
+                exceeding the device's local memory or register limits."),
+            Status::CL_OUT_OF_HOST_MEMORY => Some("The OpenCL implementation failed to \
+                allocate host memory."),
+            Status::CL_BUILD_PROGRAM_FAILURE => Some("The program failed to build. Check the \
+                program build log for details."),
+            Status::CL_INVALID_KERNEL_ARGS => Some("One or more kernel arguments have not \
+                been set."),
+            Status::CL_INVALID_WORK_GROUP_SIZE => Some("The local work size is invalid. It \
+                must evenly divide the global work size and must not exceed the maximum \
+                work group size for the kernel and device."),
+            Status::CL_INVALID_WORK_ITEM_SIZE => Some("A local work size dimension exceeds \
+                the maximum work item size for the device."),
+            Status::CL_INVALID_GLOBAL_WORK_SIZE => Some("The global work size is zero or \
+                exceeds the range supported by the device."),
+            Status::CL_INVALID_BUFFER_SIZE => Some("The requested buffer size is zero or \
+                exceeds the maximum allocation size for the device."),
+            Status::CL_INVALID_GL_SHAREGROUP_REFERENCE_KHR => Some("The OpenGL context or \
+                share group is invalid or not associated with an OpenCL context."),
+            Status::CL_PLATFORM_NOT_FOUND_KHR => Some("No OpenCL platforms were found. Ensure \
+                that an OpenCL driver (ICD) is installed and registered."),
+            _ => None,
+        }
+    }
+}
+
 
 enum_from_primitive! {
     /// specify the texture target type
@@ -1050,6 +1201,7 @@ enum_from_primitive! {
         Image1d = ffi::CL_MEM_OBJECT_IMAGE1D as isize,
         Image1dArray = ffi::CL_MEM_OBJECT_IMAGE1D_ARRAY as isize,
         Image1dBuffer = ffi::CL_MEM_OBJECT_IMAGE1D_BUFFER as isize,
+        Pipe = ffi::CL_MEM_OBJECT_PIPE as isize,
     }
 }
 
@@ -1117,6 +1269,17 @@ enum_from_primitive! {
 }
 
 
+enum_from_primitive! {
+    /// cl_pipe_info
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum PipeInfo {
+        PacketSize = ffi::CL_PIPE_PACKET_SIZE as isize,
+        MaxPackets = ffi::CL_PIPE_MAX_PACKETS as isize,
+    }
+}
+
+
 enum_from_primitive! {
     /// cl_sampler_info
     #[repr(C)]
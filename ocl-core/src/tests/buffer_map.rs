@@ -0,0 +1,34 @@
+const DATASET_SIZE: usize = 1 << 14;
+
+#[test]
+fn map_write_unmap_read() {
+    let platform_id = ::default_platform().unwrap();
+    let device_ids = ::get_device_ids(&platform_id, None, None).unwrap();
+    let device = device_ids[0];
+    let context_properties = ::ContextProperties::new().platform(platform_id);
+    let context = ::create_context(Some(&context_properties),
+        &[device], None, None).unwrap();
+    let queue = ::create_command_queue(&context, &device, None).unwrap();
+
+    let buffer = unsafe { ::create_buffer::<_, f32>(&context, ::MEM_READ_WRITE, DATASET_SIZE, None).unwrap() };
+
+    // Map for writing and fill through the raw pointer:
+    let mut mapped = unsafe { ::enqueue_map_buffer::<f32, _, _, _>(&queue, &buffer, true,
+        ::MAP_WRITE_INVALIDATE_REGION, 0, DATASET_SIZE, None::<::Event>, None::<&mut ::Event>).unwrap() };
+
+    for (idx, ele) in unsafe { mapped.as_slice_mut(DATASET_SIZE) }.iter_mut().enumerate() {
+        *ele = idx as f32;
+    }
+
+    let mut unmap_event = ::Event::null();
+    ::enqueue_unmap_mem_object(&queue, &buffer, &mapped, None::<::Event>,
+        Some(&mut unmap_event)).unwrap();
+
+    let mut vec = vec![0.0f32; DATASET_SIZE];
+    unsafe { ::enqueue_read_buffer::<f32, _, _, _>(&queue, &buffer, true, 0, &mut vec,
+        Some(&unmap_event), None::<&mut ::Event>).unwrap() };
+
+    for (idx, &ele) in vec.iter().enumerate() {
+        assert_eq!(ele, idx as f32);
+    }
+}
@@ -0,0 +1,10 @@
+use ::DeviceType;
+
+#[test]
+fn device_type_display() {
+    assert_eq!(DeviceType::GPU.to_string(), "GPU");
+    assert_eq!((DeviceType::GPU | DeviceType::DEFAULT).to_string(), "GPU | DEFAULT");
+    assert_eq!(DeviceType::new().cpu().accelerator().to_string(), "CPU | ACCELERATOR");
+    assert_eq!(DeviceType::ALL.to_string(), "ALL");
+    assert_eq!(DeviceType::empty().to_string(), "NONE");
+}
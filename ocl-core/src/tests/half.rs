@@ -0,0 +1,43 @@
+//! Half-precision conversions.
+
+use ::f16;
+
+#[test]
+fn f16_round_trip() {
+    let exact = [0.0f32, -0.0, 1.0, -2.5, 0.333251953125, 65504.0, 6.103515625e-5,
+        5.960464477539063e-8];
+
+    for &val in exact.iter() {
+        let half = f16::from_f32(val);
+        assert_eq!(half.to_f32().to_bits(), val.to_bits(), "{}", val);
+    }
+
+    assert_eq!(f16::from_f32(1.0).to_bits(), 0x3c00);
+    assert_eq!(f16::from_f32(-2.0).to_bits(), 0xc000);
+    assert_eq!(f16::from_f32(65504.0).to_bits(), 0x7bff);
+    assert_eq!(f16::from_f32(5.960464477539063e-8).to_bits(), 0x0001);
+
+    // Overflow, infinity, and NaN:
+    assert_eq!(f16::from_f32(1.0e6).to_bits(), 0x7c00);
+    assert_eq!(f16::from_f32(::std::f32::NEG_INFINITY).to_bits(), 0xfc00);
+    assert!(f16::from_f32(::std::f32::NAN).to_f32().is_nan());
+
+    // Underflow:
+    assert_eq!(f16::from_f32(1.0e-10).to_bits(), 0x0000);
+}
+
+#[test]
+fn f16_round_half_to_even() {
+    // 1.0 + 2^-11 lies exactly between 1.0 and the next half (1.0 + 2^-10),
+    // ties round to the even mantissa (1.0):
+    assert_eq!(f16::from_f32(1.0 + 2.0f32.powi(-11)).to_bits(), 0x3c00);
+    // 1.0 + 3 * 2^-11 ties between 0x3c01 and 0x3c02, rounding to 0x3c02:
+    assert_eq!(f16::from_f32(1.0 + 3.0 * 2.0f32.powi(-11)).to_bits(), 0x3c02);
+    // Just above the tie rounds up:
+    assert_eq!(f16::from_f32(1.0 + 2.0f32.powi(-11) + 2.0f32.powi(-20)).to_bits(), 0x3c01);
+    // Subnormal ties (2^-25 lies between zero and the smallest subnormal):
+    assert_eq!(f16::from_f32(2.0f32.powi(-25)).to_bits(), 0x0000);
+    assert_eq!(f16::from_f32(3.0 * 2.0f32.powi(-25)).to_bits(), 0x0002);
+    // Rounding up out of the largest finite value overflows to infinity:
+    assert_eq!(f16::from_f32(65520.0).to_bits(), 0x7c00);
+}
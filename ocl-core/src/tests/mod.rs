@@ -17,8 +17,12 @@ extern crate rand;
 pub mod build_error;
 pub mod buffer_copy;
 pub mod buffer_fill;
+pub mod buffer_map;
 pub mod vector_types;
 pub mod compile_program;
+pub mod status;
+pub mod device_type;
+pub mod half;
 use self::rand::Rng;
 use error::{Result as OclCoreResult};
 use ::{OclScl, PlatformId, DeviceId, Context};
@@ -0,0 +1,22 @@
+use num_traits::FromPrimitive;
+use ::Status;
+
+#[test]
+fn status_from_code() {
+    assert_eq!(Status::from_i32(-5), Some(Status::CL_OUT_OF_RESOURCES));
+    assert_eq!(Status::from_i32(-54), Some(Status::CL_INVALID_WORK_GROUP_SIZE));
+    assert_eq!(Status::from_i32(-1001), Some(Status::CL_PLATFORM_NOT_FOUND_KHR));
+    assert_eq!(Status::from_i32(-1006), Some(Status::CL_INVALID_D3D11_DEVICE_KHR));
+    assert_eq!(Status::from_i32(-1059), Some(Status::CL_INVALID_PARTITION_NAME_EXT));
+    assert_eq!(Status::from_i32(-72), Some(Status::CL_MAX_SIZE_RESTRICTION_EXCEEDED));
+
+    assert_eq!(Status::CL_PLATFORM_NOT_FOUND_KHR.to_string(), "CL_PLATFORM_NOT_FOUND_KHR");
+}
+
+#[test]
+fn status_description() {
+    assert!(Status::CL_PLATFORM_NOT_FOUND_KHR.description().unwrap().contains("ICD"));
+    assert!(Status::CL_INVALID_WORK_GROUP_SIZE.description().is_some());
+    assert!(Status::CL_OUT_OF_RESOURCES.description().is_some());
+    assert!(Status::CL_SUCCESS.description().is_none());
+}
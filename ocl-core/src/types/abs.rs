@@ -511,8 +511,9 @@ impl Clone for Context {
 }
 
 impl Drop for Context {
-    /// Panics in the event of an error of type `Error::Status` except when
-    /// the status code is `CL_INVALID_CONTEXT` (which is ignored).
+    /// Passes any release error to the hook set with
+    /// `::set_release_error_hook` (or prints it to stderr) except when the
+    /// status code is `CL_INVALID_CONTEXT` (which is ignored).
     ///
     /// This is done because certain platforms error with `CL_INVALID_CONTEXT`
     /// for unknown reasons and as far as we know can be safely ignored.
@@ -523,7 +524,7 @@ impl Drop for Context {
                 if let Some(Status::CL_INVALID_CONTEXT) = e.api_status() {
                     return;
                 }
-                panic!("{:?}", e);
+                functions::report_release_error(&e);
             }
         }
     }
@@ -620,7 +621,9 @@ impl Clone for CommandQueue {
 
 impl Drop for CommandQueue {
     fn drop(&mut self) {
-        unsafe { functions::release_command_queue(self).unwrap(); }
+        if let Err(e) = unsafe { functions::release_command_queue(self) } {
+            functions::report_release_error(&e);
+        }
     }
 }
 
@@ -1034,9 +1037,30 @@ impl Event {
             callback_receiver: EventCallbackFn,
             user_data_ptr: *mut c_void,
             ) -> OclCoreResult<()>
+    {
+        self.set_callback_status(CommandExecutionStatus::Complete, callback_receiver,
+            user_data_ptr)
+    }
+
+    /// Sets a callback function which will be called when the execution
+    /// status of this event's command reaches `callback_trigger`
+    /// (`Submitted`, `Running`, or `Complete`).
+    ///
+    /// See `Event::set_callback` for more.
+    ///
+    /// # Safety
+    ///
+    /// `user_data` must be guaranteed to still exist if and when `callback_receiver`
+    /// is ever called.
+    ///
+    pub unsafe fn set_callback_status(&self,
+            callback_trigger: CommandExecutionStatus,
+            callback_receiver: EventCallbackFn,
+            user_data_ptr: *mut c_void,
+            ) -> OclCoreResult<()>
     {
         if self.is_valid() {
-            ::set_event_callback(self, CommandExecutionStatus::Complete,
+            ::set_event_callback(self, callback_trigger,
                 Some(callback_receiver), user_data_ptr as *mut _ as *mut c_void)
         } else {
             Err("ocl_core::Event::set_callback: This event is null. Cannot set callback until \
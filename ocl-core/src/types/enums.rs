@@ -10,6 +10,7 @@
 #![allow(dead_code)]
 
 use std::fmt;
+use std::mem;
 use failure::Fail;
 use num_traits::FromPrimitive;
 use util;
@@ -17,7 +18,7 @@ use ffi::{cl_image_format, cl_context_properties, c_void};
 
 use ::{CommandQueueProperties, PlatformId, PlatformInfo, DeviceId, DeviceInfo, ContextInfo,
     GlContextInfo, Context, CommandQueue, CommandQueueInfo, CommandType, CommandExecutionStatus,
-    Mem, MemInfo, MemObjectType, MemFlags, SamplerInfo, AddressingMode, FilterMode,
+    Mem, MemInfo, MemObjectType, MemFlags, PipeInfo, SamplerInfo, AddressingMode, FilterMode,
     ProgramInfo, ProgramBuildInfo, Program, ProgramBuildStatus, ProgramBinaryType, KernelInfo,
     KernelArgInfo, KernelWorkGroupInfo, KernelArgAddressQualifier, KernelArgAccessQualifier,
     KernelArgTypeQualifier, ImageInfo, ImageFormat, EventInfo, ProfilingInfo, DeviceType,
@@ -44,6 +45,8 @@ pub enum EmptyInfoResultError {
     Mem,
     #[fail(display = "Image info unavailable")]
     Image,
+    #[fail(display = "Pipe info unavailable")]
+    Pipe,
     #[fail(display = "Sampler info unavailable")]
     Sampler,
     #[fail(display = "Program info unavailable")]
@@ -1061,7 +1064,7 @@ impl MemInfoResult {
             },
             MemInfo::HostPtr => {
                 // [FIXME]: UNTESTED, INCOMPLETE.
-                if result.len() == 8 {
+                if result.len() == mem::size_of::<*mut c_void>() {
                     let ptr = unsafe { util::bytes_into::<*mut c_void>(result)? };
 
                     if ptr.is_null() {
@@ -1069,7 +1072,7 @@ impl MemInfoResult {
                     } else {
                         MemInfoResult::HostPtr(Some((ptr, None)))
                     }
-                } else if result.len() == 16 {
+                } else if result.len() == mem::size_of::<(*mut c_void, usize)>() {
                     let ptr_and_origin = unsafe {
                         util::bytes_into::<(*mut c_void, usize)>(result)?
                     };
@@ -1253,6 +1256,54 @@ impl From<ImageInfoResult> for String {
 }
 
 
+/// A pipe info result.
+pub enum PipeInfoResult {
+    PacketSize(u32),
+    MaxPackets(u32),
+}
+
+impl PipeInfoResult {
+    pub fn from_bytes(request: PipeInfo, result: Vec<u8>) -> OclCoreResult<PipeInfoResult> {
+        if result.is_empty() {
+            return Err(OclCoreError::from(
+                EmptyInfoResultError::Pipe));
+        }
+        let ir = match request {
+            PipeInfo::PacketSize => {
+                let r = unsafe { util::bytes_into::<u32>(result)? };
+                PipeInfoResult::PacketSize(r)
+            },
+            PipeInfo::MaxPackets => {
+                let r = unsafe { util::bytes_into::<u32>(result)? };
+                PipeInfoResult::MaxPackets(r)
+            },
+        };
+        Ok(ir)
+    }
+}
+
+impl fmt::Debug for PipeInfoResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", &self.to_string())
+    }
+}
+
+impl fmt::Display for PipeInfoResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PipeInfoResult::PacketSize(ref s) => write!(f, "{}", s),
+            PipeInfoResult::MaxPackets(ref s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl From<PipeInfoResult> for String {
+    fn from(ir: PipeInfoResult) -> String {
+        ir.to_string()
+    }
+}
+
+
 /// A sampler info result.
 pub enum SamplerInfoResult {
     ReferenceCount(u32),
@@ -1342,7 +1393,9 @@ pub enum ProgramInfoResult {
     /// each device.
     Binaries(Vec<Vec<u8>>),
     NumKernels(usize),
-    KernelNames(String),
+    /// Contains the name of each kernel in the program (split from the
+    /// semicolon-separated list returned by the API).
+    KernelNames(Vec<String>),
 }
 
 impl ProgramInfoResult {
@@ -1389,7 +1442,10 @@ impl ProgramInfoResult {
             },
             ProgramInfo::KernelNames => {
                 match util::bytes_into_string(result) {
-                    Ok(s) => ProgramInfoResult::KernelNames(s),
+                    Ok(s) => ProgramInfoResult::KernelNames(s.split(';')
+                        .filter(|n| !n.is_empty())
+                        .map(|n| n.to_owned())
+                        .collect()),
                     Err(err) => return Err(err.into()),
                 }
             },
@@ -1415,7 +1471,7 @@ impl fmt::Display for ProgramInfoResult {
             ProgramInfoResult::BinarySizes(ref s) => write!(f, "{:?}", s),
             ProgramInfoResult::Binaries(ref s) => write!(f, "{:?}", s),
             ProgramInfoResult::NumKernels(ref s) => write!(f, "{}", s),
-            ProgramInfoResult::KernelNames(ref s) => write!(f, "{}", s),
+            ProgramInfoResult::KernelNames(ref s) => write!(f, "{}", s.join(";")),
         }
     }
 }
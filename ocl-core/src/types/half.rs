@@ -0,0 +1,177 @@
+//! Half-precision (16-bit) floating point types.
+//!
+//! Rust has no native 16-bit float. `f16` stores the raw IEEE 754 binary16
+//! bit pattern and provides conversions to and from `f32`, allowing
+//! `cl_khr_fp16` data (`half`, `half4`, `half8`) to be transferred to and
+//! from buffers. No arithmetic is provided, convert to `f32` first.
+
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use ::OclPrm;
+
+
+/// A half-precision (16-bit) floating point value, equivalent to `half`
+/// within a kernel.
+///
+/// Equality compares bit patterns (`NaN == NaN` if the patterns match and
+/// `0.0 != -0.0`).
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct f16(u16);
+
+impl f16 {
+    /// Returns a new `f16` from its raw bit pattern.
+    #[inline]
+    pub fn from_bits(bits: u16) -> f16 {
+        f16(bits)
+    }
+
+    /// Returns the raw bit pattern of this value.
+    #[inline]
+    pub fn to_bits(self) -> u16 {
+        self.0
+    }
+
+    /// Converts an `f32` to the nearest `f16`, rounding ties to even.
+    ///
+    /// Values too large to be represented become infinity and values too
+    /// small become (signed) zero or a subnormal.
+    pub fn from_f32(val: f32) -> f16 {
+        let x = val.to_bits();
+        let sign = ((x >> 16) & 0x8000) as u16;
+        let exp = ((x >> 23) & 0xff) as i32;
+        let man = x & 0x007f_ffff;
+
+        // Infinity and NaN (keeping NaNs quiet):
+        if exp == 0xff {
+            let nan_bit = if man == 0 { 0 } else { 0x0200 };
+            return f16(sign | 0x7c00 | nan_bit | (man >> 13) as u16);
+        }
+
+        let half_exp = exp - 127 + 15;
+
+        // Overflow:
+        if half_exp >= 0x1f {
+            return f16(sign | 0x7c00);
+        }
+
+        // Subnormal or zero:
+        if half_exp <= 0 {
+            let shift = (14 - half_exp) as u32;
+            if shift > 24 {
+                return f16(sign);
+            }
+            let man = man | 0x0080_0000;
+            let half_man = (man >> shift) as u16;
+            let round_bit = 1 << (shift - 1);
+            let round_up = (man & round_bit) != 0 && (man & (3 * round_bit - 1)) != 0;
+            return f16(sign | (half_man + round_up as u16));
+        }
+
+        // Normal. A carry out of the mantissa correctly bumps the exponent
+        // (possibly to infinity):
+        let half_man = (man >> 13) as u16;
+        let round_bit = 0x0000_1000;
+        let round_up = (man & round_bit) != 0 && (man & (3 * round_bit - 1)) != 0;
+        f16((sign | ((half_exp as u16) << 10) | half_man) + round_up as u16)
+    }
+
+    /// Converts this value to an `f32` (exactly).
+    pub fn to_f32(self) -> f32 {
+        let sign = ((self.0 & 0x8000) as u32) << 16;
+        let exp = ((self.0 >> 10) & 0x1f) as u32;
+        let man = (self.0 & 0x03ff) as u32;
+
+        let bits = match (exp, man) {
+            (0, 0) => sign,
+            (0, _) => {
+                // Subnormal, normalize:
+                let shift = man.leading_zeros() - 21;
+                let exp = 127 - 15 + 1 - shift;
+                sign | (exp << 23) | (((man << shift) & 0x03ff) << 13)
+            },
+            (0x1f, _) => sign | 0x7f80_0000 | (man << 13),
+            _ => sign | ((exp + 127 - 15) << 23) | (man << 13),
+        };
+
+        f32::from_bits(bits)
+    }
+}
+
+impl From<f32> for f16 {
+    fn from(val: f32) -> f16 {
+        f16::from_f32(val)
+    }
+}
+
+impl From<f16> for f32 {
+    fn from(val: f16) -> f32 {
+        val.to_f32()
+    }
+}
+
+impl fmt::Debug for f16 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_f32(), f)
+    }
+}
+
+impl fmt::Display for f16 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.to_f32(), f)
+    }
+}
+
+unsafe impl OclPrm for f16 {}
+
+
+macro_rules! half_vec {
+    ($name:ident, $len:expr, $doc:expr, $( $c:ident ),+) => {
+        #[doc = $doc]
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+        #[repr(C)]
+        pub struct $name(pub [f16; $len]);
+
+        impl $name {
+            /// Returns a new vector from its components.
+            #[inline]
+            pub fn new($( $c: f16 ),+) -> $name {
+                $name([$( $c ),+])
+            }
+
+            /// Returns a new vector with each component set to `val`.
+            #[inline]
+            pub fn splat(val: f16) -> $name {
+                $name([val; $len])
+            }
+        }
+
+        impl From<[f16; $len]> for $name {
+            fn from(a: [f16; $len]) -> $name {
+                $name(a)
+            }
+        }
+
+        impl Deref for $name {
+            type Target = [f16; $len];
+
+            fn deref(&self) -> &[f16; $len] {
+                &self.0
+            }
+        }
+
+        impl DerefMut for $name {
+            fn deref_mut(&mut self) -> &mut [f16; $len] {
+                &mut self.0
+            }
+        }
+
+        unsafe impl OclPrm for $name {}
+    }
+}
+
+half_vec!(Half4, 4, "A vector of four half-precision values, equivalent to `half4` within \
+    a kernel.", s0, s1, s2, s3);
+half_vec!(Half8, 8, "A vector of eight half-precision values, equivalent to `half8` within \
+    a kernel.", s0, s1, s2, s3, s4, s5, s6, s7);
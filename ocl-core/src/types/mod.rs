@@ -3,3 +3,4 @@
 pub mod abs;
 pub mod enums;
 pub mod structs;
+pub mod half;
@@ -104,6 +104,41 @@ impl<'a> ArgVal<'a> {
         ArgVal::primitive(vector)
     }
 
+    /// Returns a new `ArgVal` referring to a plain-old-data value, such as a
+    /// small struct, passed by value to a kernel parameter of a matching
+    /// (e.g. `struct`) type.
+    ///
+    /// `T` should have the same size, field order, and alignment as the type
+    /// declared in the kernel, otherwise the kernel will read garbage (or the
+    /// call to `::set_kernel_arg` will fail with `CL_INVALID_ARG_SIZE`). The
+    /// type is not otherwise verified.
+    ///
+    /// ```rust, ignore
+    /// // typedef struct { float a; int b; } Params;
+    /// #[repr(C)]
+    /// #[derive(Clone, Copy)]
+    /// struct Params { a: f32, b: i32 }
+    ///
+    /// core::set_kernel_arg(&kernel, 0, unsafe { ArgVal::structure(&Params { a: 1.5, b: 2 }) })?;
+    /// ```
+    ///
+    /// ### Safety
+    ///
+    /// The bytes of `value` are read as-is. `T` must be `#[repr(C)]`, must
+    /// not contain any padding bytes (declare explicit padding fields
+    /// instead), and must consist only of plain data: no references, raw
+    /// pointers, function pointers, or other types whose values are not
+    /// meaningful on the device.
+    pub unsafe fn structure<T>(value: &'a T) -> ArgVal<'a>
+            where T: Copy + 'static {
+        ArgVal {
+            size: mem::size_of::<T>() as size_t,
+            value: value as *const T as *const c_void,
+            is_mem: false,
+            _p: PhantomData,
+        }
+    }
+
     /// Returns a new `ArgVal` corresponding to a `__local` argument.
     ///
     /// To specify a `__local` argument size in bytes, use `::raw` instead
@@ -372,6 +407,41 @@ impl ContextProperties {
         self
     }
 
+    /// Specifies an `ID3D10Device*` to use for Direct3D 10 interoperability
+    /// (builder-style).
+    pub fn d3d10_device(mut self, d3d10_device: *mut c_void) -> ContextProperties {
+        self.set_d3d10_device(d3d10_device);
+        self
+    }
+
+    /// Specifies an `ID3D11Device*` to use for Direct3D 11 interoperability
+    /// (builder-style).
+    pub fn d3d11_device(mut self, d3d11_device: *mut c_void) -> ContextProperties {
+        self.set_d3d11_device(d3d11_device);
+        self
+    }
+
+    /// Specifies an `IDirect3DDevice9*` to use for D3D9 media sharing
+    /// (builder-style).
+    pub fn adapter_d3d9(mut self, d3d9_device: *mut c_void) -> ContextProperties {
+        self.set_adapter_d3d9(d3d9_device);
+        self
+    }
+
+    /// Specifies an `IDirect3DDevice9Ex*` to use for D3D9 media sharing
+    /// (builder-style).
+    pub fn adapter_d3d9ex(mut self, d3d9ex_device: *mut c_void) -> ContextProperties {
+        self.set_adapter_d3d9ex(d3d9ex_device);
+        self
+    }
+
+    /// Specifies an `IDXVAHD_Device*` to use for DXVA media sharing
+    /// (builder-style).
+    pub fn adapter_dxva(mut self, dxva_device: *mut c_void) -> ContextProperties {
+        self.set_adapter_dxva(dxva_device);
+        self
+    }
+
     /// Pushes a `ContextPropertyValue` onto this list of properties
     /// (builder-style).
     pub fn property_value(mut self, prop: ContextPropertyValue) -> ContextProperties {
@@ -421,6 +491,36 @@ impl ContextProperties {
         self.contains_gl_context_or_sharegroup = true;
     }
 
+    /// Specifies an `ID3D10Device*` to use for Direct3D 10 interoperability.
+    pub fn set_d3d10_device(&mut self, d3d10_device: *mut c_void) {
+        self.props.insert(ContextProperty::D3d10DeviceKhr,
+            ContextPropertyValue::D3d10DeviceKhr(d3d10_device as *mut ffi::cl_d3d10_device_source_khr));
+    }
+
+    /// Specifies an `ID3D11Device*` to use for Direct3D 11 interoperability.
+    pub fn set_d3d11_device(&mut self, d3d11_device: *mut c_void) {
+        self.props.insert(ContextProperty::D3d11DeviceKhr,
+            ContextPropertyValue::D3d11DeviceKhr(d3d11_device as isize));
+    }
+
+    /// Specifies an `IDirect3DDevice9*` to use for D3D9 media sharing.
+    pub fn set_adapter_d3d9(&mut self, d3d9_device: *mut c_void) {
+        self.props.insert(ContextProperty::AdapterD3d9Khr,
+            ContextPropertyValue::AdapterD3d9Khr(d3d9_device as isize));
+    }
+
+    /// Specifies an `IDirect3DDevice9Ex*` to use for D3D9 media sharing.
+    pub fn set_adapter_d3d9ex(&mut self, d3d9ex_device: *mut c_void) {
+        self.props.insert(ContextProperty::AdapterD3d9exKhr,
+            ContextPropertyValue::AdapterD3d9exKhr(d3d9ex_device as isize));
+    }
+
+    /// Specifies an `IDXVAHD_Device*` to use for DXVA media sharing.
+    pub fn set_adapter_dxva(&mut self, dxva_device: *mut c_void) {
+        self.props.insert(ContextProperty::AdapterDxvaKhr,
+            ContextPropertyValue::AdapterDxvaKhr(dxva_device as isize));
+    }
+
     /// Pushes a `ContextPropertyValue` onto this list of properties.
     pub fn set_property_value(&mut self, prop: ContextPropertyValue) {
         match prop {
@@ -456,7 +556,26 @@ impl ContextProperties {
                     ContextPropertyValue::EglDisplayKhr(val));
                 self.contains_gl_context_or_sharegroup = true;
             },
-            _ => panic!("'{:?}' is not yet a supported variant.", prop),
+            ContextPropertyValue::D3d10DeviceKhr(val) => {
+                self.props.insert(ContextProperty::D3d10DeviceKhr,
+                    ContextPropertyValue::D3d10DeviceKhr(val));
+            },
+            ContextPropertyValue::AdapterD3d9Khr(val) => {
+                self.props.insert(ContextProperty::AdapterD3d9Khr,
+                    ContextPropertyValue::AdapterD3d9Khr(val));
+            },
+            ContextPropertyValue::AdapterD3d9exKhr(val) => {
+                self.props.insert(ContextProperty::AdapterD3d9exKhr,
+                    ContextPropertyValue::AdapterD3d9exKhr(val));
+            },
+            ContextPropertyValue::AdapterDxvaKhr(val) => {
+                self.props.insert(ContextProperty::AdapterDxvaKhr,
+                    ContextPropertyValue::AdapterDxvaKhr(val));
+            },
+            ContextPropertyValue::D3d11DeviceKhr(val) => {
+                self.props.insert(ContextProperty::D3d11DeviceKhr,
+                    ContextPropertyValue::D3d11DeviceKhr(val));
+            },
         }
     }
 
@@ -524,7 +643,17 @@ impl ContextProperties {
                     props_raw.push(key.clone() as isize);
                     props_raw.push(sync as isize);
                 },
-                _ => panic!("'{:?}' is not yet a supported variant.", key),
+                ContextPropertyValue::D3d10DeviceKhr(device) => {
+                    props_raw.push(key.clone() as isize);
+                    props_raw.push(device as isize);
+                },
+                ContextPropertyValue::AdapterD3d9Khr(device) |
+                ContextPropertyValue::AdapterD3d9exKhr(device) |
+                ContextPropertyValue::AdapterDxvaKhr(device) |
+                ContextPropertyValue::D3d11DeviceKhr(device) => {
+                    props_raw.push(key.clone() as isize);
+                    props_raw.push(device);
+                },
             };
         }
 
@@ -535,6 +664,58 @@ impl ContextProperties {
         props_raw
     }
 
+    /// Verifies that `raw_context_properties` is a correctly formed list of
+    /// `(property, value)` pairs followed by a single terminating `0`.
+    ///
+    /// Each property must be recognized and specified only once. Values for
+    /// `InteropUserSync` must be a `cl_bool` (`0` or `1`) and all other
+    /// values (handles and pointers) must be non-null.
+    ///
+    /// Malformed lists are otherwise only rejected by the driver, usually
+    /// with an unhelpful `CL_INVALID_PROPERTY` or `CL_INVALID_VALUE` error.
+    pub fn verify_raw(raw_context_properties: &[isize]) -> OclCoreResult<()> {
+        if raw_context_properties.last() != Some(&0) {
+            return Err("ContextProperties::verify_raw: The property list is not \
+                terminated with a '0'.".into());
+        }
+
+        if raw_context_properties.len() % 2 != 1 {
+            return Err(format!("ContextProperties::verify_raw: The property list length ({}) \
+                is invalid. Each property must be followed by exactly one value.",
+                raw_context_properties.len()).into());
+        }
+
+        let pair_count = raw_context_properties.len() / 2;
+        let mut keys = Vec::with_capacity(pair_count);
+
+        for pair in raw_context_properties[..pair_count * 2].chunks(2) {
+            let (key_raw, val_raw) = (pair[0], pair[1]);
+
+            let key = ContextProperty::from_isize(key_raw).ok_or(OclCoreError::from(
+                format!("ContextProperties::verify_raw: Unrecognized property: '{}'.", key_raw)))?;
+
+            if keys.contains(&key) {
+                return Err(format!("ContextProperties::verify_raw: The property '{:?}' is \
+                    specified more than once.", key).into());
+            }
+
+            match key {
+                ContextProperty::InteropUserSync => if val_raw != 0 && val_raw != 1 {
+                    return Err(format!("ContextProperties::verify_raw: Invalid value for \
+                        '{:?}': '{}'. Must be either '0' or '1'.", key, val_raw).into());
+                },
+                _ => if val_raw == 0 {
+                    return Err(format!("ContextProperties::verify_raw: Invalid value for \
+                        '{:?}': Must not be null.", key).into());
+                },
+            }
+
+            keys.push(key);
+        }
+
+        Ok(())
+    }
+
     /// Returns a single context property value.
     pub unsafe fn extract_property_from_raw(property: ContextProperty,
             raw_context_properties: &[isize]) -> Option<ContextPropertyValue>
@@ -574,8 +755,7 @@ impl ContextProperties {
     pub unsafe fn from_raw(raw_context_properties: &[isize]) -> OclCoreResult<ContextProperties> {
         // The raw properties **should** be `(isize, isize)` pairs + isize (null) terminator.
         assert!(mem::size_of::<cl_context_properties>() == mem::size_of::<isize>());
-        assert!(raw_context_properties.len() % 2 == 1);
-        assert!(*raw_context_properties.last().unwrap() == 0);
+        ContextProperties::verify_raw(raw_context_properties)?;
 
         let pair_count = raw_context_properties.len() / 2;
         let mut context_props = ContextProperties {
@@ -853,78 +1033,99 @@ impl ImageFormat {
         }
     }
 
+    /// Returns the number of channels (components) of a pixel using the
+    /// channel order specified by this `ImageFormat`.
+    ///
+    /// Padding channels (the 'x' in `Rx`, `Rgx`, and `Rgbx`) are counted.
+    pub fn channel_count(&self) -> usize {
+        match self.channel_order {
+            ImageChannelOrder::R | ImageChannelOrder::A | ImageChannelOrder::Intensity |
+                ImageChannelOrder::Luminance | ImageChannelOrder::Depth => 1,
+            ImageChannelOrder::Rg | ImageChannelOrder::Ra | ImageChannelOrder::Rx |
+                ImageChannelOrder::DepthStencil => 2,
+            ImageChannelOrder::Rgb | ImageChannelOrder::Rgx => 3,
+            ImageChannelOrder::Rgba | ImageChannelOrder::Bgra | ImageChannelOrder::Argb |
+                ImageChannelOrder::Rgbx => 4,
+        }
+    }
+
+    /// Returns the size in bytes of each channel using the channel data type
+    /// specified by this `ImageFormat` or `None` if the data type packs all
+    /// channels of a pixel together (`UnormShort565`, `UnormShort555`, and
+    /// `UnormInt101010`).
+    pub fn channel_bytes(&self) -> Option<usize> {
+        match self.channel_data_type {
+            ImageChannelDataType::SnormInt8 | ImageChannelDataType::UnormInt8 |
+                ImageChannelDataType::SignedInt8 | ImageChannelDataType::UnsignedInt8 => Some(1),
+            ImageChannelDataType::SnormInt16 | ImageChannelDataType::UnormInt16 |
+                ImageChannelDataType::SignedInt16 | ImageChannelDataType::UnsignedInt16 |
+                ImageChannelDataType::HalfFloat => Some(2),
+            ImageChannelDataType::SignedInt32 | ImageChannelDataType::UnsignedInt32 |
+                ImageChannelDataType::Float => Some(4),
+            // Stored within 32 bits:
+            ImageChannelDataType::UnormInt24 => Some(4),
+            ImageChannelDataType::UnormShort565 | ImageChannelDataType::UnormShort555 |
+                ImageChannelDataType::UnormInt101010 => None,
+        }
+    }
+
     /// Returns the size in bytes of a pixel using the format specified by this
     /// `ImageFormat`.
     ///
-    /// TODO: Add a special case for Depth & DepthStencil
-    /// (https://www.khronos.org/registry/cl/sdk/2.0/docs/man/xhtml/cl_khr_gl_depth_images.html).
+    /// Packed data types (`UnormShort565`, `UnormShort555`, and
+    /// `UnormInt101010`) occupy 2, 2, and 4 bytes respectively regardless of
+    /// channel order. `DepthStencil` pixels occupy 4 bytes when used with
+    /// `UnormInt24` (24-bit depth, 8-bit stencil) and 8 bytes otherwise
+    /// (32-bit float depth, 8-bit stencil, 24 bits unused).
     ///
-    /// TODO: Validate combinations.
-    /// TODO: Use `core::get_image_info` to check these with a test.
+    /// Combinations of channel order and data type are not validated.
+    pub fn bytes_per_pixel(&self) -> usize {
+        match (self.channel_order, self.channel_data_type) {
+            (_, ImageChannelDataType::UnormShort565) => 2,
+            (_, ImageChannelDataType::UnormShort555) => 2,
+            (_, ImageChannelDataType::UnormInt101010) => 4,
+            (ImageChannelOrder::DepthStencil, ImageChannelDataType::UnormInt24) => 4,
+            (ImageChannelOrder::DepthStencil, _) => 8,
+            _ => self.channel_count() * self.channel_bytes().unwrap_or(0),
+        }
+    }
+
+    /// Returns the size in bytes of a pixel using the format specified by this
+    /// `ImageFormat`.
     ///
+    /// Equivalent to `::bytes_per_pixel`.
     pub fn pixel_bytes(&self) -> usize {
-        let channel_count = match self.channel_order {
-            ImageChannelOrder::R => 1,
-            ImageChannelOrder::A => 1,
-            ImageChannelOrder::Rg => 2,
-            ImageChannelOrder::Ra => 2,
-            // This format can only be used if channel data type = CL_UNORM_SHORT_565, CL_UNORM_SHORT_555 or CL_UNORM_INT101010:
-            ImageChannelOrder::Rgb => 1,
-            ImageChannelOrder::Rgba => 4,
-            // This format can only be used if channel data type = CL_UNORM_INT8, CL_SNORM_INT8, CL_SIGNED_INT8 or CL_UNSIGNED_INT8:
-            ImageChannelOrder::Bgra => 4,
-            // This format can only be used if channel data type = CL_UNORM_INT8, CL_SNORM_INT8, CL_SIGNED_INT8 or CL_UNSIGNED_INT8:
-            ImageChannelOrder::Argb => 4,
-            // This format can only be used if channel data type = CL_UNORM_INT8, CL_UNORM_INT16, CL_SNORM_INT8, CL_SNORM_INT16, CL_HALF_FLOAT, or CL_FLOAT:
-            ImageChannelOrder::Intensity => 4,
-            // This format can only be used if channel data type = CL_UNORM_INT8, CL_UNORM_INT16, CL_SNORM_INT8, CL_SNORM_INT16, CL_HALF_FLOAT, or CL_FLOAT:
-            ImageChannelOrder::Luminance => 4,
-            ImageChannelOrder::Rx => 2,
-            ImageChannelOrder::Rgx => 4,
-            // This format can only be used if channel data type = CL_UNORM_SHORT_565, CL_UNORM_SHORT_555 or CL_UNORM_INT101010:
-            ImageChannelOrder::Rgbx => 4,
-            // Depth => 1,
-            // DepthStencil => 1,
-            _ => 0,
-        };
+        self.bytes_per_pixel()
+    }
 
-        let channel_size = match self.channel_data_type {
-            // Each channel component is a normalized signed 8-bit integer value:
-            ImageChannelDataType::SnormInt8 => 1,
-            // Each channel component is a normalized signed 16-bit integer value:
-            ImageChannelDataType::SnormInt16 => 2,
-            // Each channel component is a normalized unsigned 8-bit integer value:
-            ImageChannelDataType::UnormInt8 => 1,
-            // Each channel component is a normalized unsigned 16-bit integer value:
-            ImageChannelDataType::UnormInt16 => 2,
-            // Represents a normalized 5-6-5 3-channel RGB image. The channel order must be CL_RGB or CL_RGBx:
-            ImageChannelDataType::UnormShort565 => 2,
-            // Represents a normalized x-5-5-5 4-channel xRGB image. The channel order must be CL_RGB or CL_RGBx:
-            ImageChannelDataType::UnormShort555 => 2,
-            // Represents a normalized x-10-10-10 4-channel xRGB image. The channel order must be CL_RGB or CL_RGBx:
-            ImageChannelDataType::UnormInt101010 => 4,
-            // Each channel component is an unnormalized signed 8-bit integer value:
-            ImageChannelDataType::SignedInt8 => 1,
-            // Each channel component is an unnormalized signed 16-bit integer value:
-            ImageChannelDataType::SignedInt16 => 2,
-            // Each channel component is an unnormalized signed 32-bit integer value:
-            ImageChannelDataType::SignedInt32 => 4,
-            // Each channel component is an unnormalized unsigned 8-bit integer value:
-            ImageChannelDataType::UnsignedInt8 => 1,
-            // Each channel component is an unnormalized unsigned 16-bit integer value:
-            ImageChannelDataType::UnsignedInt16 => 2,
-            // Each channel component is an unnormalized unsigned 32-bit integer value:
-            ImageChannelDataType::UnsignedInt32 => 4,
-            // Each channel component is a 16-bit half-float value:
-            ImageChannelDataType::HalfFloat => 2,
-            // Each channel component is a single precision floating-point value:
-            ImageChannelDataType::Float => 4,
-            // Each channel component is a normalized unsigned 24-bit integer value:
-            // UnormInt24 => 3,
-            _ => 0
-        };
+    /// Returns a new format with four normalized unsigned 8-bit channels
+    /// (`CL_RGBA`, `CL_UNORM_INT8`).
+    pub fn rgba8() -> ImageFormat {
+        ImageFormat::new(ImageChannelOrder::Rgba, ImageChannelDataType::UnormInt8)
+    }
+
+    /// Returns a new format with four normalized unsigned 8-bit channels in
+    /// BGRA order (`CL_BGRA`, `CL_UNORM_INT8`).
+    pub fn bgra8() -> ImageFormat {
+        ImageFormat::new(ImageChannelOrder::Bgra, ImageChannelDataType::UnormInt8)
+    }
+
+    /// Returns a new format with four single precision float channels
+    /// (`CL_RGBA`, `CL_FLOAT`).
+    pub fn rgba_f32() -> ImageFormat {
+        ImageFormat::new(ImageChannelOrder::Rgba, ImageChannelDataType::Float)
+    }
+
+    /// Returns a new format with a single normalized unsigned 8-bit channel
+    /// (`CL_R`, `CL_UNORM_INT8`).
+    pub fn r8() -> ImageFormat {
+        ImageFormat::new(ImageChannelOrder::R, ImageChannelDataType::UnormInt8)
+    }
 
-        channel_count * channel_size
+    /// Returns a new format with a single single precision float channel
+    /// (`CL_R`, `CL_FLOAT`).
+    pub fn r_f32() -> ImageFormat {
+        ImageFormat::new(ImageChannelOrder::R, ImageChannelDataType::Float)
     }
 }
 
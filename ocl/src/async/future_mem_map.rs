@@ -2,7 +2,7 @@
 // use std::sync::atomic::AtomicBool;
 use futures::{Future, Poll, Async};
 use core::{OclPrm, MemMap as MemMapCore, Mem, ClNullEventPtr};
-use async::MemMap;
+use async::{MemMap, MemMapReadOnly};
 use error::{Error as OclError, Result as OclResult};
 use ::{Event, Queue, EventList};
 
@@ -178,4 +178,66 @@ unsafe impl<T: OclPrm> Send for FutureMemMap<T> {}
 unsafe impl<T: OclPrm> Sync for FutureMemMap<T> {}
 
 
+/// A future which resolves to a read-only `MemMapReadOnly` as soon as its
+/// creating (`MAP_READ`) command completes.
+///
+/// Created by `BufferMapCmd::enq_async_read`.
+///
+/// [UNSTABLE]: This type's methods may be renamed or otherwise changed at any time.
+#[must_use = "futures do nothing unless polled"]
+#[derive(Debug)]
+pub struct FutureMemMapReadOnly<T: OclPrm> {
+    inner: FutureMemMap<T>,
+}
 
+impl<T: OclPrm> FutureMemMapReadOnly<T> {
+    /// Set an event wait list for the unmap command.
+    ///
+    /// See `FutureMemMap::set_unmap_wait_events`.
+    pub fn ewait_unmap<L: Into<EventList>>(mut self, wait_events: L) -> FutureMemMapReadOnly<T> {
+        self.inner.set_unmap_wait_events(wait_events);
+        self
+    }
+
+    /// Specifies an event which will be triggered (set complete) after this
+    /// future resolves **and** after the resulting `MemMapReadOnly` is
+    /// dropped or manually unmapped.
+    ///
+    /// See `FutureMemMap::create_unmap_event`.
+    pub fn enew_unmap<En>(self, enew: En) -> FutureMemMapReadOnly<T>
+            where En: ClNullEventPtr {
+        FutureMemMapReadOnly { inner: self.inner.enew_unmap(enew) }
+    }
+
+    /// Specifies the queue to be used for the unmap command.
+    pub fn with_unmap_queue(mut self, queue: Queue) -> FutureMemMapReadOnly<T> {
+        self.inner.set_unmap_queue(queue);
+        self
+    }
+
+    /// Returns the unmap event if it has been created.
+    #[inline]
+    pub fn unmap_event(&self) -> Option<&Event> {
+        self.inner.unmap_event()
+    }
+
+    /// Blocks the current thread until the OpenCL command is complete.
+    pub fn wait(self) -> OclResult<MemMapReadOnly<T>> {
+        <Self as Future>::wait(self)
+    }
+}
+
+impl<T: OclPrm> From<FutureMemMap<T>> for FutureMemMapReadOnly<T> {
+    fn from(inner: FutureMemMap<T>) -> FutureMemMapReadOnly<T> {
+        FutureMemMapReadOnly { inner: inner }
+    }
+}
+
+impl<T> Future for FutureMemMapReadOnly<T> where T: OclPrm + 'static {
+    type Item = MemMapReadOnly<T>;
+    type Error = OclError;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        self.inner.poll().map(|poll| poll.map(MemMapReadOnly::from))
+    }
+}
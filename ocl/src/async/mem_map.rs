@@ -260,6 +260,74 @@ impl<T: OclPrm> AsMem<T> for MemMap<T> {
     }
 }
 
+
+
+/// A read-only view of memory mapped by `clEnqueueMap{...}` with the
+/// `MAP_READ` flag.
+///
+/// Identical to a `MemMap` except that it dereferences only to `&[T]`,
+/// preventing writes to a region mapped for reading (which, depending on the
+/// platform, are either silently discarded or result in undefined behavior).
+///
+/// Created by resolving the future returned by
+/// `BufferMapCmd::enq_async_read` or by converting an existing `MemMap`.
+///
+/// [UNSTABLE]: Still in a state of flux.
+///
+#[derive(Debug)]
+pub struct MemMapReadOnly<T> where T: OclPrm {
+    map: MemMap<T>,
+}
+
+impl<T> MemMapReadOnly<T> where T: OclPrm {
+    /// Returns an unmap command builder.
+    ///
+    /// Call `::enq` on it to enqueue the unmap command.
+    pub fn unmap<'c>(&'c mut self) -> MemUnmapCmd<'c, T> {
+        self.map.unmap()
+    }
+
+    /// Returns a reference to the unmap target event if it has been set.
+    pub fn unmap_event(&self) -> Option<&Event> {
+        self.map.unmap_event()
+    }
+
+    /// Returns a reference to the unmap wait event list if it has been set.
+    pub fn unmap_wait_events(&self) -> Option<&EventList> {
+        self.map.unmap_wait_events()
+    }
+
+    /// Returns true if an unmap command has already been enqueued, causing
+    /// the memory referenced by this `MemMapReadOnly` to become invalid.
+    #[inline] pub fn is_unmapped(&self) -> bool { self.map.is_unmapped() }
+
+    /// Returns a pointer to the host mapped memory.
+    #[inline] pub fn as_ptr(&self) -> *const T { self.map.as_ptr() }
+
+    /// Returns a reference to the internal core command queue.
+    #[inline] pub fn queue(&self) -> &Queue { self.map.queue() }
+}
+
+impl<T> From<MemMap<T>> for MemMapReadOnly<T> where T: OclPrm {
+    fn from(map: MemMap<T>) -> MemMapReadOnly<T> {
+        MemMapReadOnly { map: map }
+    }
+}
+
+impl<T> Deref for MemMapReadOnly<T> where T: OclPrm {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.map
+    }
+}
+
+impl<T: OclPrm> AsMem<T> for MemMapReadOnly<T> {
+    fn as_mem(&self) -> &MemCore {
+        self.map.as_mem()
+    }
+}
+
 // impl<'a, T: OclPrm> AsMem for &'a mut MemMap<T> {
 //     fn as_mem(&self) -> &MemCore {
 //         self.core.as_mem()
@@ -12,8 +12,8 @@ mod buffer_stream;
 pub use self::order_lock::{OrderLock, ReadGuard, WriteGuard, FutureGuard, FutureReadGuard,
     FutureWriteGuard, OrderGuard};
 pub use self::rw_vec::RwVec;
-pub use self::mem_map::MemMap;
-pub use self::future_mem_map::FutureMemMap;
+pub use self::mem_map::{MemMap, MemMapReadOnly};
+pub use self::future_mem_map::{FutureMemMap, FutureMemMapReadOnly};
 pub use self::buffer_sink::{BufferSink, FutureFlush, Inner as BufferSinkInner};
 pub use self::buffer_stream::{BufferStream, FutureFlood, Inner as BufferStreamInner};
 
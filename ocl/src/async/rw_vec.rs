@@ -9,6 +9,7 @@
 
 use std::ops::{Deref, DerefMut};
 use async::{OrderLock, FutureGuard, ReadGuard, WriteGuard};
+use error::{Result as OclResult};
 use async::qutex::QrwLock;
 
 
@@ -57,6 +58,29 @@ impl<T> RwVec<T> {
         self.lock.write()
     }
 
+    /// Blocks the current thread until a read lock can be obtained and
+    /// returns the resulting guard.
+    ///
+    /// The lock is granted in order, once all previously requested write
+    /// guards (such as those held by a buffer read into this vec) have been
+    /// released. The guard dereferences to the contained `Vec` (use `::as_slice` etc.).
+    /// Unlike `::read`, this does not consume `self`.
+    pub fn read_lock(&self) -> OclResult<ReadGuard<Vec<T>>> {
+        self.lock.clone().read().wait()
+    }
+
+    /// Blocks the current thread until a write lock can be obtained and
+    /// returns the resulting guard.
+    ///
+    /// The lock is granted in order, once all previously requested read and
+    /// write guards (such as those held by buffer reads and writes using
+    /// this vec) have been released. The guard
+    /// dereferences mutably to the contained `Vec` (use `::as_mut_slice`
+    /// etc.). Unlike `::write`, this does not consume `self`.
+    pub fn write_lock(&self) -> OclResult<WriteGuard<Vec<T>>> {
+        self.lock.clone().write().wait()
+    }
+
     /// Returns a mutable slice into the contained `Vec`.
     ///
     /// Used by buffer command builders when preparing future read and write
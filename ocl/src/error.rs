@@ -8,7 +8,7 @@ use futures::sync::oneshot::Canceled as OneshotCanceled;
 use futures::sync::mpsc::SendError;
 use core::error::{Error as OclCoreError};
 use core::Status;
-use standard::{DeviceError, PlatformError, KernelError};
+use standard::{DeviceError, PlatformError, KernelError, TaskGraphError};
 
 use ::BufferCmdError;
 
@@ -38,6 +38,8 @@ pub enum ErrorKind {
     Platform(PlatformError),
     #[fail(display = "{}", _0)]
     Kernel(KernelError),
+    #[fail(display = "{}", _0)]
+    TaskGraph(TaskGraphError),
 }
 
 
@@ -55,6 +57,11 @@ impl Error {
         }
     }
 
+    /// Returns the raw error status code for `OclCore` variants.
+    pub fn status_code(&self) -> Option<i32> {
+        self.api_status().map(|status| status as i32)
+    }
+
     /// Returns the error variant and contents.
     pub fn kind(&self) -> &ErrorKind {
         self.inner.get_context()
@@ -175,6 +182,12 @@ impl From<KernelError> for Error {
     }
 }
 
+impl From<TaskGraphError> for Error {
+    fn from(err: TaskGraphError) -> Error {
+        Error { inner: Context::new(ErrorKind::TaskGraph(err)) }
+    }
+}
+
 impl From<Error> for String {
     fn from(err: Error) -> String {
         err.to_string()
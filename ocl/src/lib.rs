@@ -18,6 +18,31 @@
 //! [`ocl-core`] and [`cl-sys`] crates.
 //!
 //!
+//! ## Ownership
+//!
+//! The high-level handle types (`Context`, `Queue`, `Program`, `Buffer`,
+//! `Image`, `Sampler`, and `Event`) are reference counted by OpenCL itself.
+//! Cloning a handle retains the underlying object (`clRetain*`) and dropping
+//! a handle releases it (`clRelease*`). Each clone is independent: the
+//! original may be dropped while clones remain usable and the object is
+//! destroyed only when the last handle is dropped. Most of these types
+//! provide a `::reference_count` method to help debug leaks.
+//!
+//! `Kernel` is the exception. Kernel arguments are shared state so a
+//! `Kernel` may not be cloned. Clone the `KernelBuilder` instead.
+//!
+//!
+//! ## OpenGL Interoperability
+//!
+//! Sharing buffers and textures with OpenGL (`cl_khr_gl_sharing`) requires no
+//! cargo feature: create a context with `ContextBuilder::gl_context`, memory
+//! objects with `Buffer::from_gl_buffer` or `Image::from_gl_texture`, and
+//! hand them between APIs with `Queue::enqueue_acquire_gl_objects` and
+//! `Queue::enqueue_release_gl_objects`. These are unavailable when the
+//! `opencl_vendor_mesa` feature is enabled. Direct3D 11 sharing, being
+//! Windows-only, is gated behind the `d3d11_interop` feature instead.
+//!
+//!
 //! ## Help Wanted
 //!
 //! Please request or help complete any functionality you may need by filing
@@ -61,10 +86,15 @@ mod standard;
 pub mod error;
 pub mod async;
 
-pub use self::standard::{Platform, Extensions, Device, Context, Program, Queue, Kernel, Buffer, Image, Event,
-    EventList, EventArray, Sampler, SpatialDims, ProQue, BufferCmdError};
-pub use self::async::{MemMap, FutureMemMap, RwVec, ReadGuard, WriteGuard,
-    FutureReadGuard, FutureWriteGuard};
+pub use self::standard::{Platform, Extensions, Device, DeviceSummary, Context, Program, Queue,
+    Kernel, Buffer, Image, Event, EventList, EventArray, Sampler, SpatialDims, ProQue,
+    BufferCmdError, MappedGuard, BufferReadChunks, ImageMap, TaskGraph, TaskGraphError,
+    ReduceOp, ProfilingSummary, UserEvent, BufferPool, PooledBuffer, RetryPolicy,
+    QueueSet, QueueSetPolicy};
+#[cfg(feature = "opencl_version_2_0")]
+pub use self::standard::{SvmVec, Pipe};
+pub use self::async::{MemMap, FutureMemMap, MemMapReadOnly, FutureMemMapReadOnly, RwVec,
+    ReadGuard, WriteGuard, FutureReadGuard, FutureWriteGuard};
 pub use error::{Error, Result};
 pub use {core::Error as OclCoreError};
 #[doc(no_inline)]
@@ -108,6 +138,9 @@ pub mod prm {
 
     pub use ffi::{ cl_GLuint, cl_GLint, cl_GLenum };
 
+    // Half-precision types (`cl_khr_fp16`):
+    pub use core::{f16, Half4, Half8};
+
     // Wrapping types. Use these to mimic in-kernel behaviour:
     pub use core::{
         Char, Char2, Char3, Char4, Char8, Char16,
@@ -132,10 +165,11 @@ pub mod traits {
 pub mod builders {
     //! Builders and associated settings-related types.
 
-    pub use standard::{ContextBuilder, BuildOpt, ProgramBuilder, ImageBuilder, ProQueBuilder,
-        DeviceSpecifier, BufferCmdKind, BufferCmdDataShape, BufferCmd, BufferReadCmd,
-        BufferWriteCmd, BufferMapCmd, ImageCmdKind, ImageCmd, KernelCmd, BufferBuilder,
-        KernelBuilder};
+    pub use standard::{ContextBuilder, BuildOpt, BuildOpts, OptLevel, ProgramBuilder,
+        ImageBuilder, ProQueBuilder, DeviceSpecifier, DeviceSelector, BufferCmdKind,
+        BufferCmdDataShape, BufferCmd, BufferReadCmd, BufferWriteCmd, BufferMapCmd, ImageCmdKind,
+        ImageCmd, ImageMapCmd, KernelCmd, BufferBuilder, KernelBuilder, SamplerBuilder,
+        QueueBuilder};
     pub use standard::{ClNullEventPtrEnum, ClWaitListPtrEnum};
     pub use core::{ImageFormat, ImageDescriptor, ContextProperties};
     // #[cfg(not(release))] pub use standard::BufferTest;
@@ -162,7 +196,7 @@ pub mod flags {
         // cl_mem_flags - bitfield
         MemFlags, MEM_READ_WRITE, MEM_WRITE_ONLY, MEM_READ_ONLY, MEM_USE_HOST_PTR,
             MEM_ALLOC_HOST_PTR, MEM_COPY_HOST_PTR, MEM_HOST_WRITE_ONLY, MEM_HOST_READ_ONLY,
-            MEM_HOST_NO_ACCESS,
+            MEM_HOST_NO_ACCESS, MEM_SVM_FINE_GRAIN_BUFFER, MEM_SVM_ATOMICS,
         // cl_mem_migration_flags - bitfield
         MemMigrationFlags, MIGRATE_MEM_OBJECT_HOST, MIGRATE_MEM_OBJECT_CONTENT_UNDEFINED,
         // cl_map_flags - bitfield
@@ -6,9 +6,10 @@ use std::ops::{Deref, DerefMut, Range};
 use core::{self, Error as OclCoreError, Result as OclCoreResult, OclPrm, Mem as MemCore,
     MemFlags, MemInfo, MemInfoResult, BufferRegion, MapFlags, AsMem, MemCmdRw, MemCmdAll,
     ClNullEventPtr};
-use ::{Context, Queue, FutureMemMap, MemMap, Event, RwVec, FutureReadGuard, FutureWriteGuard,
-    SpatialDims};
-use standard::{ClNullEventPtrEnum, ClWaitListPtrEnum, HostSlice};
+use ::{Context, Queue, FutureMemMap, FutureMemMapReadOnly, MemMap, Event, RwVec,
+    FutureReadGuard, FutureWriteGuard, SpatialDims};
+use standard::{ClNullEventPtrEnum, ClWaitListPtrEnum, HostSlice, ReduceOp};
+use standard::reduce::reduce_buffer;
 use error::{Error as OclError, Result as OclResult};
 
 #[cfg(not(feature="opencl_vendor_mesa"))]
@@ -20,7 +21,8 @@ fn check_len(mem_len: usize, data_len: usize, offset: usize) -> OclResult<()> {
         Err(format!("ocl::Buffer::enq(): Offset out of range. \
             (mem_len: {}, data_len: {}, offset: {}", mem_len, data_len, offset).into())
     } else if data_len > (mem_len - offset) {
-        Err("ocl::Buffer::enq(): Data length exceeds buffer length.".into())
+        Err(format!("ocl::Buffer::enq(): Data length exceeds buffer length. \
+            (offset + data_len > mem_len: {} + {} > {})", offset, data_len, mem_len).into())
     } else {
         Ok(())
     }
@@ -351,13 +353,16 @@ impl<'c, T> BufferCmd<'c, T> where T: 'c + OclPrm {
     /// If `.block(..)` has been set it will be ignored.
     ///
     /// `pattern` is the vector or scalar value to repeat contiguously. `len`
-    /// is the overall size expressed in units of sizeof(T) If `len` is `None`,
-    /// the pattern will fill the entire buffer, otherwise, `len` must be
-    /// divisible by sizeof(`pattern`).
+    /// is the overall size expressed in units of sizeof(T). If `len` is
+    /// `None`, the pattern will fill the entire buffer.
     ///
-    /// As an example if you want to fill the first 100 `cl_float4` sized
-    /// elements of a buffer, `pattern` would be a `cl_float4` and `len` would
-    /// be 400.
+    /// As an example if you want to fill the first 100 elements of a
+    /// `Buffer<Float4>`, `pattern` would be a `Float4` and `len` would be
+    /// `Some(100)`.
+    ///
+    /// The size of `T` must be 1, 2, 4, 8, 16, 32, 64, or 128 bytes, otherwise
+    /// an error will be returned when enqueued. Use `.ewait(..)` and
+    /// `.enew(..)` to order the fill relative to other commands.
     ///
     /// ## Panics
     ///
@@ -552,6 +557,18 @@ impl<'c, T> BufferCmd<'c, T> where T: 'c + OclPrm {
                 }
             },
 
+            BufferCmdKind::CopyToImage { image, dst_origin, region } => {
+                match self.shape {
+                    BufferCmdDataShape::Lin { offset } => {
+                        core::enqueue_copy_buffer_to_image::<T, _, _, _>(queue,
+                            &self.buffer.obj_core, image, offset, dst_origin, region,
+                            self.ewait, self.enew).map_err(OclError::from)
+                    },
+                    BufferCmdDataShape::Rect { .. } => Err(
+                        "ocl::BufferCmd::enq(): Rectangular copy to image is not a valid \
+                        operation. Please use the default shape, linear.".into())
+                }
+            },
             #[cfg(not(feature="opencl_vendor_mesa"))]
             BufferCmdKind::Fill { pattern, len } => {
                 match self.shape {
@@ -635,6 +652,12 @@ impl<'d, T> From<RwVec<T>> for ReadDst<'d, T> where T: OclPrm {
     }
 }
 
+impl<'d, T> From<Vec<T>> for ReadDst<'d, T> where T: OclPrm {
+    fn from(vec: Vec<T>) -> ReadDst<'d, T> {
+        ReadDst::RwVec(RwVec::from(vec))
+    }
+}
+
 impl<'a, 'd, T> From<&'a RwVec<T>> for ReadDst<'d, T> where T: OclPrm {
     fn from(rw_vec: &'a RwVec<T>) -> ReadDst<'d, T> {
         ReadDst::RwVec(rw_vec.clone())
@@ -906,7 +929,8 @@ impl<'c, 'd, T> BufferReadCmd<'c, 'd, T> where T: OclPrm {
     /// within subsequent futures.
     ///
     /// A data destination container appropriate for an asynchronous operation
-    /// (such as `RwVec`) must have been passed to `::read`.
+    /// (an `RwVec`, a `FutureWriteGuard`, or an owned `Vec` which will be
+    /// wrapped in an `RwVec`) must have been passed to `::read`.
     ///
     pub fn enq_async(mut self) -> OclResult<FutureWriteGuard<Vec<T>>> {
         let queue = match self.cmd.queue {
@@ -1015,6 +1039,12 @@ impl<'d, T> From<RwVec<T>> for WriteSrc<'d, T> where T: OclPrm {
     }
 }
 
+impl<'d, T> From<Vec<T>> for WriteSrc<'d, T> where T: OclPrm {
+    fn from(vec: Vec<T>) -> WriteSrc<'d, T> {
+        WriteSrc::RwVec(RwVec::from(vec))
+    }
+}
+
 impl<'a, 'd, T> From<&'a RwVec<T>> for WriteSrc<'d, T> where T: OclPrm {
     fn from(rw_vec: &'a RwVec<T>) -> WriteSrc<'d, T> {
         WriteSrc::RwVec(rw_vec.clone())
@@ -1285,8 +1315,10 @@ impl<'c, 'd, T> BufferWriteCmd<'c, 'd, T> where T: OclPrm {
     /// Enqueues this command and returns a future representing its completion
     /// which resolves to a read guard usable within subsequent futures.
     ///
-    /// A data destination container appropriate for an asynchronous operation
-    /// (such as `RwVec`) must have been passed to `::write`.
+    /// A data source container appropriate for an asynchronous operation (an
+    /// `RwVec`, a `FutureReadGuard`, or an owned `Vec` which will be wrapped
+    /// in an `RwVec`) must have been passed to `::write`. The source data is
+    /// kept alive by the returned guard until the write has completed.
     ///
     /// The returned future must be resolved.
     ///
@@ -1646,6 +1678,182 @@ impl<'c, T> BufferMapCmd<'c, T> where T: OclPrm {
             self.cmd.buffer.obj_core.clone(), queue, /*is_mapped*/))
 
     }
+
+    /// Enqueues a map command for reading and returns a future representing
+    /// the completion of that map command.
+    ///
+    /// The returned future will resolve to a read-only view of the mapped
+    /// memory (a `MemMapReadOnly`), preventing accidental writes to a region
+    /// mapped only for reading.
+    ///
+    /// The map flags default to `MAP_READ` if not otherwise set. Returns an
+    /// error if `MAP_WRITE` or `MAP_WRITE_INVALIDATE_REGION` have been set.
+    ///
+    /// ## Safety
+    ///
+    /// The caller must ensure that no other mapping allows writes to the
+    /// same memory region while the returned view exists.
+    pub unsafe fn enq_async_read(mut self) -> OclResult<FutureMemMapReadOnly<T>> {
+        match self.flags {
+            Some(flags) => if flags.intersects(MapFlags::WRITE | MapFlags::WRITE_INVALIDATE_REGION) {
+                return Err(format!("BufferMapCmd::enq_async_read: Invalid map flags for a \
+                    read-only map: {:?}. Use '::enq_async' instead.", flags).into());
+            },
+            None => self.flags = Some(::flags::MAP_READ),
+        }
+
+        self.enq_async().map(FutureMemMapReadOnly::from)
+    }
+}
+
+
+
+/// A blocking view of a mapped buffer region which is unmapped when dropped.
+///
+/// Created by [`Buffer::map_sync`]. Accessing memory via a `MappedGuard` is
+/// exactly like using a [slice].
+///
+/// Dropping the guard enqueues the unmap command and blocks until it
+/// completes, ensuring that any writes made through the guard are visible to
+/// commands subsequently enqueued on any queue.
+///
+/// [`Buffer::map_sync`]: struct.Buffer.html#method.map_sync
+/// [slice]: https://doc.rust-lang.org/std/primitive.slice.html
+#[derive(Debug)]
+pub struct MappedGuard<T: OclPrm> {
+    mem_map: MemMap<T>,
+    unmap_queue: Option<Queue>,
+}
+
+impl<T: OclPrm> MappedGuard<T> {
+    /// Specifies a queue to use for the unmap command.
+    ///
+    /// Defaults to the queue used for the map command. Unmapping on a
+    /// different queue avoids deadlocks when the mapping queue is blocked
+    /// waiting on commands which depend on this region being unmapped.
+    pub fn unmap_queue(&mut self, queue: Queue) {
+        self.unmap_queue = Some(queue);
+    }
+
+    /// Unmaps the region, blocking until the unmap command completes.
+    ///
+    /// Equivalent to dropping the guard except that errors are returned.
+    pub fn unmap(mut self) -> OclResult<()> {
+        self.unmap_and_wait()
+    }
+
+    fn unmap_and_wait(&mut self) -> OclResult<()> {
+        if self.mem_map.is_unmapped() { return Ok(()); }
+        let mut unmap_event = Event::empty();
+        self.mem_map.enqueue_unmap::<&Event, _>(self.unmap_queue.as_ref(), None,
+            Some(&mut unmap_event))?;
+        unmap_event.wait_for()
+    }
+}
+
+impl<T: OclPrm> Deref for MappedGuard<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.mem_map
+    }
+}
+
+impl<T: OclPrm> DerefMut for MappedGuard<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.mem_map
+    }
+}
+
+impl<T: OclPrm> Drop for MappedGuard<T> {
+    fn drop(&mut self) {
+        self.unmap_and_wait().ok();
+    }
+}
+
+
+
+/// An iterator which downloads a buffer in successive chunks.
+///
+/// Created by [`Buffer::read_chunks`]. Each call to `next` blocks until the
+/// current chunk has been read then enqueues a read of the following chunk
+/// before returning, allowing the following transfer to overlap with
+/// processing of the current chunk.
+///
+/// Iteration stops after the first error.
+///
+/// [`Buffer::read_chunks`]: struct.Buffer.html#method.read_chunks
+#[derive(Debug)]
+pub struct BufferReadChunks<'b, T> where T: 'b + OclPrm {
+    buffer: &'b Buffer<T>,
+    queue: Option<Queue>,
+    chunk_len: usize,
+    offset: usize,
+    pending: Option<(Vec<T>, Event)>,
+}
+
+impl<'b, T> BufferReadChunks<'b, T> where T: OclPrm {
+    /// Enqueues a non-blocking read of the chunk at `self.offset`.
+    fn enqueue_next(&mut self) -> OclResult<()> {
+        let queue = match self.queue {
+            Some(ref q) => q,
+            None => return Err(BufferCmdError::NoQueue.into()),
+        };
+
+        let len = std::cmp::min(self.chunk_len, self.buffer.len() - self.offset);
+        let mut chunk = vec![T::default(); len];
+        let mut event = Event::empty();
+
+        // The destination's heap allocation is kept alive (and untouched)
+        // within `self.pending` until the read completes:
+        unsafe {
+            core::enqueue_read_buffer(queue, &self.buffer.obj_core, false, self.offset,
+                &mut chunk, None::<&Event>, Some(&mut event))?;
+        }
+
+        self.offset += len;
+        self.pending = Some((chunk, event));
+        Ok(())
+    }
+}
+
+impl<'b, T> Iterator for BufferReadChunks<'b, T> where T: OclPrm {
+    type Item = OclResult<Vec<T>>;
+
+    fn next(&mut self) -> Option<OclResult<Vec<T>>> {
+        if self.pending.is_none() && self.offset < self.buffer.len() {
+            if let Err(err) = self.enqueue_next() {
+                self.offset = self.buffer.len();
+                return Some(Err(err));
+            }
+        }
+
+        let (chunk, event) = self.pending.take()?;
+
+        if let Err(err) = event.wait_for() {
+            self.offset = self.buffer.len();
+            return Some(Err(err));
+        }
+
+        // Prefetch the next chunk:
+        if self.offset < self.buffer.len() {
+            if let Err(err) = self.enqueue_next() {
+                self.offset = self.buffer.len();
+                return Some(Err(err));
+            }
+        }
+
+        Some(Ok(chunk))
+    }
+}
+
+impl<'b, T> Drop for BufferReadChunks<'b, T> where T: OclPrm {
+    fn drop(&mut self) {
+        // Ensure an in-flight read does not write into freed memory:
+        if let Some((_, ref event)) = self.pending {
+            event.wait_for().ok();
+        }
+    }
 }
 
 
@@ -1655,6 +1863,11 @@ impl<'c, T> BufferMapCmd<'c, T> where T: OclPrm {
 /// Data is stored remotely in a memory buffer on the device associated with
 /// `queue`.
 ///
+/// ## Destruction
+///
+/// Handled automatically. Clones (and sub-buffers) retain the underlying
+/// memory object which is freed once the last of them is dropped.
+///
 #[derive(Debug, Clone)]
 pub struct Buffer<T: OclPrm> {
     obj_core: MemCore,
@@ -1724,6 +1937,31 @@ impl<T: OclPrm> Buffer<T> {
         Ok(buf)
     }
 
+    /// Creates a buffer with a length equal to that of `slice` and
+    /// initialized with a copy of its contents.
+    ///
+    /// `MEM_COPY_HOST_PTR` is added to `flags_opt` (which defaults to
+    /// `MEM_READ_WRITE`) implicitly. Flags which conflict with it (i.e.
+    /// `MEM_USE_HOST_PTR`, see `MemFlags::verify`) cause an error, as does an
+    /// empty `slice`. `slice` is not referred to after this call returns.
+    ///
+    /// ```rust,ignore
+    /// let buffer = Buffer::from_slice(&queue, None, &[1.0f32, 2.0, 3.0])?;
+    /// assert_eq!(buffer.len(), 3);
+    /// ```
+    pub fn from_slice<'o, Q>(que_ctx: Q, flags_opt: Option<MemFlags>, slice: &[T])
+            -> OclResult<Buffer<T>>
+            where Q: Into<QueCtx<'o>> {
+        let flags = flags_opt.unwrap_or(core::MEM_READ_WRITE) | core::MEM_COPY_HOST_PTR;
+        flags.verify()?;
+
+        if slice.is_empty() {
+            return Err("Buffer::from_slice: The slice must not be empty.".into());
+        }
+
+        unsafe { Buffer::new(que_ctx, flags, slice.len(), Some(slice)) }
+    }
+
     /// Creates a buffer linked to a previously created OpenGL buffer object.
     ///
     /// [UNTESTED]
@@ -1764,6 +2002,46 @@ impl<T: OclPrm> Buffer<T> {
         Ok(buf)
     }
 
+    /// Creates a buffer linked to a previously created Direct3D 11 buffer
+    /// (`ID3D11Buffer*`).
+    ///
+    /// The context must have been created with an associated Direct3D 11
+    /// device (`ContextBuilder::d3d11_device`). Use
+    /// `Queue::enqueue_acquire_d3d11_objects` before using the buffer and
+    /// `Queue::enqueue_release_d3d11_objects` after.
+    ///
+    /// ## Safety
+    ///
+    /// `resource` must be a valid `ID3D11Buffer*` created by the Direct3D 11
+    /// device associated with the context.
+    ///
+    /// [Feature: `d3d11_interop`, Windows only]
+    #[cfg(all(feature = "d3d11_interop", windows))]
+    pub unsafe fn from_d3d11_buffer<'o, Q>(que_ctx: Q, flags_opt: Option<MemFlags>,
+            resource: *mut ::ffi::c_void) -> OclResult<Buffer<T>>
+            where Q: Into<QueCtx<'o>> {
+        let flags = flags_opt.unwrap_or(core::MEM_READ_WRITE);
+        let que_ctx = que_ctx.into();
+
+        let obj_core = match que_ctx {
+            QueCtx::Queue(ref q) => core::create_from_d3d11_buffer(&q.context(), flags, resource)?,
+            QueCtx::Context(c) => core::create_from_d3d11_buffer(c, flags, resource)?,
+        };
+
+        let len = match core::get_mem_object_info(&obj_core, MemInfo::Size)? {
+            MemInfoResult::Size(len_bytes) => len_bytes / ::std::mem::size_of::<T>(),
+            _ => unreachable!(),
+        };
+
+        Ok(Buffer {
+            obj_core,
+            queue: que_ctx.into(),
+            len,
+            offset: None,
+            _data: PhantomData,
+        })
+    }
+
     /// Returns a command builder used to read, write, copy, etc.
     ///
     /// Call `.enq()` to enqueue the command.
@@ -1829,6 +2107,109 @@ impl<T: OclPrm> Buffer<T> {
         self.cmd().map()
     }
 
+    /// Reduces the contents of this buffer to a single value using `op`
+    /// (sum, min, max, or product), blocking until complete.
+    ///
+    /// The reduction kernel is generated for `T` and compiled on first use,
+    /// then cached (per thread) for subsequent reductions using the same
+    /// context, device, type, and operation. Cached programs retain their
+    /// context and can be dropped using `ReduceOp::evict_programs` or
+    /// `ReduceOp::clear_programs`. Any length is supported.
+    ///
+    /// `queue` overrides the default queue.
+    ///
+    /// Returns an error if `T` is not a real scalar type (an integer, `f32`,
+    /// or `f64`) or if `T` is `f64` and the device does not support
+    /// `cl_khr_fp64`.
+    /// Integer sums and products wrap on overflow.
+    pub fn reduce(&self, queue: Option<&Queue>, op: ReduceOp) -> OclResult<T> {
+        reduce_buffer(self, queue, op)
+    }
+
+    /// Returns an iterator which downloads this buffer in successive chunks
+    /// of `chunk_len` elements (the final chunk may be shorter).
+    ///
+    /// The read of each chunk is enqueued before the previous chunk is
+    /// returned, overlapping transfers with processing. Only two chunks are
+    /// held in host memory at a time, making this suitable for buffers too
+    /// large to download at once.
+    ///
+    /// `queue` overrides the default queue.
+    ///
+    /// ## Panics
+    ///
+    /// `chunk_len` must be non-zero.
+    pub fn read_chunks<'b>(&'b self, queue: Option<&Queue>, chunk_len: usize)
+            -> BufferReadChunks<'b, T> {
+        assert!(chunk_len > 0, "Buffer::read_chunks: 'chunk_len' must be non-zero.");
+
+        BufferReadChunks {
+            buffer: self,
+            queue: queue.or(self.queue.as_ref()).cloned(),
+            chunk_len,
+            offset: 0,
+            pending: None,
+        }
+    }
+
+    /// Writes the contents of `iter` directly into this buffer, blocking
+    /// until complete.
+    ///
+    /// The buffer is mapped with `MAP_WRITE_INVALIDATE_REGION`, filled in
+    /// place from the iterator, then unmapped, avoiding the allocation of an
+    /// intermediate `Vec`. The previous contents of the buffer are discarded.
+    ///
+    /// `queue` overrides the default queue.
+    ///
+    /// Returns an error if the length of the iterator does not equal the
+    /// length of the buffer.
+    pub fn write_iter<I>(&self, queue: Option<&Queue>, iter: I) -> OclResult<()>
+            where I: IntoIterator<Item=T>, I::IntoIter: ExactSizeIterator {
+        let iter = iter.into_iter();
+        if iter.len() != self.len() {
+            return Err(format!("Buffer::write_iter: The iterator length ({}) does not equal \
+                the buffer length ({}).", iter.len(), self.len()).into());
+        }
+
+        let mut guard = unsafe { self.map_sync(queue, ::flags::MAP_WRITE_INVALIDATE_REGION)? };
+        let mut count = 0;
+        for (dst, val) in guard.iter_mut().zip(iter) {
+            *dst = val;
+            count += 1;
+        }
+        guard.unmap()?;
+
+        if count != self.len() {
+            return Err(format!("Buffer::write_iter: The iterator produced fewer elements ({}) \
+                than its reported length ({}).", count, self.len()).into());
+        }
+        Ok(())
+    }
+
+    /// Maps the entire buffer into the host address space, blocking until
+    /// the map command completes, and returns a guard which unmaps the
+    /// region when dropped.
+    ///
+    /// `queue` overrides the default queue for both the map and unmap
+    /// commands. Use `MappedGuard::unmap_queue` to unmap using a different
+    /// queue.
+    ///
+    /// This is a simpler, non-async alternative to `::map`.
+    ///
+    /// ## Safety
+    ///
+    /// The caller must ensure that either only one mapping of a buffer exists
+    /// at a time or that, if simultaneously mapping for the purposes of
+    /// sub-region access or whole-buffer aliasing, no two mappings will allow
+    /// writes to the same memory region at the same time.
+    pub unsafe fn map_sync(&self, queue: Option<&Queue>, flags: MapFlags)
+            -> OclResult<MappedGuard<T>> {
+        let mut cmd = self.map().flags(flags);
+        if let Some(q) = queue { cmd = cmd.queue(q); }
+        let mem_map = cmd.enq()?;
+        Ok(MappedGuard { mem_map, unmap_queue: None })
+    }
+
     /// Specifies that this command will be a copy operation.
     ///
     /// Call `.enq()` to enqueue the command.
@@ -1904,6 +2285,17 @@ impl<T: OclPrm> Buffer<T> {
         core::get_mem_object_info(&self.obj_core, info_kind)
     }
 
+    /// Returns the current reference count of the underlying memory object.
+    ///
+    /// Intended for debugging leaks; the value may be stale as soon as it is
+    /// returned.
+    pub fn reference_count(&self) -> OclResult<u32> {
+        match self.mem_info(MemInfo::ReferenceCount)? {
+            MemInfoResult::ReferenceCount(count) => Ok(count),
+            _ => unreachable!(),
+        }
+    }
+
     /// Changes the default queue used by this buffer for all subsequent
     /// command enqueue operations (reads, writes, etc.).
     ///
@@ -1954,6 +2346,35 @@ impl<T: OclPrm> Buffer<T> {
         self.queue.as_ref()
     }
 
+    /// Swaps the underlying memory object of this buffer with that of
+    /// `other`, along with its length and sub-buffer offset.
+    ///
+    /// Each buffer keeps its own default queue. This is intended for
+    /// double-buffering (ping-ponging) between iterations of a cyclical
+    /// pipeline: code which refers to `src` and `dst` by name can keep doing
+    /// so while their roles alternate.
+    ///
+    /// Commands which have already been enqueued refer to the memory objects
+    /// themselves, not to these wrappers, so swapping between iterations is
+    /// safe even while those commands are still outstanding. For the same
+    /// reason, kernel arguments set from either buffer continue to refer to
+    /// the memory object they were set with. Reset them (e.g. with
+    /// `Kernel::set_arg`) after swapping.
+    ///
+    /// ```rust,ignore
+    /// for _ in 0..iters {
+    ///     kernel.set_arg("src", &src)?;
+    ///     kernel.set_arg("dst", &dst)?;
+    ///     unsafe { kernel.enq()?; }
+    ///     src.swap(&mut dst);
+    /// }
+    /// ```
+    pub fn swap(&mut self, other: &mut Buffer<T>) {
+        std::mem::swap(&mut self.obj_core, &mut other.obj_core);
+        std::mem::swap(&mut self.len, &mut other.len);
+        std::mem::swap(&mut self.offset, &mut other.offset);
+    }
+
     /// Returns a reference to the core pointer wrapper, usable by functions in
     /// the `core` module.
     ///
@@ -1972,6 +2393,17 @@ impl<T: OclPrm> Buffer<T> {
         }
     }
 
+    /// Returns the actual size of the underlying memory object in bytes as
+    /// reported by the platform.
+    ///
+    #[inline]
+    pub fn mem_size(&self) -> OclResult<usize> {
+        match self.mem_info(MemInfo::Size)? {
+            MemInfoResult::Size(size) => Ok(size),
+            _ => unreachable!(),
+        }
+    }
+
     // /// Returns a reference to the `AtomicBool` tracking whether or not this
     // /// buffer is mapped.
     // ///
@@ -2063,6 +2495,39 @@ impl<T: OclPrm> Buffer<T> {
         })
     }
 
+    /// Converts this buffer into a buffer of a different element type
+    /// sharing the same underlying memory object.
+    ///
+    /// The size of this buffer (and its sub-buffer offset, if any), in bytes,
+    /// must be a multiple of the size of `U`. No data is copied or
+    /// reallocated.
+    ///
+    /// ```rust,ignore
+    /// let staging = Buffer::<u8>::builder().queue(queue).len(16).build()?;
+    /// let vectors: Buffer<Int4> = staging.reinterpret()?;
+    /// assert_eq!(vectors.len(), 1);
+    /// ```
+    pub fn reinterpret<U: OclPrm>(self) -> OclResult<Buffer<U>> {
+        let t_size = std::mem::size_of::<T>();
+        let u_size = std::mem::size_of::<U>();
+        let byte_len = self.len * t_size;
+        let byte_offset = self.offset.map(|o| o * t_size);
+
+        if byte_len % u_size != 0 || byte_offset.map(|o| o % u_size != 0).unwrap_or(false) {
+            return Err(format!("Buffer::reinterpret: The buffer size ({} bytes) and offset \
+                ({:?} bytes) must be multiples of the size of the new element type ({} bytes).",
+                byte_len, byte_offset, u_size).into());
+        }
+
+        Ok(Buffer {
+            obj_core: self.obj_core,
+            queue: self.queue,
+            len: byte_len / u_size,
+            offset: byte_offset.map(|o| o / u_size),
+            _data: PhantomData,
+        })
+    }
+
     /// Formats memory info.
     #[inline]
     fn fmt_mem_info(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -2139,7 +2604,8 @@ pub struct BufferBuilder<'a, T> where T: OclPrm {
     flags: Option<MemFlags>,
     host_slice: HostSlice<'a, T>,
     len: usize,
-    fill_val: Option<(T, Option<ClNullEventPtrEnum<'a>>)>
+    fill_val: Option<(T, Option<ClNullEventPtrEnum<'a>>)>,
+    check_alloc_size: bool,
 }
 
 impl<'a, T> BufferBuilder<'a, T> where T: 'a + OclPrm {
@@ -2151,6 +2617,7 @@ impl<'a, T> BufferBuilder<'a, T> where T: 'a + OclPrm {
             host_slice: HostSlice::None,
             len: 0,
             fill_val: None,
+            check_alloc_size: true,
         }
     }
 
@@ -2308,6 +2775,16 @@ impl<'a, T> BufferBuilder<'a, T> where T: 'a + OclPrm {
         self
     }
 
+    /// Fills the buffer with zeros (`T::default()`) immediately after
+    /// creation.
+    ///
+    /// Shorthand for `::fill_val(T::default())`. A queue must be specified
+    /// with `::queue`. The fill command blocks unless `::fill_event` is
+    /// used.
+    pub fn zeroed(self) -> BufferBuilder<'a, T> {
+        self.fill_val(T::default())
+    }
+
     /// Specifies the (empty) event to use for association with the completion
     /// of the fill command.
     ///
@@ -2326,17 +2803,35 @@ impl<'a, T> BufferBuilder<'a, T> where T: 'a + OclPrm {
     pub fn fill_event<'b, 'e, En>(mut self, enew: En) -> BufferBuilder<'a, T>
             where 'e: 'a, En: Into<ClNullEventPtrEnum<'e>> {
         match self.fill_val {
-            Some(ref fv) => assert!(fv.1.is_some(), "Buffer::fill_event: Fill event already set."),
+            Some(ref fv) => assert!(fv.1.is_none(), "Buffer::fill_event: Fill event already set."),
             None => panic!("Buffer::fill_event: Fill value must be set first"),
         }
         self.fill_val = self.fill_val.take().map(|fv| (fv.0, Some(enew.into())));
         self
     }
 
+    /// Disables the check, performed by `::build`, that the size of the
+    /// buffer does not exceed the maximum allocation size
+    /// (`Device::max_mem_alloc_size`) of each associated device.
+    ///
+    /// Some drivers report a conservative maximum and will successfully
+    /// allocate larger buffers.
+    pub fn skip_alloc_size_check(mut self) -> BufferBuilder<'a, T> {
+        self.check_alloc_size = false;
+        self
+    }
+
     /// Creates a buffer and returns it.
     ///
     /// Dimensions and either a context or default queue must be specified
     /// before calling `::build`.
+    ///
+    /// Returns an error if the resulting flags contain mutually exclusive
+    /// bits (see `MemFlags::verify`), such as `READ_ONLY | WRITE_ONLY` or
+    /// `ALLOC_HOST_PTR` combined with `::use_host_slice`. Also returns an
+    /// error if the size of the buffer exceeds the maximum allocation size
+    /// of any associated device unless `::skip_alloc_size_check` has been
+    /// called.
     pub fn build(self) -> OclResult<Buffer<T>> {
         let mut flags = match self.flags {
             Some(f) => f,
@@ -2360,18 +2855,43 @@ impl<'a, T> BufferBuilder<'a, T> where T: 'a + OclPrm {
             HostSlice::None => None,
         };
 
+        // Reject contradictory flags before they reach the driver, which
+        // would otherwise report only a generic `CL_INVALID_VALUE`.
+        flags.verify()?;
+
         let qc = match self.queue_option {
             Some(qc) => qc,
             None => panic!("ocl::BufferBuilder::build: A context or default queue must be set \
                 with '.context(...)' or '.queue(...)'."),
         };
 
+        // Zero-sized allocations are invalid and are handled inconsistently
+        // across platforms.
         let len = match self.len {
-            0 => panic!("ocl::BufferBuilder::build: The length must be set with \
-                '.len(...)' and cannot be zero."),
+            0 => return Err("ocl::BufferBuilder::build: The length must be set with \
+                '.len(...)' and cannot be zero (zero-length buffers are not supported).".into()),
             l @ _ => l,
         };
 
+        if self.check_alloc_size {
+            let devices = match qc {
+                QueCtx::Queue(ref queue) => vec![queue.device()],
+                QueCtx::Context(context) => context.devices(),
+            };
+            let size_bytes = (len as u64).saturating_mul(std::mem::size_of::<T>() as u64);
+
+            for device in devices {
+                let max_size = device.max_mem_alloc_size()?;
+                if size_bytes > max_size {
+                    return Err(format!("ocl::BufferBuilder::build: The requested buffer size \
+                        ({} bytes) exceeds the maximum allocation size of device '{}' ({} bytes). \
+                        Use '::skip_alloc_size_check' to attempt the allocation anyway.",
+                        size_bytes, device.name().unwrap_or_else(|_| "[unknown]".into()),
+                        max_size).into());
+                }
+            }
+        }
+
         let device_ver = match qc {
             QueCtx::Queue(ref queue) => Some(queue.device_version()),
             QueCtx::Context(_) => None,
@@ -0,0 +1,201 @@
+//! A pool of recyclable buffers.
+
+use std::mem;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+use core::{self, OclPrm, MemFlags, CommandExecutionStatus};
+use error::{Result as OclResult};
+use standard::{Buffer, Queue, Event, EventList};
+
+
+/// A buffer awaiting reuse along with the events of commands which may
+/// still be using it.
+#[derive(Debug)]
+struct PoolEntry<T: OclPrm> {
+    buffer: Buffer<T>,
+    events: EventList,
+}
+
+impl<T: OclPrm> PoolEntry<T> {
+    /// Returns true if every tracked command has completed.
+    ///
+    /// Commands which have terminated abnormally (with a negative, error
+    /// status) are considered finished, as they would otherwise prevent the
+    /// buffer from ever being reused. Events whose status cannot be queried
+    /// are considered busy.
+    fn is_idle(&self) -> bool {
+        self.events.as_slice().iter().all(|ev| {
+            // An empty event is not associated with any command:
+            if ev.is_empty() { return true; }
+
+            match core::event_status_code(ev.as_core()) {
+                Ok(status) => status <= CommandExecutionStatus::Complete as i32,
+                Err(_) => false,
+            }
+        })
+    }
+}
+
+
+/// A pool of buffers, recycling allocations of matching length.
+///
+/// Buffers are handed out as `PooledBuffer`s by `::acquire` and returned to
+/// the pool when dropped. An acquired buffer of a given length reuses a
+/// previously returned allocation of the same length if one is available
+/// and every command tracked (with `PooledBuffer::track_event`) on it has
+/// completed.
+///
+/// The number of buffers held awaiting reuse is unbounded unless limited
+/// with `::with_max_free`.
+///
+/// Cloning a `BufferPool` produces a handle to the same pool.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// let pool = BufferPool::<f32>::new(queue.clone(), None);
+///
+/// for _ in 0..iters {
+///     let mut buffer = pool.acquire(len)?;
+///     let mut event = Event::empty();
+///     kernel.set_arg("buf", &*buffer)?;
+///     unsafe { kernel.cmd().enew(&mut event).enq()?; }
+///     buffer.track_event(event);
+///     // `buffer` returns to the pool here and will not be reused until the
+///     // kernel command completes.
+/// }
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct BufferPool<T: OclPrm> {
+    queue: Queue,
+    flags: Option<MemFlags>,
+    max_free: Option<usize>,
+    free: Arc<Mutex<Vec<PoolEntry<T>>>>,
+}
+
+impl<T: OclPrm> BufferPool<T> {
+    /// Returns a new, empty pool which creates buffers using `queue` as
+    /// their default queue and `flags` (defaulting to `MEM_READ_WRITE`).
+    pub fn new(queue: Queue, flags: Option<MemFlags>) -> BufferPool<T> {
+        BufferPool {
+            queue: queue,
+            flags: flags,
+            max_free: None,
+            free: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Limits the number of buffers held awaiting reuse to `max_free`. When
+    /// a buffer is returned to a full pool, the least recently returned
+    /// buffer is released.
+    ///
+    /// Applies to buffers acquired after this call.
+    pub fn with_max_free(mut self, max_free: usize) -> BufferPool<T> {
+        self.max_free = Some(max_free);
+        self
+    }
+
+    /// Returns a buffer of length `len`, reusing an idle buffer of the same
+    /// length if one is available and allocating a new one otherwise.
+    ///
+    /// The contents of a reused buffer are those left by its previous user.
+    pub fn acquire(&self, len: usize) -> OclResult<PooledBuffer<T>> {
+        let reused = {
+            let mut free = self.free.lock().expect("BufferPool::acquire: Lock poisoned.");
+            let idx = free.iter().position(|e| e.buffer.len() == len && e.is_idle());
+            idx.map(|idx| free.swap_remove(idx).buffer)
+        };
+
+        let buffer = match reused {
+            Some(buffer) => buffer,
+            None => {
+                let mut builder = Buffer::<T>::builder()
+                    .queue(self.queue.clone())
+                    .len(len);
+                if let Some(flags) = self.flags {
+                    builder = builder.flags(flags);
+                }
+                builder.build()?
+            },
+        };
+
+        Ok(PooledBuffer {
+            buffer: Some(buffer),
+            events: EventList::new(),
+            max_free: self.max_free,
+            free: self.free.clone(),
+        })
+    }
+
+    /// Returns the number of buffers currently held by the pool awaiting
+    /// reuse.
+    pub fn free_count(&self) -> usize {
+        self.free.lock().expect("BufferPool::free_count: Lock poisoned.").len()
+    }
+
+    /// Releases all buffers currently held by the pool.
+    ///
+    /// Buffers still acquired are unaffected and will return to the pool
+    /// when dropped.
+    pub fn clear(&self) {
+        self.free.lock().expect("BufferPool::clear: Lock poisoned.").clear();
+    }
+}
+
+
+/// A buffer acquired from a `BufferPool` which is returned to the pool when
+/// dropped.
+///
+/// Derefs to `Buffer<T>`.
+#[derive(Debug)]
+pub struct PooledBuffer<T: OclPrm> {
+    buffer: Option<Buffer<T>>,
+    events: EventList,
+    max_free: Option<usize>,
+    free: Arc<Mutex<Vec<PoolEntry<T>>>>,
+}
+
+impl<T: OclPrm> PooledBuffer<T> {
+    /// Tracks the event of a command using this buffer, preventing the
+    /// buffer from being reused after being returned to the pool until the
+    /// command has completed.
+    pub fn track_event<E: Into<Event>>(&mut self, event: E) {
+        self.events.push(event);
+    }
+
+    /// Returns the buffer.
+    #[inline]
+    pub fn buffer(&self) -> &Buffer<T> {
+        self.buffer.as_ref().expect("PooledBuffer::buffer: No buffer.")
+    }
+}
+
+impl<T: OclPrm> Deref for PooledBuffer<T> {
+    type Target = Buffer<T>;
+
+    fn deref(&self) -> &Buffer<T> {
+        self.buffer()
+    }
+}
+
+impl<T: OclPrm> Drop for PooledBuffer<T> {
+    fn drop(&mut self) {
+        if let Some(buffer) = self.buffer.take() {
+            // Discard events which have already completed:
+            self.events.clear_completed().ok();
+            let events = mem::replace(&mut self.events, EventList::new());
+            let entry = PoolEntry { buffer: buffer, events: events };
+
+            if let Ok(mut free) = self.free.lock() {
+                if let Some(max_free) = self.max_free {
+                    if max_free == 0 { return; }
+                    while free.len() >= max_free {
+                        free.remove(0);
+                    }
+                }
+                free.push(entry);
+            }
+        }
+    }
+}
@@ -9,6 +9,10 @@ use core::{self, Context as ContextCore, ContextProperties, ContextPropertyValue
 use core::error::{Result as OclCoreResult};
 use error::{Error as OclError, Result as OclResult};
 use standard::{Platform, Device, DeviceSpecifier};
+#[cfg(feature = "opencl_version_2_0")]
+use core::{OclPrm, MemFlags};
+#[cfg(feature = "opencl_version_2_0")]
+use standard::SvmVec;
 
 
 
@@ -125,6 +129,36 @@ impl Context {
         core::get_context_info(&self.0, info_kind).map_err(OclError::from)
     }
 
+    /// Returns the current reference count of the underlying context object.
+    ///
+    /// Intended for debugging leaks; the value may be stale as soon as it is
+    /// returned.
+    pub fn reference_count(&self) -> OclResult<u32> {
+        match self.info(ContextInfo::ReferenceCount)? {
+            ContextInfoResult::ReferenceCount(count) => Ok(count),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the number of devices associated with this context.
+    ///
+    /// This is a cheap way to sanity-check a context without retrieving (and
+    /// allocating) the full device list.
+    pub fn num_devices(&self) -> OclResult<u32> {
+        match self.info(ContextInfo::NumDevices)? {
+            ContextInfoResult::NumDevices(num) => Ok(num),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the properties this context was created with.
+    pub fn properties(&self) -> OclResult<ContextProperties> {
+        match self.info(ContextInfo::Properties)? {
+            ContextInfoResult::Properties(props) => Ok(props),
+            _ => unreachable!(),
+        }
+    }
+
     /// Returns a reference to the core pointer wrapper, usable by functions in
     /// the `core` module.
     #[inline]
@@ -145,6 +179,24 @@ impl Context {
             .map(|d| d.version().map_err(OclError::from)).collect()
     }
 
+    /// Allocates a shared virtual memory vector of `len` elements, usable by
+    /// the host and by kernels on any device within this context.
+    ///
+    /// `flags` may include `MemFlags::SVM_FINE_GRAIN_BUFFER` and
+    /// `MemFlags::SVM_ATOMICS` in addition to the usual read/write flags if
+    /// supported by each device.
+    ///
+    /// Returns an error if any device within this context does not support
+    /// OpenCL 2.0.
+    ///
+    /// ## Safety
+    ///
+    /// The contents are uninitialized. See `SvmVec::new`.
+    #[cfg(feature = "opencl_version_2_0")]
+    pub unsafe fn svm_alloc<T: OclPrm>(&self, len: usize, flags: MemFlags) -> OclResult<SvmVec<T>> {
+        SvmVec::new(self, len, flags)
+    }
+
     /// Returns the platform this context is associated with.
     pub fn platform(&self) -> OclResult<Option<Platform>> {
         self.0.platform().map(|opt| opt.map(Platform::from)).map_err(OclError::from)
@@ -265,6 +317,14 @@ impl ContextBuilder {
         self
     }
 
+    /// Specifies whether the user is responsible for synchronization between
+    /// OpenCL and other APIs (such as OpenGL).
+    ///
+    pub fn interop_user_sync(&mut self, sync: bool) -> &mut ContextBuilder {
+        self.properties.set_interop_user_sync(sync);
+        self
+    }
+
     /// Specifies an OpenGL context to associate with.
     ///
     /// Overwrites any previously specified OpenGL context.
@@ -283,6 +343,18 @@ impl ContextBuilder {
         self
     }
 
+    /// Specifies an `ID3D11Device*` to associate with for Direct3D 11
+    /// interoperability.
+    ///
+    /// Overwrites any previously specified Direct3D 11 device.
+    ///
+    /// [Feature: `d3d11_interop`, Windows only]
+    #[cfg(all(feature = "d3d11_interop", windows))]
+    pub fn d3d11_device(&mut self, d3d11_device: *mut ::ffi::c_void) -> &mut ContextBuilder {
+        self.properties.set_d3d11_device(d3d11_device);
+        self
+    }
+
     /// Specifies a list of devices with which to associate the context.
     ///
     /// Devices may be specified in any number of ways including simply
@@ -4,7 +4,8 @@ use std;
 use std::ops::{Deref, DerefMut};
 use std::borrow::Borrow;
 use ffi::cl_device_id;
-use core::{self, util, DeviceId as DeviceIdCore, DeviceType, DeviceInfo, DeviceInfoResult, ClDeviceIdPtr};
+use core::{self, util, DeviceId as DeviceIdCore, DeviceType, DeviceInfo, DeviceInfoResult, ClDeviceIdPtr,
+    OpenclVersion};
 use error::{Error as OclError, Result as OclResult};
 use standard::Platform;
 
@@ -19,6 +20,8 @@ pub enum DeviceError {
     #[fail(display = "An index in the resolve list is out of range (index: {}, max: {})",
         idx, max)]
     ResolveIdxsInvalidIndex { idx: usize, max: usize },
+    #[fail(display = "No device matches the selection criteria: {:?}", _0)]
+    SelectNoMatch(DeviceSelector),
 }
 
 // Perhaps add something like this to the `DeviceSpecifier`.
@@ -245,6 +248,114 @@ impl From<DeviceType> for DeviceSpecifier {
 }
 
 
+/// Selects the single most suitable device across all platforms.
+///
+/// Filters (`name_contains`, `min_compute_units`) narrow the list of
+/// candidates. Preferences (`prefer_gpu`, `max_global_mem`) rank the remaining
+/// candidates, applied in that order, with ties broken by platform and device
+/// order.
+///
+/// Create using `Device::select`.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// let device = Device::select()
+///     .prefer_gpu()
+///     .max_global_mem()
+///     .min_compute_units(16)
+///     .resolve()?;
+/// ```
+///
+#[derive(Debug, Clone, Default)]
+pub struct DeviceSelector {
+    prefer_gpu: bool,
+    max_global_mem: bool,
+    name_contains: Option<String>,
+    min_compute_units: Option<u32>,
+}
+
+impl DeviceSelector {
+    /// Returns a new selector which matches any device.
+    pub fn new() -> DeviceSelector {
+        DeviceSelector::default()
+    }
+
+    /// Ranks GPU devices above all others.
+    pub fn prefer_gpu(mut self) -> DeviceSelector {
+        self.prefer_gpu = true;
+        self
+    }
+
+    /// Ranks devices with more global memory above those with less.
+    pub fn max_global_mem(mut self) -> DeviceSelector {
+        self.max_global_mem = true;
+        self
+    }
+
+    /// Excludes devices whose name does not contain `name`
+    /// (case-insensitive).
+    pub fn name_contains<S: Into<String>>(mut self, name: S) -> DeviceSelector {
+        self.name_contains = Some(name.into());
+        self
+    }
+
+    /// Excludes devices with fewer than `count` compute units.
+    pub fn min_compute_units(mut self, count: u32) -> DeviceSelector {
+        self.min_compute_units = Some(count);
+        self
+    }
+
+    /// Returns the devices within `devices` which pass all filters, ordered
+    /// from most to least preferred.
+    pub fn filter(&self, devices: &[Device]) -> OclResult<Vec<Device>> {
+        macro_rules! info {
+            ($device:expr, $variant:ident) => (
+                match $device.info(DeviceInfo::$variant)? {
+                    DeviceInfoResult::$variant(r) => r,
+                    _ => unreachable!(),
+                }
+            )
+        }
+
+        let mut candidates = Vec::with_capacity(devices.len());
+
+        for &device in devices {
+            if let Some(ref name) = self.name_contains {
+                if !device.name()?.to_lowercase().contains(&name.to_lowercase()) { continue; }
+            }
+
+            if let Some(count) = self.min_compute_units {
+                if info!(device, MaxComputeUnits) < count { continue; }
+            }
+
+            let is_gpu = self.prefer_gpu && device.is_gpu()?;
+            let global_mem = if self.max_global_mem { info!(device, GlobalMemSize) } else { 0 };
+            candidates.push((device, is_gpu, global_mem));
+        }
+
+        // Stable, so that ties retain their original order:
+        candidates.sort_by(|a, b| (b.1, b.2).cmp(&(a.1, a.2)));
+        Ok(candidates.into_iter().map(|(device, _, _)| device).collect())
+    }
+
+    /// Returns the most preferred device among all devices on all platforms
+    /// or an error if no device passes all filters.
+    pub fn resolve(&self) -> OclResult<Device> {
+        self.resolve_from(&Device::list_all_platforms()?)
+    }
+
+    /// Returns the most preferred device within `devices` or an error if no
+    /// device passes all filters.
+    pub fn resolve_from(&self, devices: &[Device]) -> OclResult<Device> {
+        match self.filter(devices)?.first() {
+            Some(&device) => Ok(device),
+            None => Err(DeviceError::SelectNoMatch(self.clone()).into()),
+        }
+    }
+}
+
+
 /// An individual device identifier (an OpenCL device_id).
 ///
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -274,6 +385,12 @@ impl Device {
         DeviceSpecifier::default()
     }
 
+    /// Returns a `DeviceSelector` useful for choosing the most suitable
+    /// device across all platforms.
+    pub fn select() -> DeviceSelector {
+        DeviceSelector::new()
+    }
+
     /// Resolves a list of indices into a list of valid devices.
     ///
     /// `devices` is the set of all indexable devices.
@@ -344,6 +461,20 @@ impl Device {
         Self::list(platform, None)
     }
 
+    /// Returns a list of all devices avaliable on every platform, ordered by
+    /// platform (as listed by `Platform::list`).
+    ///
+    /// See [`::list`](struct.Device.html#method.list) for other
+    /// error information.
+    ///
+    pub fn list_all_platforms() -> OclResult<Vec<Device>> {
+        let mut devices = Vec::with_capacity(16);
+        for platform in Platform::list() {
+            devices.extend(Self::list_all(platform)?);
+        }
+        Ok(devices)
+    }
+
     /// Returns a list of devices filtered by type then selected using a
     /// list of indices.
     ///
@@ -407,6 +538,16 @@ impl Device {
         }
     }
 
+    /// Returns the maximum size of a single memory object allocation, in
+    /// bytes, or an error.
+    pub fn max_mem_alloc_size(&self) -> OclResult<u64> {
+        match self.info(DeviceInfo::MaxMemAllocSize) {
+            Ok(DeviceInfoResult::MaxMemAllocSize(r)) => Ok(r),
+            Err(err) => Err(OclError::from(err)),
+            _ => panic!("Device::max_mem_alloc_size: Unexpected 'DeviceInfoResult' variant."),
+        }
+    }
+
     /// Returns the memory base address alignment offset or an error.
     pub fn mem_base_addr_align(&self) -> OclResult<u32> {
         match self.info(DeviceInfo::MemBaseAddrAlign) {
@@ -416,6 +557,19 @@ impl Device {
         }
     }
 
+    /// Returns true if the device supports OpenCL version `major.minor` or
+    /// higher.
+    ///
+    /// Returns false if the device version cannot be determined. Use
+    /// `::version` (available through `Deref`) to retrieve the version
+    /// itself.
+    pub fn supports(&self, major: u16, minor: u16) -> bool {
+        match self.version() {
+            Ok(ver) => ver >= OpenclVersion::new(major, minor),
+            Err(_) => false,
+        }
+    }
+
     /// Returns whether or not the device is available for use.
     pub fn is_available(&self) -> OclResult<bool> {
         match self.info(DeviceInfo::Available) {
@@ -425,6 +579,52 @@ impl Device {
         }
     }
 
+    /// Returns a synchronized pair of timestamps, `(device_timestamp,
+    /// host_timestamp)`, in nanoseconds.
+    ///
+    /// The device timestamp shares a timebase with event profiling info,
+    /// allowing profiling timestamps to be aligned with the host timeline.
+    ///
+    /// Requires OpenCL 2.1+.
+    #[cfg(feature = "opencl_version_2_1")]
+    pub fn device_and_host_timer(&self) -> OclResult<(u64, u64)> {
+        core::get_device_and_host_timer(&self.0, None).map_err(OclError::from)
+    }
+
+    /// Returns the current value of the host clock, in nanoseconds, in the
+    /// same timebase as the host timestamp returned by
+    /// `::device_and_host_timer`.
+    ///
+    /// Requires OpenCL 2.1+.
+    #[cfg(feature = "opencl_version_2_1")]
+    pub fn host_timer(&self) -> OclResult<u64> {
+        core::get_host_timer(&self.0, None).map_err(OclError::from)
+    }
+
+    /// Returns the device type.
+    pub fn device_type(&self) -> OclResult<DeviceType> {
+        match self.info(DeviceInfo::Type) {
+            Ok(DeviceInfoResult::Type(r)) => Ok(r),
+            Err(err) => Err(OclError::from(err)),
+            _ => panic!("Device::device_type: Unexpected 'DeviceInfoResult' variant."),
+        }
+    }
+
+    /// Returns whether or not the device is a GPU.
+    pub fn is_gpu(&self) -> OclResult<bool> {
+        self.device_type().map(|t| t.contains(DeviceType::GPU))
+    }
+
+    /// Returns whether or not the device is a CPU.
+    pub fn is_cpu(&self) -> OclResult<bool> {
+        self.device_type().map(|t| t.contains(DeviceType::CPU))
+    }
+
+    /// Returns whether or not the device is a dedicated accelerator.
+    pub fn is_accelerator(&self) -> OclResult<bool> {
+        self.device_type().map(|t| t.contains(DeviceType::ACCELERATOR))
+    }
+
     /// Returns raw info about the device, as a vector of bytes. Intended for use with non-standard
     /// OpenCL extensions.
     pub fn info_raw(&self, info_kind: u32) -> OclResult<Vec<u8>> {
@@ -436,6 +636,34 @@ impl Device {
         core::get_device_info(&self.0, info_kind).map_err(OclError::from)
     }
 
+    /// Returns a summary of the commonly needed capabilities of this device.
+    ///
+    /// The summary's `Display` implementation formats it as a block of text
+    /// similar to the output of `clinfo`, suitable for bug reports.
+    pub fn summary(&self) -> OclResult<DeviceSummary> {
+        macro_rules! info {
+            ($variant:ident) => (
+                match self.info(DeviceInfo::$variant)? {
+                    DeviceInfoResult::$variant(r) => r,
+                    _ => unreachable!(),
+                }
+            )
+        }
+
+        Ok(DeviceSummary {
+            name: self.name()?,
+            vendor: self.vendor()?,
+            version: info!(Version),
+            driver_version: info!(DriverVersion),
+            max_compute_units: info!(MaxComputeUnits),
+            max_clock_frequency: info!(MaxClockFrequency),
+            global_mem_size: info!(GlobalMemSize),
+            local_mem_size: info!(LocalMemSize),
+            max_wg_size: info!(MaxWorkGroupSize),
+            extensions: info!(Extensions).split_whitespace().map(String::from).collect(),
+        })
+    }
+
     /// Returns a string containing a formatted list of device properties.
     pub fn to_string(&self) -> String {
         self.clone().into()
@@ -583,3 +811,39 @@ impl DerefMut for Device {
         &mut self.0
     }
 }
+
+
+/// A summary of the commonly needed capabilities of a device.
+///
+/// Returned by `Device::summary`.
+#[derive(Clone, Debug)]
+pub struct DeviceSummary {
+    pub name: String,
+    pub vendor: String,
+    pub version: OpenclVersion,
+    pub driver_version: String,
+    pub max_compute_units: u32,
+    /// Maximum clock frequency in MHz.
+    pub max_clock_frequency: u32,
+    /// Global memory size in bytes.
+    pub global_mem_size: u64,
+    /// Local memory size in bytes.
+    pub local_mem_size: u64,
+    pub max_wg_size: usize,
+    pub extensions: Vec<String>,
+}
+
+impl std::fmt::Display for DeviceSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        writeln!(f, "Device Name:           {}", self.name)?;
+        writeln!(f, "Vendor:                {}", self.vendor)?;
+        writeln!(f, "OpenCL Version:        {}", self.version)?;
+        writeln!(f, "Driver Version:        {}", self.driver_version)?;
+        writeln!(f, "Max Compute Units:     {}", self.max_compute_units)?;
+        writeln!(f, "Max Clock Frequency:   {} MHz", self.max_clock_frequency)?;
+        writeln!(f, "Global Memory Size:    {} MiB", self.global_mem_size / (1 << 20))?;
+        writeln!(f, "Local Memory Size:     {} KiB", self.local_mem_size / (1 << 10))?;
+        writeln!(f, "Max Work Group Size:   {}", self.max_wg_size)?;
+        write!(f, "Extensions:            {}", self.extensions.join(" "))
+    }
+}
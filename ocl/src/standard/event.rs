@@ -16,17 +16,19 @@
 extern crate nodrop;
 
 
-use std::{mem, ptr, fmt};
+use std::{mem, ptr, fmt, cmp};
 use std::borrow::Borrow;
+use std::iter::FromIterator;
 use std::ops::{Deref, DerefMut};
 use std::cell::Ref;
+use num_traits::FromPrimitive;
 use futures::{Future, Poll, Async};
 #[cfg(not(feature = "async_block"))]
 use futures::task;
-use ffi::cl_event;
+use ffi::{cl_event, c_void};
 use core::{self, Event as EventCore, EventInfo, EventInfoResult, ProfilingInfo,
     ProfilingInfoResult, ClNullEventPtr, ClWaitListPtr, ClEventPtrRef,
-    CommandQueue as CommandQueueCore, ClContextPtr};
+    CommandQueue as CommandQueueCore, ClContextPtr, CommandExecutionStatus, CommandType};
 use error::{Error as OclError, Result as OclResult};
 use standard::{Queue, ClWaitListPtrEnum};
 #[cfg(not(feature = "async_block"))]
@@ -37,6 +39,12 @@ const PRINT_DEBUG: bool = false;
 
 /// An event representing a command or user created event.
 ///
+/// ## Destruction
+///
+/// Handled automatically. Clones retain the underlying event and may be
+/// stored and waited on after the original has been dropped. Empty events
+/// (see `::empty`) may not be cloned.
+///
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 #[repr(C)]
 #[must_use = "futures do nothing unless polled"]
@@ -63,6 +71,25 @@ impl Event {
         self.0.is_null()
     }
 
+    /// Blocks the host thread until the command associated with this event
+    /// is complete.
+    ///
+    /// Returns an error if this event is empty or if the command terminated
+    /// abnormally.
+    pub fn wait(&self) -> OclResult<()> {
+        if self.is_empty() { return Err("Event::wait: Event is empty.".into()); }
+        core::wait_for_event(&self.0).map_err(OclError::from)
+    }
+
+    /// Returns true if the command associated with this event has completed.
+    ///
+    /// Returns an error if this event is empty or if its status could not be
+    /// queried.
+    pub fn is_complete(&self) -> OclResult<bool> {
+        if self.is_empty() { return Err("Event::is_complete: Event is empty.".into()); }
+        core::event_is_complete(&self.0).map_err(OclError::from)
+    }
+
     /// Sets a callback function to trigger upon completion of this event
     /// which will unpark the current task.
     ///
@@ -105,16 +132,101 @@ impl Event {
         self.set_callback(core::_complete_user_event, unmap_event_ptr).map_err(OclError::from)
     }
 
+    /// Registers a closure to be called once upon completion of this event.
+    ///
+    /// The closure is passed the final execution status of the event's
+    /// command (`CommandExecutionStatus::Complete`). If the command terminates
+    /// abnormally, the closure is dropped without being called.
+    ///
+    /// The closure may be called immediately (from within this function) if
+    /// this event has already completed. It is otherwise called from a thread
+    /// belonging to the OpenCL implementation and must not block.
+    ///
+    pub fn on_complete<F>(&self, callback: F) -> OclResult<()>
+            where F: FnOnce(CommandExecutionStatus) + Send + 'static
+    {
+        self.set_callback_status(CommandExecutionStatus::Complete, callback)
+    }
+
+    /// Registers a closure to be called once when the execution status of
+    /// this event's command reaches `trigger`.
+    ///
+    /// `trigger` must be one of `CommandExecutionStatus::Submitted`,
+    /// `Running`, or `Complete`. This can be used, for example, to log when
+    /// a command begins running on the device. The closure is passed the
+    /// status which triggered it. If the command terminates abnormally, the
+    /// closure is dropped without being called.
+    ///
+    /// Multiple closures may be registered on the same event. Closures
+    /// registered for different statuses are called in the order those
+    /// statuses are reached. A closure may be called immediately (from within
+    /// this function) if its status has already been reached. It is otherwise
+    /// called from a thread belonging to the OpenCL implementation and must
+    /// not block.
+    ///
+    pub fn set_callback_status<F>(&self, trigger: CommandExecutionStatus, callback: F)
+            -> OclResult<()>
+            where F: FnOnce(CommandExecutionStatus) + Send + 'static
+    {
+        match trigger {
+            CommandExecutionStatus::Submitted | CommandExecutionStatus::Running |
+                CommandExecutionStatus::Complete => (),
+            _ => return Err(format!("Event::set_callback_status: Invalid callback trigger \
+                status: '{:?}'. Callbacks may only be triggered by the 'Submitted', 'Running', \
+                or 'Complete' statuses.", trigger).into()),
+        }
+
+        let callback: CompletionCallback = Box::new(callback);
+        let user_data = Box::into_raw(Box::new(callback)) as *mut c_void;
+
+        match unsafe { self.0.set_callback_status(trigger, _call_completion_callback, user_data) } {
+            Ok(()) => Ok(()),
+            Err(err) => {
+                // The callback was never registered and will not be called.
+                unsafe { drop(Box::from_raw(user_data as *mut CompletionCallback)); }
+                Err(OclError::from(err))
+            },
+        }
+    }
+
     /// Returns info about the event.
     pub fn info(&self, info_kind: EventInfo) -> OclResult<EventInfoResult> {
         core::get_event_info(&self.0, info_kind).map_err(OclError::from)
     }
 
+    /// Returns the current reference count of the underlying event object.
+    ///
+    /// Intended for debugging leaks; the value may be stale as soon as it is
+    /// returned.
+    pub fn reference_count(&self) -> OclResult<u32> {
+        match self.info(EventInfo::ReferenceCount)? {
+            EventInfoResult::ReferenceCount(count) => Ok(count),
+            _ => unreachable!(),
+        }
+    }
+
     /// Returns info about the event.
     pub fn profiling_info(&self, info_kind: ProfilingInfo) -> OclResult<ProfilingInfoResult> {
         core::get_event_profiling_info(&self.0, info_kind).map_err(OclError::from)
     }
 
+    /// Returns the device timestamps, in nanoseconds, at which the command
+    /// associated with this event started and ended execution.
+    ///
+    /// The command must have completed and its queue must have been created
+    /// with `QUEUE_PROFILING_ENABLE`.
+    pub fn profiling_start_end(&self) -> OclResult<(u64, u64)> {
+        let start = match self.profiling_info(ProfilingInfo::Start)? {
+            ProfilingInfoResult::Start(t) => t,
+            _ => unreachable!(),
+        };
+        let end = match self.profiling_info(ProfilingInfo::End)? {
+            ProfilingInfoResult::End(t) => t,
+            _ => unreachable!(),
+        };
+        Ok((start, end))
+    }
+
     /// Returns this event's associated command queue.
     pub fn queue_core(&self) -> OclResult<CommandQueueCore> {
         match self.info(EventInfo::CommandQueue)? {
@@ -123,6 +235,23 @@ impl Event {
         }
     }
 
+    /// Returns the execution status of the command associated with this
+    /// event.
+    pub fn status(&self) -> OclResult<CommandExecutionStatus> {
+        match self.info(EventInfo::CommandExecutionStatus)? {
+            EventInfoResult::CommandExecutionStatus(status) => Ok(status),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the type of the command associated with this event.
+    pub fn command_type(&self) -> OclResult<CommandType> {
+        match self.info(EventInfo::CommandType)? {
+            EventInfoResult::CommandType(cmd_type) => Ok(cmd_type),
+            _ => unreachable!(),
+        }
+    }
+
     /// Returns a reference to the core pointer wrapper, usable by functions in
     /// the `core` module.
     ///
@@ -170,6 +299,25 @@ impl Event {
     }
 }
 
+/// A boxed closure registered with `Event::on_complete` or
+/// `Event::set_callback_status`.
+type CompletionCallback = Box<dyn FnOnce(CommandExecutionStatus) + Send + 'static>;
+
+/// Trampoline for closures registered with `Event::set_callback_status`. Takes
+/// ownership of (and frees) the boxed closure pointed to by `user_data`.
+extern "C" fn _call_completion_callback(event_ptr: cl_event, event_status: i32,
+        user_data: *mut c_void)
+{
+    let _ = event_ptr;
+    if user_data.is_null() { return; }
+
+    let callback = unsafe { Box::from_raw(user_data as *mut CompletionCallback) };
+
+    if let Some(status) = CommandExecutionStatus::from_i32(event_status) {
+        callback(status);
+    }
+}
+
 impl From<EventCore> for Event {
     #[inline]
     fn from(ev: EventCore) -> Event {
@@ -285,6 +433,85 @@ impl Future for Event {
 }
 
 
+/// A user event, the status of which is set from the host.
+///
+/// Commands waiting on a user event (see the `::ewait` method of any
+/// command builder) will not begin executing until it is set complete with
+/// `::set_complete`. Setting an error with `::set_error` instead causes
+/// those commands to terminate.
+///
+/// Derefs to `Event` and can be used anywhere an `Event` can.
+///
+/// ## Destruction
+///
+/// Handled automatically. Clones retain the underlying event and can be
+/// sent to other threads, allowing a host thread to gate a chain of commands
+/// on an external condition.
+///
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct UserEvent(Event);
+
+impl UserEvent {
+    /// Creates and returns a new user event associated with `context`.
+    pub fn new<C: ClContextPtr>(context: C) -> OclResult<UserEvent> {
+        Event::user(context).map(UserEvent)
+    }
+
+    /// Sets the status of this event to complete, releasing any commands
+    /// waiting on it.
+    ///
+    /// Returns an error if the status has already been set.
+    pub fn set_complete(&self) -> OclResult<()> {
+        self.0.set_complete().map_err(OclError::from)
+    }
+
+    /// Sets the status of this event to `error_code`, causing any commands
+    /// waiting on it to terminate.
+    ///
+    /// `error_code` must be negative. Returns an error if it is not or if
+    /// the status has already been set.
+    pub fn set_error(&self, error_code: i32) -> OclResult<()> {
+        core::set_user_event_error(&self.0, error_code).map_err(OclError::from)
+    }
+
+    /// Returns a reference to the underlying event.
+    #[inline]
+    pub fn as_event(&self) -> &Event {
+        &self.0
+    }
+}
+
+impl From<UserEvent> for Event {
+    fn from(uev: UserEvent) -> Event {
+        uev.0
+    }
+}
+
+impl Deref for UserEvent {
+    type Target = Event;
+
+    fn deref(&self) -> &Event {
+        &self.0
+    }
+}
+
+impl AsRef<EventCore> for UserEvent {
+    fn as_ref(&self) -> &EventCore {
+        &self.0
+    }
+}
+
+unsafe impl ClWaitListPtr for UserEvent {
+    #[inline] unsafe fn as_ptr_ptr(&self) -> *const cl_event { self.0.as_ptr_ptr() }
+    #[inline] fn count(&self) -> u32 { self.0._count() }
+}
+
+unsafe impl<'a> ClWaitListPtr for &'a UserEvent {
+    #[inline] unsafe fn as_ptr_ptr(&self) -> *const cl_event { self.0.as_ptr_ptr() }
+    #[inline] fn count(&self) -> u32 { self.0._count() }
+}
+
+
 
 /// Returns an empty, initialized (zeroed) event array.
 fn empty_event_array() -> NoDrop<[Event; 8]> {
@@ -628,6 +855,26 @@ unsafe impl<'a> ClWaitListPtr for &'a EventArray {
 
 
 
+/// Aggregate command execution times of a list of events.
+///
+/// All times are in nanoseconds. Created by `EventList::profiling_summary`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProfilingSummary {
+    /// The number of events.
+    pub count: usize,
+    /// The sum of all command durations.
+    pub total: u64,
+    /// The shortest command duration.
+    pub min: u64,
+    /// The longest command duration.
+    pub max: u64,
+    /// The mean command duration.
+    pub mean: u64,
+    /// The time from the earliest command start to the latest command end.
+    pub span: u64,
+}
+
+
 /// The guts of an EventList.
 #[derive(Debug, Clone)]
 enum Inner {
@@ -682,6 +929,11 @@ impl EventList {
         }
     }
 
+    /// Returns a new `EventList` containing clones of each event in `events`.
+    pub fn from_slice(events: &[Event]) -> EventList {
+        events.iter().cloned().collect()
+    }
+
     /// Converts the contained list from a stack allocated to a heap allocated
     /// array [or vice-versa].
     ///
@@ -779,6 +1031,16 @@ impl EventList {
         }
     }
 
+    /// Blocks the host thread until all events in this list are complete,
+    /// waiting on the entire list with a single call to `clWaitForEvents`.
+    ///
+    /// Returns an error if any of the commands associated with the events
+    /// terminated abnormally. Returns immediately if the list is empty.
+    pub fn wait(&self) -> OclResult<()> {
+        if self.as_slice().is_empty() { return Ok(()); }
+        core::wait_for_events(self.as_slice().len() as u32, self).map_err(OclError::from)
+    }
+
     /// Enqueue a marker event representing the completion of each and every
     /// event in this list.
     ///
@@ -801,6 +1063,39 @@ impl EventList {
         }
     }
 
+    /// Returns the total, minimum, maximum, and mean execution durations of
+    /// the commands associated with each event in this list along with the
+    /// overall span from the earliest start to the latest end.
+    ///
+    /// All commands must have completed (call `::wait_for` first) and have
+    /// been enqueued on queues created with `QUEUE_PROFILING_ENABLE`.
+    /// Returns an error if the list is empty.
+    pub fn profiling_summary(&self) -> OclResult<ProfilingSummary> {
+        let events = self.as_slice();
+        if events.is_empty() {
+            return Err("EventList::profiling_summary: List empty.".into());
+        }
+
+        let mut summary = ProfilingSummary { count: events.len(), total: 0, min: u64::max_value(),
+            max: 0, mean: 0, span: 0 };
+        let mut earliest_start = u64::max_value();
+        let mut latest_end = 0;
+
+        for event in events {
+            let (start, end) = event.profiling_start_end()?;
+            let duration = end.saturating_sub(start);
+            summary.total += duration;
+            summary.min = cmp::min(summary.min, duration);
+            summary.max = cmp::max(summary.max, duration);
+            earliest_start = cmp::min(earliest_start, start);
+            latest_end = cmp::max(latest_end, end);
+        }
+
+        summary.mean = summary.total / summary.count as u64;
+        summary.span = latest_end.saturating_sub(earliest_start);
+        Ok(summary)
+    }
+
     /// Returns a slice of the contained events.
     #[inline]
     pub fn as_slice(&self) -> &[Event] {
@@ -1196,6 +1491,22 @@ impl IntoIterator for EventList {
     }
 }
 
+impl<E> FromIterator<E> for EventList where E: Into<Event> {
+    fn from_iter<I: IntoIterator<Item = E>>(iter: I) -> EventList {
+        let mut list = EventList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<E> Extend<E> for EventList where E: Into<Event> {
+    fn extend<I: IntoIterator<Item = E>>(&mut self, iter: I) {
+        for event in iter {
+            self.push(event);
+        }
+    }
+}
+
 impl Future for EventList {
     type Item = ();
     type Error = OclError;
@@ -1,8 +1,8 @@
 //! An OpenCL Image.
 //!
 //
-// * NOTE: `Image` does not have the latest command builders nor does it have
-//   support for mapping yet. (TODO: implement)
+// * NOTE: `Image` does not have the latest command builders yet. (TODO:
+//   implement)
 //
 
 use std;
@@ -13,10 +13,9 @@ use error::{Error as OclError, Result as OclResult};
 use core::{self, OclPrm, Mem as MemCore, MemFlags, MemObjectType, ImageFormatParseResult,
     ImageFormat, ImageDescriptor, ImageInfo, ImageInfoResult, MemInfo, MemInfoResult,
     ImageChannelOrder, ImageChannelDataType, AsMem, MemCmdRw, MemCmdAll,
-    MapFlags};
+    MapFlags, MemMap as MemMapCore};
 use standard::{Context, Queue, SpatialDims, ClNullEventPtrEnum, ClWaitListPtrEnum,
-    QueCtx, HostSlice};
-use ::MemMap;
+    QueCtx, HostSlice, Event, Buffer};
 
 #[cfg(not(feature="opencl_vendor_mesa"))]
 use ffi::{cl_GLuint, cl_GLint};
@@ -84,6 +83,7 @@ pub struct ImageCmd<'c, T: 'c> {
     ewait: Option<ClWaitListPtrEnum<'c>>,
     enew: Option<ClNullEventPtrEnum<'c>>,
     mem_dims: [usize; 3],
+    pixel_element_len: usize,
 }
 
 /// [UNSTABLE]: All methods still in a state of adjustifulsomeness.
@@ -91,8 +91,8 @@ impl<'c, T: 'c + OclPrm> ImageCmd<'c, T> {
     /// Returns a new image command builder associated with with the
     /// memory object `obj_core` along with a default `queue` and `to_len`
     /// (the length of the device side image).
-    fn new(queue: Option<&'c Queue>, obj_core: &'c MemCore, dims: [usize; 3])
-            -> ImageCmd<'c, T> {
+    fn new(queue: Option<&'c Queue>, obj_core: &'c MemCore, dims: [usize; 3],
+            pixel_element_len: usize) -> ImageCmd<'c, T> {
         ImageCmd {
             queue: queue,
             obj_core: obj_core,
@@ -105,6 +105,7 @@ impl<'c, T: 'c + OclPrm> ImageCmd<'c, T> {
             ewait: None,
             enew: None,
             mem_dims: dims,
+            pixel_element_len: pixel_element_len,
         }
     }
 
@@ -152,8 +153,8 @@ impl<'c, T: 'c + OclPrm> ImageCmd<'c, T> {
 
     /// Specifies that this command will be a map operation.
     ///
-    /// If `.block(..)` has been set it will be ignored. Non-blocking map
-    /// commands are enqueued using `::enq_async`.
+    /// If `.block(..)` has been set it will be ignored. Map commands always
+    /// block until the region has been mapped.
     ///
     /// ## Safety
     ///
@@ -173,9 +174,8 @@ impl<'c, T: 'c + OclPrm> ImageCmd<'c, T> {
         assert!(self.kind.is_unspec(), "ocl::BufferCmd::write(): Operation kind \
             already set for this command.");
         self.kind = ImageCmdKind::Map;
-
-        unimplemented!();
-        // ImageMapCmd { cmd: self }
+        self.block = true;
+        ImageMapCmd { cmd: self, flags: None }
     }
 
     /// Specifies that this command will be a copy operation.
@@ -441,6 +441,10 @@ impl<'c, T: 'c + OclPrm> ImageCmd<'c, T> {
                 core::enqueue_copy_image(queue, self.obj_core, dst_image, self.origin,
                     dst_origin, self.region, self.ewait, self.enew)
             },
+            ImageCmdKind::CopyToBuffer { buffer, dst_origin } => {
+                core::enqueue_copy_image_to_buffer::<T, _, _, _>(queue, self.obj_core, buffer,
+                    self.origin, self.region, dst_origin, self.ewait, self.enew)
+            },
 
             #[cfg(not(feature="opencl_vendor_mesa"))]
             ImageCmdKind::GLAcquire => {
@@ -465,17 +469,12 @@ impl<'c, T: 'c + OclPrm> ImageCmd<'c, T> {
 
 
 
-/// A buffer command builder used to enqueue maps.
+/// An image command builder used to enqueue maps.
 ///
-/// See [SDK][map_buffer] docs for more details.
+/// See [SDK][map_image] docs for more details.
 ///
-/// [map_buffer]: https://www.khronos.org/registry/OpenCL/sdk/1.2/docs/man/xhtml/clEnqueueMapBuffer.html
-
-    // const size_t  * origin ,
-    // const size_t  * region ,
-    // size_t  *image_row_pitch ,
-    // size_t  *image_slice_pitch ,
-
+/// [map_image]: https://www.khronos.org/registry/OpenCL/sdk/1.2/docs/man/xhtml/clEnqueueMapImage.html
+#[must_use = "commands do nothing unless enqueued"]
 pub struct ImageMapCmd<'c, T> where T: 'c {
     cmd: ImageCmd<'c, T>,
     flags: Option<MapFlags>,
@@ -492,6 +491,15 @@ impl<'c, T> ImageMapCmd<'c, T> where T: OclPrm {
         self
     }
 
+    /// Specifies a queue to use for this call only.
+    ///
+    /// Overrides the image's default queue if one is set. The same queue is
+    /// used to unmap the region.
+    pub fn queue(mut self, queue: &'c Queue) -> ImageMapCmd<'c, T> {
+        self.cmd.queue = Some(queue);
+        self
+    }
+
     /// Sets the three dimensional offset, the origin point, for an operation.
     ///
     /// Defaults to [0, 0, 0] if not set.
@@ -592,11 +600,9 @@ impl<'c, T> ImageMapCmd<'c, T> where T: OclPrm {
         self
     }
 
-    /// Enqueues this command.
-    ///
-    /// * TODO: FOR COPY, FILL, AND COPYTOBUFFER -- ENSURE PITCHES ARE BOTH UNSET.
-    #[allow(unused_variables, unreachable_code)]
-    pub fn enq(self) -> OclResult<MemMap<T>> {
+    /// Enqueues this command, blocking until the region has been mapped, and
+    /// returns a guard providing access to the mapped rows.
+    pub fn enq(self) -> OclResult<ImageMap<T>> {
         let queue = match self.cmd.queue {
             Some(q) => q,
             None => return Err("ImageCmd::enq: No queue set.".into()),
@@ -606,16 +612,14 @@ impl<'c, T> ImageMapCmd<'c, T> where T: OclPrm {
 
         match self.cmd.kind {
             ImageCmdKind::Map => {
-                // try!(check_len(self.cmd.to_len, data.len(), offset));
-
                 let mut row_pitch_bytes = 0usize;
                 let mut slc_pitch_bytes = 0usize;
 
-                unsafe {
-                    let mm_core = core::enqueue_map_image::<T, _, _, _>(
+                let mm_core = unsafe {
+                    core::enqueue_map_image::<T, _, _, _>(
                         queue,
                         self.cmd.obj_core,
-                        self.cmd.block,
+                        true,
                         flags,
                         self.cmd.origin,
                         self.cmd.region,
@@ -623,28 +627,121 @@ impl<'c, T> ImageMapCmd<'c, T> where T: OclPrm {
                         &mut slc_pitch_bytes,
                         self.cmd.ewait,
                         self.cmd.enew,
-                    )?;
+                    )?
+                };
+
+                Ok(ImageMap {
+                    core: mm_core,
+                    image: self.cmd.obj_core.clone(),
+                    queue: queue.clone(),
+                    region: self.cmd.region,
+                    row_len: self.cmd.region[0] * self.cmd.pixel_element_len,
+                    row_pitch_bytes: row_pitch_bytes,
+                    slc_pitch_bytes: slc_pitch_bytes,
+                    is_unmapped: false,
+                })
+            },
+            _ => unreachable!(),
+        }
+    }
+}
 
-                    let len_bytes = if slc_pitch_bytes == 0 {
-                        // 1D or 2D image.
-                        unimplemented!();
-                    } else {
-                        // 1D image array, 2D image array, or 3D image.
-                        unimplemented!();
-                    };
 
-                    // let unmap_event = None;
+/// A region of an image mapped into host memory by `clEnqueueMapImage`.
+///
+/// Image rows (and slices) are frequently padded by the platform and cannot
+/// be accessed as one contiguous slice. Use `::row` and `::row_mut` to access
+/// each row, or `::row_pitch_bytes` and `::slc_pitch_bytes` along with the
+/// raw pointer for manual indexing.
+///
+/// The region is unmapped when dropped if `::unmap` has not already been
+/// called.
+#[derive(Debug)]
+pub struct ImageMap<T: OclPrm> {
+    core: MemMapCore<T>,
+    image: MemCore,
+    queue: Queue,
+    region: [usize; 3],
+    row_len: usize,
+    row_pitch_bytes: usize,
+    slc_pitch_bytes: usize,
+    is_unmapped: bool,
+}
 
-                    // * TODO: Create a special container for mapped images
-                    // that can take into account row and slice pitch. It
-                    // cannot deref into a &[T] as the size of rows (and
-                    // slices) can vary with byte-sized precision.
+impl<T: OclPrm> ImageMap<T> {
+    /// Returns the mapped row `y` of slice `z` (`0` for 1D and 2D images).
+    ///
+    /// Each row contains `region[0] * pixel_element_len` elements.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if already unmapped or if `y` or `z` are out of range.
+    pub fn row(&self, y: usize, z: usize) -> &[T] {
+        let offset = self.row_offset_bytes(y, z);
+        unsafe {
+            std::slice::from_raw_parts((self.core.as_ptr() as *const u8).offset(offset as isize)
+                as *const T, self.row_len)
+        }
+    }
 
-                    // Ok(MemMap::new(mm_core, 0, unmap_event, self.cmd.obj_core.clone(),
-                    //     queue.core().clone()))
-                }
-            },
-            _ => unreachable!(),
+    /// Returns the mapped row `y` of slice `z` (`0` for 1D and 2D images).
+    ///
+    /// ## Panics
+    ///
+    /// Panics if already unmapped or if `y` or `z` are out of range.
+    pub fn row_mut(&mut self, y: usize, z: usize) -> &mut [T] {
+        let offset = self.row_offset_bytes(y, z);
+        unsafe {
+            std::slice::from_raw_parts_mut((self.core.as_mut_ptr() as *mut u8)
+                .offset(offset as isize) as *mut T, self.row_len)
+        }
+    }
+
+    /// Enqueues an unmap command for the mapped region.
+    pub fn unmap(&mut self) -> OclResult<()> {
+        if self.is_unmapped {
+            return Err("ocl::ImageMap::unmap: Already unmapped.".into());
+        }
+        core::enqueue_unmap_mem_object(&self.queue, &self.image, &self.core,
+            None::<&Event>, None::<&mut Event>)?;
+        self.is_unmapped = true;
+        Ok(())
+    }
+
+    /// Returns the size of the mapped region in pixels.
+    #[inline] pub fn region(&self) -> [usize; 3] { self.region }
+
+    /// Returns the number of elements within each row.
+    #[inline] pub fn row_len(&self) -> usize { self.row_len }
+
+    /// Returns the number of bytes between the start of consecutive rows.
+    #[inline] pub fn row_pitch_bytes(&self) -> usize { self.row_pitch_bytes }
+
+    /// Returns the number of bytes between the start of consecutive slices
+    /// (`0` for 1D and 2D images).
+    #[inline] pub fn slc_pitch_bytes(&self) -> usize { self.slc_pitch_bytes }
+
+    /// Returns true if the region has been unmapped.
+    #[inline] pub fn is_unmapped(&self) -> bool { self.is_unmapped }
+
+    /// Returns a pointer to the start of the mapped region.
+    #[inline] pub fn as_ptr(&self) -> *const T { self.core.as_ptr() }
+
+    /// Returns a mutable pointer to the start of the mapped region.
+    #[inline] pub fn as_mut_ptr(&mut self) -> *mut T { self.core.as_mut_ptr() }
+
+    fn row_offset_bytes(&self, y: usize, z: usize) -> usize {
+        assert!(!self.is_unmapped, "Mapped memory has been unmapped and cannot be accessed.");
+        assert!(y < self.region[1] && z < self.region[2], "ocl::ImageMap: Row index out of \
+            range (y: {}, z: {}, region: {:?}).", y, z, self.region);
+        (z * self.slc_pitch_bytes) + (y * self.row_pitch_bytes)
+    }
+}
+
+impl<T: OclPrm> Drop for ImageMap<T> {
+    fn drop(&mut self) {
+        if !self.is_unmapped {
+            self.unmap().ok();
         }
     }
 }
@@ -655,6 +752,10 @@ impl<'c, T> ImageMapCmd<'c, T> where T: OclPrm {
 ///
 /// Use `::builder` for an easy way to create. [UNIMPLEMENTED]
 ///
+/// ## Destruction
+///
+/// Handled automatically. Clones retain the underlying memory object.
+///
 #[derive(Clone, Debug)]
 pub struct Image<T: OclPrm> {
     obj_core: MemCore,
@@ -761,6 +862,58 @@ impl<T: OclPrm> Image<T> {
         Ok(new_img)
     }
 
+    /// Returns a new `Image` from a subresource of an existing Direct3D 11
+    /// 2D texture (`ID3D11Texture2D*`).
+    ///
+    /// The context must have been created with an associated Direct3D 11
+    /// device (`ContextBuilder::d3d11_device`). Use
+    /// `Queue::enqueue_acquire_d3d11_objects` before using the image and
+    /// `Queue::enqueue_release_d3d11_objects` after.
+    ///
+    /// ## Safety
+    ///
+    /// `resource` must be a valid `ID3D11Texture2D*` created by the Direct3D
+    /// 11 device associated with the context.
+    ///
+    /// [Feature: `d3d11_interop`, Windows only]
+    #[cfg(all(feature = "d3d11_interop", windows))]
+    pub unsafe fn from_d3d11_texture2d<'o, Q>(que_ctx: Q, flags: MemFlags,
+            resource: *mut ::ffi::c_void, subresource: u32) -> OclResult<Image<T>>
+            where Q: Into<QueCtx<'o>>
+    {
+        let que_ctx = que_ctx.into();
+        let context = que_ctx.context_cloned();
+
+        let obj_core = core::create_from_d3d11_texture2d(&context, flags, resource,
+            subresource)?;
+
+        let pixel_element_len = match core::get_image_info(&obj_core, ImageInfo::ElementSize)? {
+            ImageInfoResult::ElementSize(s) => s / mem::size_of::<T>(),
+            _ => return Err("ocl::Image::element_len(): \
+                Unexpected 'ImageInfoResult' variant.".into()),
+        };
+
+        let width = match core::get_image_info(&obj_core, ImageInfo::Width)? {
+            ImageInfoResult::Width(w) => w,
+            _ => return Err("ocl::Image::from_d3d11_texture2d(): \
+                Unexpected 'ImageInfoResult' variant.".into()),
+        };
+
+        let height = match core::get_image_info(&obj_core, ImageInfo::Height)? {
+            ImageInfoResult::Height(h) => h,
+            _ => return Err("ocl::Image::from_d3d11_texture2d(): \
+                Unexpected 'ImageInfoResult' variant.".into()),
+        };
+
+        Ok(Image {
+            obj_core: obj_core,
+            queue: que_ctx.into(),
+            dims: [width, height].into(),
+            pixel_element_len: pixel_element_len,
+            _pixel: PhantomData,
+        })
+    }
+
     /// Returns a new `Image` from an existant renderbuffer.
     // [WORK IN PROGRESS]
     #[cfg(not(feature="opencl_vendor_mesa"))]
@@ -805,7 +958,7 @@ impl<T: OclPrm> Image<T> {
     /// for more details.
     pub fn cmd(&self) -> ImageCmd<T> {
         ImageCmd::new(self.queue.as_ref(), &self.obj_core,
-            self.dims.to_lens().expect("ocl::Image::cmd"))
+            self.dims.to_lens().expect("ocl::Image::cmd"), self.pixel_element_len)
     }
 
     /// Returns an image command builder set to read.
@@ -844,8 +997,7 @@ impl<T: OclPrm> Image<T> {
     ///
     #[inline]
     pub unsafe fn map<'c>(&'c self) -> ImageMapCmd<'c, T> {
-        unimplemented!();
-        // self.cmd().map()
+        self.cmd().map()
     }
 
     // /// Specifies that this command will be a copy operation.
@@ -915,6 +1067,24 @@ impl<T: OclPrm> Image<T> {
         core::get_image_info(&self.obj_core, info_kind).map_err(OclError::from)
     }
 
+    /// Returns the row pitch (in bytes) of this image as reported by the
+    /// platform.
+    pub fn row_pitch_bytes(&self) -> OclResult<usize> {
+        match self.info(ImageInfo::RowPitch)? {
+            ImageInfoResult::RowPitch(pitch) => Ok(pitch),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the slice pitch (in bytes) of this image as reported by the
+    /// platform. Zero for 1D and 2D images.
+    pub fn slc_pitch_bytes(&self) -> OclResult<usize> {
+        match self.info(ImageInfo::SlicePitch)? {
+            ImageInfoResult::SlicePitch(pitch) => Ok(pitch),
+            _ => unreachable!(),
+        }
+    }
+
     /// Returns info about this image's memory.
     pub fn mem_info(&self, info_kind: MemInfo) -> OclResult<MemInfoResult> {
         // match core::get_mem_object_info(&self.obj_core, info_kind) {
@@ -1265,7 +1435,9 @@ impl<'a, T> ImageBuilder<'a, T> where T: 'a + OclPrm {
     /// Buffer synchronization.
     ///
     /// Refers to a valid buffer memory object if image_type is
-    /// `MemObjectType::Image1dBuffer`. Otherwise it must be `None` (default).
+    /// `MemObjectType::Image1dBuffer`. With OpenCL 2.0+, may also refer to a
+    /// buffer for a `MemObjectType::Image2d` or to another image from which
+    /// to create a new image. Otherwise it must be `None` (default).
     /// For a 1D image buffer object, the image pixels are taken from the buffer
     /// object's data store. When the contents of a buffer object's data store are
     /// modified, those changes are reflected in the contents of the 1D image
@@ -1278,6 +1450,31 @@ impl<'a, T> ImageBuilder<'a, T> where T: 'a + OclPrm {
         self
     }
 
+    /// Specifies a buffer from which to create a 1D image buffer
+    /// (`MemObjectType::Image1dBuffer`), allowing its contents to be read
+    /// from a kernel as an image (e.g. using a sampler).
+    ///
+    /// The image shares the data store of `buffer` (see `::buffer_sync`).
+    /// If no width has been set with `::dims`, the width will be the
+    /// number of whole pixels (according to the image format) which fit
+    /// within the buffer. Otherwise, the width multiplied by the size of a
+    /// pixel must not exceed the size of the buffer.
+    ///
+    /// Sets the image type to `MemObjectType::Image1dBuffer`.
+    ///
+    /// ```rust,ignore
+    /// let image = Image::<f32>::builder()
+    ///     .image_format(ImageFormat::r_f32())
+    ///     .buffer(&buffer)
+    ///     .queue(queue.clone())
+    ///     .build()?;
+    /// ```
+    pub fn buffer<B: OclPrm>(mut self, buffer: &Buffer<B>) -> ImageBuilder<'a, T> {
+        self.image_desc.image_type = MemObjectType::Image1dBuffer;
+        self.image_desc.buffer = Some(buffer.as_core().clone());
+        self
+    }
+
     /// Specifies the image pixel format.
     ///
     /// If unspecified, defaults to:
@@ -1334,6 +1531,8 @@ impl<'a, T> ImageBuilder<'a, T> where T: 'a + OclPrm {
     /// Builds with no host side image data memory specified and returns a
     /// new `Image`.
     pub fn build(mut self) -> OclResult<Image<T>> {
+        self.resolve_buffer_width()?;
+
         let host_slice = match self.host_slice {
             HostSlice::Use(hs) => {
                 self.flags.insert(MemFlags::new().use_host_ptr());
@@ -1356,4 +1555,45 @@ impl<'a, T> ImageBuilder<'a, T> where T: 'a + OclPrm {
         }
 
     }
+
+    /// Validates the buffer and width of a 1D image buffer, determining the
+    /// width from the size of the buffer if unset.
+    ///
+    /// Other image types are passed through unchanged, including those
+    /// created from a buffer or from another image (OpenCL 2.0+), which are
+    /// validated by the implementation.
+    fn resolve_buffer_width(&mut self) -> OclResult<()> {
+        if self.image_desc.image_type != MemObjectType::Image1dBuffer {
+            return Ok(());
+        }
+
+        let buffer_bytes = match self.image_desc.buffer {
+            Some(ref buffer) => {
+                match core::get_mem_object_info(buffer, MemInfo::Size)? {
+                    MemInfoResult::Size(s) => s,
+                    _ => unreachable!(),
+                }
+            },
+            None => return Err("ImageBuilder::build: A buffer must be \
+                specified (using '::buffer') for images of type \
+                'MemObjectType::Image1dBuffer'.".into()),
+        };
+
+        // Height and depth are unused by 1D images but are used to determine
+        // the dimensions (and element count) of the resulting `Image`:
+        self.image_desc.image_height = 1;
+        self.image_desc.image_depth = 1;
+
+        let pixel_bytes = self.image_format.bytes_per_pixel();
+
+        if self.image_desc.image_width == 0 {
+            self.image_desc.image_width = buffer_bytes / pixel_bytes;
+        } else if self.image_desc.image_width * pixel_bytes > buffer_bytes {
+            return Err(format!("ImageBuilder::build: The image width ({} pixels of {} bytes \
+                each) exceeds the size of the buffer ({} bytes).", self.image_desc.image_width,
+                pixel_bytes, buffer_bytes).into());
+        }
+
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -12,11 +12,15 @@ use std::borrow::Borrow;
 use core::ffi::c_void;
 use core::{self, util, OclPrm, Kernel as KernelCore, CommandQueue as CommandQueueCore, Mem as MemCore,
     ArgVal, KernelInfo, KernelInfoResult, KernelArgInfo, KernelArgInfoResult,
-    KernelWorkGroupInfo, KernelWorkGroupInfoResult, AsMem, MemCmdAll, ClVersions};
+    KernelWorkGroupInfo, KernelWorkGroupInfoResult, AsMem, MemCmdAll, ClVersions,
+    CommandQueueInfo, CommandQueueInfoResult, DeviceInfo, DeviceInfoResult, ProgramInfo,
+    ProgramInfoResult};
 use core::error::{ErrorKind as OclCoreErrorKind};
 use error::{Error as OclError, Result as OclResult, ErrorKind as OclErrorKind};
 use standard::{SpatialDims, Program, Queue, WorkDims, Sampler, Device, ClNullEventPtrEnum,
-    ClWaitListPtrEnum, Buffer, Image};
+    ClWaitListPtrEnum, Buffer, Image, Event, EventList};
+#[cfg(feature = "opencl_version_2_0")]
+use standard::{SvmVec, Pipe};
 pub use self::arg_type::{BaseType, Cardinality, ArgType};
 
 
@@ -28,6 +32,8 @@ pub enum KernelError {
     #[fail(display = "Global Work Size cannot be left unspecified. Set a default for \
         the kernel or specify one when enqueuing command.")]
     CmdNoGws,
+    #[fail(display = "Global work size ({:?}) cannot be zero in any dimension.", _0)]
+    CmdGwsZero([usize; 3]),
     #[fail(display = "Unable to resolve argument named: '{}'. Ensure that an argument with \
         that name has been declared before building kernel.", _0)]
     NamedArgsInvalidArgName(String),
@@ -47,6 +53,17 @@ pub enum KernelError {
         (required: {}, specified: {}). Use named arguments with 'None' or zero values to \
         declare arguments you plan to assign a value to at a later time.", required, specified)]
     BuilderWrongArgCount { required: u32, specified: u32 },
+    #[fail(display = "Local work size ({}) in dimension {} exceeds the maximum work item \
+        size of the device ({}). Use a smaller local work size or leave it unspecified.",
+        size, dim, max)]
+    CmdLwsExceedsMaxWorkItemSize { dim: usize, size: usize, max: usize },
+    #[fail(display = "Local work size ({:?}, {} work items in total) exceeds the maximum work \
+        group size of the kernel on the device ({}). Use a smaller local work size or leave \
+        it unspecified.", lws, size, max)]
+    CmdLwsExceedsMaxWgSize { lws: [usize; 3], size: usize, max: usize },
+    #[fail(display = "Local memory argument at index: [{}] ({} bytes) exceeds the local \
+        memory size of the device ({} bytes).", idx, size, max)]
+    ArgLocalExceedsLocalMemSize { idx: u32, size: u64, max: u64 },
 }
 
 
@@ -61,6 +78,10 @@ pub struct KernelCmd<'k> {
     lws: SpatialDims,
     wait_events: Option<ClWaitListPtrEnum<'k>>,
     new_event: Option<ClNullEventPtrEnum<'k>>,
+    single_task: bool,
+    unchecked: bool,
+    pad_gws: bool,
+    lws_auto: bool,
 }
 
 /// A kernel enqueue command.
@@ -115,6 +136,81 @@ impl<'k> KernelCmd<'k> {
         self
     }
 
+    /// Specifies a global work size for this call only, rounding each
+    /// dimension up to the next multiple of the local work size.
+    ///
+    /// OpenCL 1.x requires the global work size to be evenly divisible by
+    /// the local work size. If no local work size is specified, one is
+    /// chosen using the preferred work group size multiple of the kernel
+    /// (`KernelWorkGroupInfo::PreferredWorkGroupSizeMultiple`) for the first
+    /// dimension.
+    ///
+    /// The additional work items execute the kernel as well. Pass the
+    /// original size to the kernel as an argument and return early from any
+    /// work item beyond it.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore
+    /// // __kernel void add(__global float* buf, uint len, float val) {
+    /// //     if (get_global_id(0) >= len) return;
+    /// //     ...
+    /// kernel.set_arg("len", 1000u32)?;
+    /// kernel.cmd().gws_padded(1000).local_work_size(64).enq()?; // 1024 work items
+    /// ```
+    pub fn gws_padded<D: Into<SpatialDims>>(mut self, gws: D) -> KernelCmd<'k> {
+        self.gws = gws.into();
+        self.pad_gws = true;
+        self
+    }
+
+    /// Skips the verification of the local work size against the limits of
+    /// the device and kernel normally done when enqueuing.
+    ///
+    /// The verification requires several queries to the implementation and
+    /// may be skipped in hot loops once a configuration is known to be valid.
+    /// An oversized local work size will otherwise result in an
+    /// `CL_INVALID_WORK_GROUP_SIZE` or `CL_INVALID_WORK_ITEM_SIZE` error.
+    pub fn unchecked(mut self) -> KernelCmd<'k> {
+        self.unchecked = true;
+        self
+    }
+
+    /// Selects a local work size automatically, overriding any local work
+    /// size specified for this call or set as the kernel's default.
+    ///
+    /// The kernel's preferred work group size multiple
+    /// (`KernelWorkGroupInfo::PreferredWorkGroupSizeMultiple`) on the
+    /// queue's device is used if it evenly divides the first dimension of
+    /// the global work size. Otherwise, the largest size which does so and
+    /// does not exceed the maximum work group size of the kernel on that
+    /// device is used. Remaining dimensions use a local size of one.
+    ///
+    /// When combined with `::gws_padded`, the global work size is padded to
+    /// the preferred multiple, which is then always used.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore
+    /// kernel.cmd().gws_padded(1000).lws_auto().enq()?;
+    /// ```
+    pub fn lws_auto(mut self) -> KernelCmd<'k> {
+        self.lws_auto = true;
+        self
+    }
+
+    /// Specifies that this kernel is to be executed as a single work-item,
+    /// overriding any work sizes or offset.
+    ///
+    /// Useful for serial work such as the final step of a reduction. Uses
+    /// `clEnqueueTask` or, when the `opencl_version_2_0` feature is enabled
+    /// (`clEnqueueTask` is deprecated as of 2.0), an equivalent
+    /// `clEnqueueNDRangeKernel` with global and local work sizes of one.
+    pub fn single_task(mut self) -> KernelCmd<'k> {
+        self.single_task = true;
+        self
+    }
+
     /// Specifies an event or list of events to wait on before the command
     /// will run.
     ///
@@ -199,17 +295,277 @@ impl<'k> KernelCmd<'k> {
             None => return Err(KernelError::CmdNoQueue.into()),
         };
 
+        if self.single_task {
+            return self.enq_single_task(queue);
+        }
+
         let dim_count = self.gws.dim_count();
 
-        let gws = match self.gws.to_work_size() {
+        let mut gws = match self.gws.to_work_size() {
             Some(gws) => gws,
             None => return Err(KernelError::CmdNoGws.into()),
         };
 
+        // A zero-sized range is invalid and is handled inconsistently across
+        // platforms.
+        if gws.iter().any(|&g| g == 0) {
+            return Err(KernelError::CmdGwsZero(gws).into());
+        }
+
+        let mut lws = if self.lws_auto { None } else { self.lws.to_work_size() };
+
+        if self.pad_gws {
+            let lws_padded = match lws {
+                Some(lws) => lws,
+                None => [self.preferred_wg_size_multiple(queue)?, 1, 1],
+            };
+            for (g, &l) in gws.iter_mut().zip(lws_padded.iter()).filter(|&(_, &l)| l > 0) {
+                *g = ((*g + l - 1) / l) * l;
+            }
+            lws = Some(lws_padded);
+        }
+
+        if self.lws_auto && lws.is_none() {
+            lws = Some(self.auto_lws(queue, &gws)?);
+        }
+
+        if let Some(ref lws) = lws {
+            if !self.unchecked { self.verify_lws(queue, lws)?; }
+        }
+
         core::enqueue_kernel(queue, &self.kernel, dim_count, self.gwo.to_work_offset(),
-            &gws, self.lws.to_work_size(), self.wait_events, self.new_event)
+            &gws, lws, self.wait_events, self.new_event)
             .map_err(OclError::from)
     }
+
+    /// Enqueues this kernel command and returns a new event representing its
+    /// completion.
+    ///
+    /// Equivalent to creating an empty event, passing it to `::enew`, and
+    /// calling `::enq`:
+    ///
+    /// ```rust,ignore
+    /// let event = kernel_0.cmd().queue(&queue).enq_enew()?;
+    /// kernel_1.cmd().queue(&queue).ewait(&event).enq()?;
+    /// ```
+    ///
+    /// Returns an error if a new event destination has already been set
+    /// with `::enew`.
+    ///
+    /// # Safety
+    ///
+    /// See `::enq`.
+    pub unsafe fn enq_enew(self) -> OclResult<Event> {
+        if self.new_event.is_some() {
+            return Err("KernelCmd::enq_enew: A new event destination has already been set \
+                with '::enew'. Use '::enq' instead.".into());
+        }
+
+        let mut event = Event::empty();
+        self.enew(&mut event).enq()?;
+        Ok(event)
+    }
+
+    /// Enqueues this kernel once per tile of size `tile` covering the whole
+    /// domain of size `total`, returning the events of each enqueue in
+    /// order.
+    ///
+    /// Each enqueue uses a global work offset pointing at the start of its
+    /// tile (relative to the global work offset set with `::gwo`, if any)
+    /// and a global work size equal to `tile`, truncated at the upper edges
+    /// of the domain when `total` is not a multiple of `tile`. Within the
+    /// kernel, `get_global_id` therefore returns indices within `total` as
+    /// if the kernel had been enqueued once over the entire domain. Any
+    /// global work size set with `::gws` is ignored.
+    ///
+    /// All tiles wait on the events set with `::ewait`. `::enew` and
+    /// `::single_task` are not supported and will cause an error to be
+    /// returned, as will `total` and `tile` having differing dimension
+    /// counts or `tile` having a zero dimension.
+    ///
+    /// If a local work size is set (with `::lws` or as the kernel's
+    /// default), both `tile` and `total` must be multiples of it in each
+    /// dimension so that every tile (including those at the edges) consists
+    /// of whole work groups. This is verified before anything is enqueued.
+    ///
+    /// If enqueuing a tile fails, the error is returned and the events of
+    /// tiles already enqueued are dropped. Those tiles remain enqueued and
+    /// will still execute; use `Queue::finish` to wait for them.
+    ///
+    /// ```rust,ignore
+    /// // Four enqueues with offsets of 0, 256, 512, and 768:
+    /// let events = kernel.cmd().enq_tiled(1024, 256)?;
+    /// events.wait_for()?;
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// See `::enq`.
+    pub unsafe fn enq_tiled<Dt, Dl>(self, total: Dt, tile: Dl) -> OclResult<EventList>
+            where Dt: Into<SpatialDims>, Dl: Into<SpatialDims> {
+        let (total, tile) = (total.into(), tile.into());
+
+        if self.new_event.is_some() || self.single_task || self.pad_gws || self.lws_auto {
+            return Err("KernelCmd::enq_tiled: '::enew', '::single_task', '::gws_padded', and \
+                '::lws_auto' cannot be used with a tiled enqueue.".into());
+        }
+
+        let dim_count = total.dim_count();
+        let (total_lens, tile_lens) = match (total.to_lens(), tile.to_lens()) {
+            (Ok(t), Ok(l)) if dim_count == tile.dim_count() => (t, l),
+            _ => return Err(format!("KernelCmd::enq_tiled: The domain ({:?}) and tile ({:?}) \
+                dimensions must both be specified and have the same dimension count.",
+                total, tile).into()),
+        };
+
+        if tile_lens.iter().any(|&l| l == 0) {
+            return Err(format!("KernelCmd::enq_tiled: Invalid tile size ({:?}).", tile).into());
+        }
+
+        let queue = match self.queue {
+            Some(q) => q,
+            None => return Err(KernelError::CmdNoQueue.into()),
+        };
+
+        let lws = self.lws.to_work_size();
+        if let Some(ref lws) = lws {
+            if !self.unchecked { self.verify_lws(queue, lws)?; }
+
+            // Edge tiles are only whole work groups if both the tile and the
+            // domain are multiples of the local work size:
+            if (0..3).any(|d| lws[d] == 0 || tile_lens[d] % lws[d] != 0 ||
+                    total_lens[d] % lws[d] != 0) {
+                return Err(format!("KernelCmd::enq_tiled: The domain ({:?}) and tile ({:?}) \
+                    sizes must both be multiples of the local work size ({:?}).", total, tile,
+                    self.lws).into());
+            }
+        }
+
+        let base_offset = self.gwo.to_work_offset().unwrap_or([0, 0, 0]);
+        let wait_list = self.wait_events.as_ref().map(|wl| wl.to_list());
+        let tile_counts: Vec<usize> = (0..3).map(|d| {
+            (total_lens[d] + tile_lens[d] - 1) / tile_lens[d]
+        }).collect();
+
+        let mut events = EventList::with_capacity(tile_counts.iter().product());
+
+        for z in 0..tile_counts[2] {
+            for y in 0..tile_counts[1] {
+                for x in 0..tile_counts[0] {
+                    let mut offset = [0; 3];
+                    let mut gws = [0; 3];
+
+                    for (d, &idx) in [x, y, z].iter().enumerate() {
+                        let start = idx * tile_lens[d];
+                        offset[d] = base_offset[d] + start;
+                        gws[d] = std::cmp::min(tile_lens[d], total_lens[d] - start);
+                    }
+
+                    let mut event = Event::empty();
+                    core::enqueue_kernel(queue, &self.kernel, dim_count, Some(offset), &gws,
+                        lws, wait_list.as_ref(), Some(&mut event))?;
+                    events.push(event);
+                }
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Returns the preferred work group size multiple of the kernel on the
+    /// device associated with `queue`.
+    fn preferred_wg_size_multiple(&self, queue: &CommandQueueCore) -> OclResult<usize> {
+        let device = match core::get_command_queue_info(queue, CommandQueueInfo::Device)? {
+            CommandQueueInfoResult::Device(d) => d,
+            _ => unreachable!(),
+        };
+
+        match core::get_kernel_work_group_info(self.kernel, &device,
+                KernelWorkGroupInfo::PreferredWorkGroupSizeMultiple)? {
+            KernelWorkGroupInfoResult::PreferredWorkGroupSizeMultiple(m) => Ok(m),
+            r => Err(format!("KernelCmd: Preferred work group size multiple \
+                unavailable: {}", r).into()),
+        }
+    }
+
+    /// Returns a local work size for the global work size `gws` (see
+    /// `::lws_auto`).
+    fn auto_lws(&self, queue: &CommandQueueCore, gws: &[usize; 3]) -> OclResult<[usize; 3]> {
+        let device = match core::get_command_queue_info(queue, CommandQueueInfo::Device)? {
+            CommandQueueInfoResult::Device(d) => d,
+            _ => unreachable!(),
+        };
+
+        let max_item_size = match core::get_device_info(&device, DeviceInfo::MaxWorkItemSizes)? {
+            DeviceInfoResult::MaxWorkItemSizes(s) => s[0],
+            _ => unreachable!(),
+        };
+
+        let max_wg_size = match core::get_kernel_work_group_info(self.kernel, &device,
+                KernelWorkGroupInfo::WorkGroupSize)? {
+            KernelWorkGroupInfoResult::WorkGroupSize(s) => s,
+            r => return Err(format!("KernelCmd::lws_auto: Work group size unavailable: {}",
+                r).into()),
+        };
+
+        let max = std::cmp::max(std::cmp::min(max_item_size, max_wg_size), 1);
+        let multiple = self.preferred_wg_size_multiple(queue)?;
+
+        if multiple > 0 && multiple <= max && gws[0] % multiple == 0 {
+            return Ok([multiple, 1, 1]);
+        }
+
+        let size = (1..std::cmp::min(max, gws[0]) + 1).rev()
+            .find(|&l| gws[0] % l == 0)
+            .unwrap_or(1);
+        Ok([size, 1, 1])
+    }
+
+    /// Verifies that a local work size does not exceed the maximum work
+    /// item sizes of the device associated with `queue` or the maximum work
+    /// group size of the kernel on that device.
+    fn verify_lws(&self, queue: &CommandQueueCore, lws: &[usize; 3]) -> OclResult<()> {
+        let device = match core::get_command_queue_info(queue, CommandQueueInfo::Device)? {
+            CommandQueueInfoResult::Device(d) => d,
+            _ => unreachable!(),
+        };
+
+        let max_item_sizes = match core::get_device_info(&device, DeviceInfo::MaxWorkItemSizes)? {
+            DeviceInfoResult::MaxWorkItemSizes(s) => s,
+            _ => unreachable!(),
+        };
+
+        for (dim, (&size, &max)) in lws.iter().zip(max_item_sizes.iter()).enumerate() {
+            if size > max {
+                return Err(KernelError::CmdLwsExceedsMaxWorkItemSize { dim, size, max }.into());
+            }
+        }
+
+        let size: usize = lws.iter().product();
+
+        if let KernelWorkGroupInfoResult::WorkGroupSize(max) = core::get_kernel_work_group_info(
+                self.kernel, &device, KernelWorkGroupInfo::WorkGroupSize)? {
+            if size > max {
+                return Err(KernelError::CmdLwsExceedsMaxWgSize { lws: *lws, size, max }.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "opencl_version_2_0"))]
+    unsafe fn enq_single_task(self, queue: &CommandQueueCore) -> OclResult<()> {
+        let name = core::get_kernel_info(self.kernel, KernelInfo::FunctionName).ok()
+            .map(|n| n.to_string());
+        core::enqueue_task(queue, &self.kernel, self.wait_events, self.new_event,
+            name.as_ref().map(|n| n.as_str())).map_err(OclError::from)
+    }
+
+    #[cfg(feature = "opencl_version_2_0")]
+    unsafe fn enq_single_task(self, queue: &CommandQueueCore) -> OclResult<()> {
+        core::enqueue_kernel(queue, &self.kernel, 1, None, &[1, 1, 1], Some([1, 1, 1]),
+            self.wait_events, self.new_event).map_err(OclError::from)
+    }
 }
 
 
@@ -275,6 +631,10 @@ impl<'b> ArgValKeeper<'b> {
         unsafe { ArgValKeeper::OwnedPrm(util::into_bytes(prm)) }
     }
 
+    unsafe fn owned_struct<T>(value: T) -> ArgValKeeper<'b> where T: Copy + 'static {
+        ArgValKeeper::OwnedPrm(util::into_bytes(value))
+    }
+
     fn owned_mem<T>(buf: MemCore) -> ArgValKeeper<'b> where T: OclPrm {
         ArgValKeeper::OwnedMem(buf)
     }
@@ -413,6 +773,19 @@ impl<'b, T> From<Image<T>> for ArgValConverter<'b, T> where T: OclPrm {
     }
 }
 
+#[cfg(feature = "opencl_version_2_0")]
+impl<'b, T> From<&'b Pipe<T>> for ArgValConverter<'b, T> where T: OclPrm {
+    /// Converts from a `Pipe`.
+    fn from(pipe: &'b Pipe<T>) -> ArgValConverter<'b, T> {
+        ArgValConverter {
+            val: ArgValKeeper::Shared(ArgVal::mem(pipe.as_core())),
+            type_id: None,
+            mem: Some(pipe.as_core().clone()),
+            _ty: PhantomData,
+        }
+    }
+}
+
 impl<'b, T> From<&'b T> for ArgValConverter<'b, T> where T: OclPrm {
     /// Converts from a scalar or vector value.
     fn from(prm: &'b T) -> ArgValConverter<'b, T> {
@@ -526,6 +899,9 @@ pub struct Kernel {
     gws: SpatialDims,
     lws: SpatialDims,
     arg_types: Option<Vec<ArgType>>,
+    // Argument names as reported by the platform. Populated the first time a
+    // name not declared using the builder is resolved.
+    src_arg_names: RefCell<Option<Vec<String>>>,
 }
 
 impl Kernel {
@@ -561,8 +937,39 @@ impl Kernel {
     }
 
     /// Returns the argument index of a named argument if it exists.
+    ///
+    /// Names declared using the builder (`::arg_named`) take precedence,
+    /// otherwise the argument names reported by the platform (see
+    /// `KernelArgInfo::Name`) are searched.
     pub fn named_arg_idx(&self, name: &'static str) -> Option<u32> {
-        self.named_args.resolve_idx(name).ok()
+        self.resolve_arg_idx(name.into()).ok()
+    }
+
+    /// Resolves an argument index specifier into an argument index.
+    ///
+    /// Argument names not declared using the builder are looked up amongst
+    /// the argument names reported by the platform, when available (OpenCL
+    /// 1.2+, some platforms also require the program to have been built with
+    /// `-cl-kernel-arg-info`). The reported names are queried once then
+    /// cached.
+    fn resolve_arg_idx(&self, idx: ArgIdxSpecifier) -> OclResult<u32> {
+        match idx.to_idx(&self.named_args) {
+            Ok(arg_idx) => Ok(arg_idx),
+            Err(err) => {
+                if let ArgIdxSpecifier::Str(ref name) = idx {
+                    if self.src_arg_names.borrow().is_none() {
+                        *self.src_arg_names.borrow_mut() = self.arg_names().ok();
+                    }
+
+                    if let Some(ref names) = *self.src_arg_names.borrow() {
+                        if let Some(arg_idx) = names.iter().position(|n| n == name) {
+                            return Ok(arg_idx as u32);
+                        }
+                    }
+                }
+                Err(err)
+            },
+        }
     }
 
     /// Sets an argument by index without checks of any kind.
@@ -586,6 +993,12 @@ impl Kernel {
     /// Sets a `Buffer`, `Image`, scalar, or vector argument by index or by
     /// name.
     ///
+    /// Arguments may be re-set any number of times after the kernel has been
+    /// built, for example to swap the buffers used between enqueues. Names
+    /// declared with `KernelBuilder::arg_named` are resolved first, followed
+    /// by the names used in the kernel source when available (see
+    /// `::named_arg_idx`).
+    ///
     /// ### Example
     /// ```rust,ignore
     /// // Create a kernel with arguments corresponding to those in the kernel.
@@ -606,7 +1019,7 @@ impl Kernel {
     /// ```
     pub fn set_arg<'a, T, Ai, Av>(&self, idx: Ai, arg: Av) -> OclResult<()>
             where T: OclPrm, Ai: Into<ArgIdxSpecifier>, Av: Into<ArgValConverter<'a, T>> {
-        let arg_idx = idx.into().to_idx(&self.named_args)?;
+        let arg_idx = self.resolve_arg_idx(idx.into())?;
         self.verify_arg_type::<T>(arg_idx)?;
         let arg: ArgValConverter<T> = arg.into();
 
@@ -624,6 +1037,119 @@ impl Kernel {
         self._set_arg::<T>(arg_idx, val)
     }
 
+    /// Sets a local memory argument, by index or by name, to an allocation
+    /// of `length * size_of::<T>()` bytes.
+    ///
+    /// Unlike `KernelBuilder::arg_local`, this may be called any number of
+    /// times after the kernel has been built, allowing scratch space (e.g.
+    /// for a reduction or scan) to be sized to the local work size of each
+    /// enqueue. The size is verified against the local memory size
+    /// (`DeviceInfo::LocalMemSize`) of each device associated with the
+    /// kernel's program.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// // __kernel void sum(__global float* buf, __local float* scratch) { ... }
+    /// kern.set_arg_local::<f32, _>("scratch", lws)?;
+    /// unsafe { kern.cmd().local_work_size(lws).enq()?; }
+    /// ```
+    pub fn set_arg_local<T, Ai>(&self, idx: Ai, length: usize) -> OclResult<()>
+            where T: OclPrm, Ai: Into<ArgIdxSpecifier> {
+        let arg_idx = self.resolve_arg_idx(idx.into())?;
+        self.verify_arg_type::<T>(arg_idx)?;
+        self.verify_local_mem_size(arg_idx, (length * std::mem::size_of::<T>()) as u64)?;
+        self.mem_args.remove(&arg_idx);
+        core::set_kernel_arg(&self.obj_core, arg_idx, ArgVal::local::<T>(&length))
+            .map_err(OclError::from)
+    }
+
+    /// Verifies that a local memory allocation of `size` bytes does not
+    /// exceed the local memory size of any device associated with this
+    /// kernel's program.
+    fn verify_local_mem_size(&self, arg_idx: u32, size: u64) -> OclResult<()> {
+        let program = match core::get_kernel_info(&self.obj_core, KernelInfo::Program)? {
+            KernelInfoResult::Program(p) => p,
+            _ => unreachable!(),
+        };
+
+        let devices = match core::get_program_info(&program, ProgramInfo::Devices)? {
+            ProgramInfoResult::Devices(d) => d,
+            _ => unreachable!(),
+        };
+
+        for device in devices.iter() {
+            let max = match core::get_device_info(device, DeviceInfo::LocalMemSize)? {
+                DeviceInfoResult::LocalMemSize(s) => s,
+                _ => unreachable!(),
+            };
+
+            if size > max {
+                return Err(KernelError::ArgLocalExceedsLocalMemSize { idx: arg_idx, size, max }
+                    .into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets a plain-old-data (e.g. struct) argument, by index or by name, to
+    /// a copy of `value`.
+    ///
+    /// `T` should match the size and layout of the type declared in the
+    /// kernel. No argument type verification is performed.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// // typedef struct { float a; int b; } Params;
+    /// // __kernel void apply(__global float* buf, Params params) { ... }
+    /// #[repr(C)]
+    /// #[derive(Clone, Copy)]
+    /// struct Params { a: f32, b: i32 }
+    ///
+    /// unsafe { kern.set_arg_struct("params", &Params { a: 2.0, b: 3 })?; }
+    /// ```
+    ///
+    /// ### Safety
+    ///
+    /// `T` must be a padding-free, `#[repr(C)]`, plain-data type. See
+    /// `ArgVal::structure`.
+    pub unsafe fn set_arg_struct<T, Ai>(&self, idx: Ai, value: &T) -> OclResult<()>
+            where T: Copy + 'static, Ai: Into<ArgIdxSpecifier> {
+        let arg_idx = self.resolve_arg_idx(idx.into())?;
+        self.mem_args.remove(&arg_idx);
+        core::set_kernel_arg(&self.obj_core, arg_idx, ArgVal::structure(value))
+            .map_err(OclError::from)
+    }
+
+    /// Sets a pointer argument, by index or by name, to the start of a
+    /// shared virtual memory vector.
+    ///
+    /// Unlike buffers, `svm` is not retained by this kernel and must outlive
+    /// every enqueue using it.
+    #[cfg(feature = "opencl_version_2_0")]
+    pub fn set_arg_svm<T, Ai>(&self, idx: Ai, svm: &SvmVec<T>) -> OclResult<()>
+            where T: OclPrm, Ai: Into<ArgIdxSpecifier> {
+        let arg_idx = self.resolve_arg_idx(idx.into())?;
+        self.verify_arg_type::<T>(arg_idx)?;
+        self.mem_args.remove(&arg_idx);
+        unsafe {
+            core::set_kernel_arg_svm_pointer(&self.obj_core, arg_idx, svm.as_ptr() as *const c_void)
+                .map_err(OclError::from)
+        }
+    }
+
+    /// Sets a pipe argument, by index or by name.
+    ///
+    /// Pipe parameters (e.g. `__read_only pipe int`) carry no pointee type
+    /// information so no argument type verification is performed.
+    #[cfg(feature = "opencl_version_2_0")]
+    pub fn set_arg_pipe<T, Ai>(&self, idx: Ai, pipe: &Pipe<T>) -> OclResult<()>
+            where T: OclPrm, Ai: Into<ArgIdxSpecifier> {
+        let arg_idx = self.resolve_arg_idx(idx.into())?;
+        self.mem_args.insert(arg_idx, pipe.as_core().clone());
+        core::set_kernel_arg(&self.obj_core, arg_idx, ArgVal::mem(pipe.as_core()))
+            .map_err(OclError::from)
+    }
+
     /// Modifies the kernel argument named: `name`.
     #[deprecated(since = "0.18.0", note = "Use `::set_arg` instead.")]
     pub fn set_arg_buf_named<'a, T, M>(&'a self, name: &'static str,
@@ -693,14 +1219,46 @@ impl Kernel {
     pub fn set_arg_sampler_named<'a, Ai>(&'a self, idx: Ai,
             sampler_opt: Option<&Sampler>) -> OclResult<()>
             where Ai: Into<ArgIdxSpecifier> {
-        // let arg_idx = self.named_args.resolve_idx(name)?;
-        let arg_idx = idx.into().to_idx(&self.named_args)?;
+        let arg_idx = self.resolve_arg_idx(idx.into())?;
         match sampler_opt {
             Some(sampler) => self._set_arg::<u64>(arg_idx, ArgVal::sampler(sampler)),
             None => self._set_arg::<u64>(arg_idx, ArgVal::sampler_null()),
         }
     }
 
+    /// Returns a new kernel created from the same program and function name
+    /// as this one, with its own, independent, set of arguments.
+    ///
+    /// Cloning the underlying `KernelCore` only retains the same OpenCL
+    /// kernel object, sharing its argument state, making concurrent use with
+    /// different arguments racy. The kernel returned here does not share
+    /// argument state with this one, allowing both to be set and enqueued
+    /// independently (from different threads, for example).
+    ///
+    /// Argument names, the default queue, and default work sizes are copied
+    /// but argument values are not and must be set before enqueuing.
+    pub fn clone_fresh(&self) -> OclResult<Kernel> {
+        let program = self.obj_core.program()?;
+        let obj_core = core::create_kernel(&program, self.name()?)?;
+
+        let mem_args = match self.mem_args.0 {
+            Some(_) => MemArgs(Some(RefCell::new(BTreeMap::new()))),
+            None => MemArgs(None),
+        };
+
+        Ok(Kernel {
+            obj_core,
+            named_args: self.named_args.clone(),
+            mem_args,
+            queue: self.queue.clone(),
+            gwo: self.gwo,
+            gws: self.gws,
+            lws: self.lws,
+            arg_types: self.arg_types.clone(),
+            src_arg_names: RefCell::new(self.src_arg_names.borrow().clone()),
+        })
+    }
+
     /// Returns a command builder which is used to chain parameters of an
     /// 'enqueue' command together.
     pub fn cmd(&self) -> KernelCmd {
@@ -711,7 +1269,11 @@ impl Kernel {
             gws: self.gws,
             lws: self.lws,
             wait_events: None,
-            new_event: None
+            new_event: None,
+            single_task: false,
+            unchecked: false,
+            pad_gws: false,
+            lws_auto: false,
         }
     }
 
@@ -818,18 +1380,57 @@ impl Kernel {
         core::get_kernel_info(&self.obj_core, info_kind).map_err(OclError::from)
     }
 
+    /// Returns the current reference count of the underlying kernel object.
+    ///
+    /// Intended for debugging leaks; the value may be stale as soon as it is
+    /// returned.
+    pub fn reference_count(&self) -> OclResult<u32> {
+        match self.info(KernelInfo::ReferenceCount)? {
+            KernelInfoResult::ReferenceCount(count) => Ok(count),
+            _ => unreachable!(),
+        }
+    }
+
     /// Returns work group information for this kernel.
     pub fn wg_info(&self, device: Device, info_kind: KernelWorkGroupInfo)
             -> OclResult<KernelWorkGroupInfoResult> {
         core::get_kernel_work_group_info(&self.obj_core, device, info_kind).map_err(OclError::from)
     }
 
+    /// Returns the maximum work group size which can be used to execute this
+    /// kernel on `device`.
+    pub fn max_wg_size(&self, device: Device) -> OclResult<usize> {
+        match self.wg_info(device, KernelWorkGroupInfo::WorkGroupSize)? {
+            KernelWorkGroupInfoResult::WorkGroupSize(r) => Ok(r),
+            r => Err(format!("Kernel::max_wg_size: Work group size unavailable: {}", r).into()),
+        }
+    }
+
+    /// Returns the preferred multiple of work group size for launch on
+    /// `device` (a performance hint).
+    pub fn preferred_wg_size_multiple(&self, device: Device) -> OclResult<usize> {
+        match self.wg_info(device, KernelWorkGroupInfo::PreferredWorkGroupSizeMultiple)? {
+            KernelWorkGroupInfoResult::PreferredWorkGroupSizeMultiple(r) => Ok(r),
+            r => Err(format!("Kernel::preferred_wg_size_multiple: Preferred work group size \
+                multiple unavailable: {}", r).into()),
+        }
+    }
+
     /// Returns argument information for this kernel.
     pub fn arg_info(&self, arg_idx: u32, info_kind: KernelArgInfo)
             -> OclResult<KernelArgInfoResult> {
         arg_info(&*self.as_core(), arg_idx, info_kind)
     }
 
+    /// Returns the names of each of this kernel's arguments, in order, as
+    /// written in the kernel source.
+    ///
+    /// Requires OpenCL 1.2+. Some platforms only provide argument names for
+    /// programs built with the `-cl-kernel-arg-info` compiler option.
+    pub fn arg_names(&self) -> OclResult<Vec<String>> {
+        (0..self.num_args()?).map(|arg_idx| arg_name(&self.obj_core, arg_idx)).collect()
+    }
+
     /// Returns the name of this kernel.
     pub fn name(&self) -> OclResult<String> {
         core::get_kernel_info(&self.obj_core, KernelInfo::FunctionName)
@@ -1148,6 +1749,12 @@ impl<'b> KernelBuilder<'b> {
         self.new_arg(ArgValKeeper::Shared(ArgVal::local::<T>(&length)), None, None)
     }
 
+    /// Non-builder-style version of `::arg_struct()`.
+    unsafe fn new_arg_struct<T>(&mut self, value: T) -> u32
+            where T: Copy + 'static {
+        self.new_arg(ArgValKeeper::owned_struct(value), None, None)
+    }
+
     /// Adds a new `Buffer`, `Image`, scalar, or vector argument to the
     /// kernel.
     ///
@@ -1242,13 +1849,32 @@ impl<'b> KernelBuilder<'b> {
         self
     }
 
+    /// Adds a new plain-old-data (e.g. struct) argument, passed by value.
+    ///
+    /// The argument is added to the bottom of the argument order.
+    ///
+    /// `T` should match the size and layout of the type declared in the
+    /// kernel. No argument type verification is performed. The value is
+    /// copied and may be re-set later using `Kernel::set_arg_struct`.
+    ///
+    /// ### Safety
+    ///
+    /// `T` must be a padding-free, `#[repr(C)]`, plain-data type. See
+    /// `ArgVal::structure`.
+    pub unsafe fn arg_struct<'s, T>(&'s mut self, value: T) -> &'s mut KernelBuilder<'b>
+            where T: Copy + 'static {
+        self.new_arg_struct(value);
+        self
+    }
+
     /// Adds a new argument specifying the allocation of a local variable of size
     /// `length * sizeof(T)` bytes (builder_style).
     ///
     /// The argument is added to the bottom of the argument order.
     ///
     /// Local variables are used to share data between work items in the same
-    /// workgroup.
+    /// workgroup. Use `Kernel::set_arg_local` to resize the allocation after
+    /// the kernel has been built.
     pub fn arg_local<'s, T>(&'s mut self, length: usize) -> &'s mut KernelBuilder<'b>
             where T: OclPrm {
         self.new_arg_loc::<T>(length);
@@ -1483,6 +2109,7 @@ impl<'b> KernelBuilder<'b> {
             gws: self.gws,
             lws: self.lws,
             arg_types,
+            src_arg_names: RefCell::new(None),
         })
     }
 }
@@ -8,26 +8,44 @@ mod context;
 mod program;
 mod kernel;
 mod queue;
+mod queue_set;
 mod buffer;
+mod buffer_pool;
 mod image;
 mod sampler;
 mod pro_que;
 mod event;
 mod spatial_dims;
+mod task_graph;
+mod reduce;
+#[cfg(feature = "opencl_version_2_0")]
+mod svm;
+#[cfg(feature = "opencl_version_2_0")]
+mod pipe;
 
 pub use self::platform::{PlatformError, Extensions, Platform};
-pub use self::device::{DeviceError, Device, DeviceSpecifier};
+pub use self::device::{DeviceError, Device, DeviceSpecifier, DeviceSelector, DeviceSummary};
 pub use self::context::{Context, ContextBuilder};
-pub use self::program::{Program, ProgramBuilder, BuildOpt};
-pub use self::queue::Queue;
+pub use self::program::{Program, ProgramBuilder, BuildOpt, BuildOpts, OptLevel};
+pub use self::queue::{Queue, QueueBuilder, RetryPolicy};
+pub use self::queue_set::{QueueSet, QueueSetPolicy};
 pub use self::kernel::{KernelError, KernelCmd, Kernel, KernelBuilder};
 pub use self::buffer::{BufferCmdKind, BufferCmdDataShape, BufferCmd, Buffer, QueCtx,
-    BufferBuilder, BufferReadCmd, BufferWriteCmd, BufferMapCmd, BufferCmdError};
-pub use self::image::{ImageCmdKind, ImageCmd, Image, ImageBuilder};
-pub use self::sampler::Sampler;
+    BufferBuilder, BufferReadCmd, BufferWriteCmd, BufferMapCmd, BufferCmdError, MappedGuard,
+    BufferReadChunks};
+pub use self::buffer_pool::{BufferPool, PooledBuffer};
+pub use self::image::{ImageCmdKind, ImageCmd, ImageMapCmd, Image, ImageMap, ImageBuilder};
+pub use self::sampler::{Sampler, SamplerBuilder};
 pub use self::pro_que::{ProQue, ProQueBuilder};
-pub use self::event::{Event, EventArray, EventList, IntoMarker, RawEventArray, IntoRawEventArray};
+pub use self::event::{Event, UserEvent, EventArray, EventList, IntoMarker, RawEventArray,
+    IntoRawEventArray, ProfilingSummary};
 pub use self::spatial_dims::SpatialDims;
+pub use self::task_graph::{TaskGraph, TaskGraphError};
+pub use self::reduce::ReduceOp;
+#[cfg(feature = "opencl_version_2_0")]
+pub use self::svm::SvmVec;
+#[cfg(feature = "opencl_version_2_0")]
+pub use self::pipe::Pipe;
 #[cfg(not(feature = "async_block"))]
 pub use self::cb::{_unpark_task, box_raw_void};
 pub use self::traits::{MemLen, WorkDims};
@@ -110,7 +128,7 @@ mod cb {
 mod types {
     use std::ptr;
     use std::cell::Ref;
-    use standard::{Event, EventList, RawEventArray, Queue};
+    use standard::{Event, UserEvent, EventList, RawEventArray, Queue};
     use core::ffi::cl_event;
     use core::{Event as EventCore, ClNullEventPtr, ClWaitListPtr};
     use error::Result as OclResult;
@@ -238,6 +256,12 @@ mod types {
         }
     }
 
+    impl<'a> From<&'a UserEvent> for ClWaitListPtrEnum<'a> {
+        fn from(e: &'a UserEvent) -> ClWaitListPtrEnum<'a> {
+            ClWaitListPtrEnum::Event(e.as_event())
+        }
+    }
+
     impl<'a> From<&'a mut Event> for ClWaitListPtrEnum<'a> {
         fn from(e: &'a mut Event) -> ClWaitListPtrEnum<'a> {
             ClWaitListPtrEnum::Event(e)
@@ -0,0 +1,99 @@
+//! Pipes (OpenCL 2.0+).
+
+use std;
+use std::ops::Deref;
+use std::marker::PhantomData;
+use core::{self, OclPrm, Mem as MemCore, MemFlags, MemInfo, MemInfoResult, PipeInfo,
+    PipeInfoResult};
+use error::{Error as OclError, Result as OclResult};
+use standard::Context;
+
+
+/// A first-in, first-out queue of `T` packets usable only by kernels, for
+/// example to stream data from a producer kernel to a consumer kernel without
+/// round-tripping through a global buffer.
+///
+/// Pass to a kernel using `KernelBuilder::arg` or `Kernel::set_arg_pipe`
+/// where the kernel parameter is declared as, for example, `__write_only pipe
+/// int`. Programs using pipes must be built with `-cl-std=CL2.0` or higher.
+///
+/// ## Destruction
+///
+/// Handled automatically. Feel free to store, clone, and share among threads
+/// as you please.
+///
+#[derive(Debug, Clone)]
+pub struct Pipe<T: OclPrm> {
+    obj_core: MemCore,
+    _pd: PhantomData<T>,
+}
+
+impl<T: OclPrm> Pipe<T> {
+    /// Creates a new pipe capable of holding `max_packets` packets, each the
+    /// size of `T`.
+    ///
+    /// `flags` defaults to `MEM_READ_WRITE`. Only `MEM_READ_WRITE` and
+    /// `MEM_HOST_NO_ACCESS` are valid.
+    ///
+    /// Returns an error if any device within `context` does not support
+    /// OpenCL 2.0.
+    pub fn new(context: &Context, max_packets: u32, flags: Option<MemFlags>)
+            -> OclResult<Pipe<T>> {
+        let flags = flags.unwrap_or(MemFlags::new().read_write());
+        let packet_size = std::mem::size_of::<T>() as u32;
+
+        let obj_core = core::create_pipe(context, flags, packet_size, max_packets, None)?;
+
+        Ok(Pipe {
+            obj_core: obj_core,
+            _pd: PhantomData,
+        })
+    }
+
+    /// Returns the size of each packet, in bytes.
+    pub fn packet_size(&self) -> OclResult<u32> {
+        match self.info(PipeInfo::PacketSize)? {
+            PipeInfoResult::PacketSize(s) => Ok(s),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the maximum number of packets this pipe can hold.
+    pub fn max_packets(&self) -> OclResult<u32> {
+        match self.info(PipeInfo::MaxPackets)? {
+            PipeInfoResult::MaxPackets(c) => Ok(c),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns info about this pipe.
+    pub fn info(&self, info_kind: PipeInfo) -> OclResult<PipeInfoResult> {
+        core::get_pipe_info(&self.obj_core, info_kind).map_err(OclError::from)
+    }
+
+    /// Returns info about the underlying memory object.
+    pub fn mem_info(&self, info_kind: MemInfo) -> OclResult<MemInfoResult> {
+        core::get_mem_object_info(&self.obj_core, info_kind).map_err(OclError::from)
+    }
+
+    /// Returns a reference to the core pointer wrapper, usable by functions in
+    /// the `core` module.
+    #[inline]
+    pub fn as_core(&self) -> &MemCore {
+        &self.obj_core
+    }
+}
+
+impl<T: OclPrm> Deref for Pipe<T> {
+    type Target = MemCore;
+
+    fn deref(&self) -> &MemCore {
+        &self.obj_core
+    }
+}
+
+impl<T: OclPrm> AsRef<MemCore> for Pipe<T> {
+    fn as_ref(&self) -> &MemCore {
+        &self.obj_core
+    }
+}
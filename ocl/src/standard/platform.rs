@@ -5,10 +5,12 @@
 //! k/1.2/docs/man/xhtml/clGetPlatformInfo.html)
 
 use std;
+use std::mem;
 use std::ops::{Deref, DerefMut};
 use std::str::SplitWhitespace;
-use ffi::cl_platform_id;
-use core::{self, PlatformId as PlatformIdCore, PlatformInfo, PlatformInfoResult, ClPlatformIdPtr};
+use ffi::{cl_platform_id, c_void};
+use core::{self, PlatformId as PlatformIdCore, PlatformInfo, PlatformInfoResult, ClPlatformIdPtr,
+    OpenclVersion};
 use error::{Error as OclError, Result as OclResult};
 
 
@@ -16,6 +18,10 @@ use error::{Error as OclError, Result as OclResult};
 pub enum PlatformError {
     #[fail(display = "No platforms found.")]
     NoPlatforms,
+    #[fail(display = "The extension '{}' is not advertised by this platform.", _0)]
+    ExtensionUnsupported(String),
+    #[fail(display = "Extension function types must be the size of a pointer (function: '{}').", _0)]
+    ExtensionFnInvalidType(String),
 }
 
 
@@ -34,13 +40,18 @@ impl Extensions {
     pub fn as_str(&self) -> &str {
         &self.inner
     }
+
+    /// Returns true if `extension` is contained within this list.
+    pub fn contains(&self, extension: &str) -> bool {
+        self.iter().any(|ext| ext == extension)
+    }
 }
 
 
 /// A platform identifier.
 ///
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Hash)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
 pub struct Platform(PlatformIdCore);
 
 impl Platform {
@@ -121,6 +132,19 @@ impl Platform {
             .map(|r| r.into()).map_err(OclError::from)
     }
 
+    /// Returns the OpenCL version supported by the platform, parsed from the
+    /// string returned by `::version` (e.g. 'OpenCL 1.2 ...' -> `[1, 2]`).
+    ///
+    /// Use the result to branch on features available only in particular
+    /// versions:
+    ///
+    /// ```rust,ignore
+    /// if platform.opencl_version()? >= [2, 0].into() { ... }
+    /// ```
+    pub fn opencl_version(&self) -> OclResult<OpenclVersion> {
+        self.0.version().map_err(OclError::from)
+    }
+
     /// Returns the platform name as a string.
     pub fn name(&self) -> OclResult<String> {
         core::get_platform_info(&self.0, PlatformInfo::Name)
@@ -142,6 +166,44 @@ impl Platform {
         extensions.map(|e| Extensions { inner: e.into() }).map_err(OclError::from)
     }
 
+    /// Unloads the platform compiler, releasing the resources it has
+    /// allocated.
+    ///
+    /// This is a hint to the implementation and is useful on memory
+    /// constrained devices once all programs have been built. Programs built
+    /// afterwards will transparently cause the compiler to be reloaded.
+    ///
+    /// Requires OpenCL 1.2+.
+    pub fn unload_compiler(&self) -> OclResult<()> {
+        core::unload_platform_compiler(&self.0, None).map_err(OclError::from)
+    }
+
+    /// Returns the address of the extension function named `fn_name` as a
+    /// function pointer of type `F`.
+    ///
+    /// A non-null address does not imply that an extension is supported so
+    /// `extension` (e.g. "cl_khr_icd") is first verified to be listed within
+    /// `CL_PLATFORM_EXTENSIONS`, returning an error if it is not.
+    ///
+    /// Requires OpenCL 1.2+.
+    ///
+    /// ## Safety
+    ///
+    /// `F` must be an `extern "system" fn` type exactly matching the
+    /// signature of the extension function.
+    pub unsafe fn extension_fn<F: Copy>(&self, extension: &str, fn_name: &str) -> OclResult<F> {
+        if mem::size_of::<F>() != mem::size_of::<*mut c_void>() {
+            return Err(PlatformError::ExtensionFnInvalidType(fn_name.to_owned()).into());
+        }
+
+        if !self.extensions()?.contains(extension) {
+            return Err(PlatformError::ExtensionUnsupported(extension.to_owned()).into());
+        }
+
+        let fn_ptr = core::get_extension_function_address_for_platform(&self.0, fn_name, None)?;
+        Ok(mem::transmute_copy(&fn_ptr))
+    }
+
     /// Returns a reference to the underlying `PlatformIdCore`.
     pub fn as_core(&self) -> &PlatformIdCore {
         &self.0
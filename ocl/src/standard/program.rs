@@ -2,19 +2,20 @@
 use std;
 use std::ops::{Deref, DerefMut};
 use std::ffi::CString;
-use std::io::Read;
+use std::io::{Read, Write};
 use std::fs::File;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::collections::HashSet;
 use std::convert::Into;
 
 
 use core::{self, Result as OclCoreResult, Program as ProgramCore, Context as ContextCore,
-    ProgramInfo, ProgramInfoResult, ProgramBuildInfo, ProgramBuildInfoResult};
+    Kernel as KernelCore, ProgramInfo, ProgramInfoResult, ProgramBuildInfo, ProgramBuildInfoResult,
+    ProgramBuildStatus, DeviceInfo, DeviceInfoResult};
 #[cfg(feature = "opencl_version_2_1")]
 use core::ClVersions;
 use error::{Result as OclResult, Error as OclError};
-use standard::{Context, Device, DeviceSpecifier};
+use standard::{Context, Device, DeviceSpecifier, Platform};
 
 
 /// A program from which kernels can be created from.
@@ -83,6 +84,44 @@ impl Program {
         core::get_program_info(&self.0, info_kind)
     }
 
+    /// Returns the current reference count of the underlying program object.
+    ///
+    /// Intended for debugging leaks; the value may be stale as soon as it is
+    /// returned.
+    pub fn reference_count(&self) -> OclResult<u32> {
+        match self.info(ProgramInfo::ReferenceCount)? {
+            ProgramInfoResult::ReferenceCount(count) => Ok(count),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the names of all kernels contained within this program.
+    pub fn kernel_names(&self) -> OclResult<Vec<String>> {
+        match self.info(ProgramInfo::KernelNames)? {
+            ProgramInfoResult::KernelNames(names) => Ok(names),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the number of kernels contained within this program.
+    pub fn num_kernels(&self) -> OclResult<usize> {
+        match self.info(ProgramInfo::NumKernels)? {
+            ProgramInfoResult::NumKernels(num) => Ok(num),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Creates a core kernel object for every kernel function within this
+    /// program.
+    ///
+    /// The order of the returned kernels is implementation defined and may
+    /// not match the order of `::kernel_names`. Use
+    /// `KernelInfo::FunctionName` to identify each. No arguments are set on
+    /// the returned kernels.
+    pub fn create_all_kernels(&self) -> OclResult<Vec<KernelCore>> {
+        core::create_kernels_in_program(&self.0).map_err(OclError::from)
+    }
+
     /// Returns info about this program's build.
     ///
     /// * TODO: Check that device is valid.
@@ -91,6 +130,25 @@ impl Program {
         core::get_program_build_info(&self.0, &device, info_kind)
     }
 
+    /// Returns the build status of this program for `device`.
+    pub fn build_status(&self, device: Device) -> OclResult<ProgramBuildStatus> {
+        match self.build_info(device, ProgramBuildInfo::BuildStatus)? {
+            ProgramBuildInfoResult::BuildStatus(status) => Ok(status),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the build log of this program for `device`.
+    ///
+    /// The log is available after successful builds as well as failed ones
+    /// and may contain compiler warnings.
+    pub fn build_log(&self, device: Device) -> OclResult<String> {
+        match self.build_info(device, ProgramBuildInfo::BuildLog)? {
+            ProgramBuildInfoResult::BuildLog(log) => Ok(log),
+            _ => unreachable!(),
+        }
+    }
+
     fn fmt_info(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         f.debug_struct("Program")
             .field("ReferenceCount", &self.info(ProgramInfo::ReferenceCount))
@@ -171,6 +229,145 @@ impl BuildOpt {
     }
 }
 
+/// Returns the compiler command line text for a build option, if any.
+fn cmplr_opt_string(option: &BuildOpt) -> Option<String> {
+    match *option {
+        BuildOpt::CmplrDefine { ref ident, ref val } => Some(format!("-D {}={}", ident, val)),
+        BuildOpt::CmplrInclDir { ref path } => Some(format!("-I {}", path)),
+        BuildOpt::CmplrOther(ref s) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+
+/// A 64-bit FNV-1a hasher used to name cached program binaries.
+///
+/// Unlike `DefaultHasher`, the output is stable across Rust releases. Values
+/// are fed as explicit bytes so as not to depend on `Hash` implementations.
+struct CacheHasher(u64);
+
+impl CacheHasher {
+    fn new() -> CacheHasher {
+        CacheHasher(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    /// Writes `bytes` preceded by their length so that consecutive values
+    /// cannot run together.
+    fn write_delimited(&mut self, bytes: &[u8]) {
+        self.write_u64(bytes.len() as u64);
+        self.write(bytes);
+    }
+
+    fn write_u64(&mut self, val: u64) {
+        let bytes: Vec<u8> = (0..8).map(|i| (val >> (i * 8)) as u8).collect();
+        self.write(&bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+
+/// An optimization level used by `BuildOpts::opt_level`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OptLevel {
+    /// Disables all optimizations (`-cl-opt-disable`).
+    Disabled,
+    /// The implementation default (no flag).
+    Default,
+    /// Allows `a * b + c` to be computed with reduced accuracy
+    /// (`-cl-mad-enable`).
+    MadEnable,
+}
+
+
+/// A typed set of compiler command line options.
+///
+/// Produces the `-D`, `-I` and `-cl-*` flags passed to the compiler, in the
+/// order they were added. Pass to `ProgramBuilder::build_opts` or format
+/// with `to_string` to inspect the final option string.
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// let opts = BuildOpts::new()
+///     .define("N", 256)
+///     .include_dir("kernels/include")
+///     .fast_relaxed_math(true)
+///     .std("CL1.2");
+///
+/// assert_eq!(opts.to_string(),
+///     "-D N=256 -I kernels/include -cl-fast-relaxed-math -cl-std=CL1.2");
+/// ```
+///
+#[derive(Clone, Debug, Default)]
+pub struct BuildOpts {
+    options: Vec<BuildOpt>,
+}
+
+impl BuildOpts {
+    /// Returns a new, empty, set of options.
+    pub fn new() -> BuildOpts {
+        BuildOpts { options: Vec::with_capacity(16) }
+    }
+
+    /// Adds a macro definition, formatted as `-D {name}={val}`.
+    pub fn define<S: Into<String>, V: ToString>(mut self, name: S, val: V) -> BuildOpts {
+        self.options.push(BuildOpt::CmplrDefine { ident: name.into(), val: val.to_string() });
+        self
+    }
+
+    /// Adds a directory to the header search path, formatted as `-I {path}`.
+    pub fn include_dir<P: AsRef<Path>>(mut self, path: P) -> BuildOpts {
+        self.options.push(BuildOpt::CmplrInclDir { path: path.as_ref().display().to_string() });
+        self
+    }
+
+    /// Sets the optimization level.
+    pub fn opt_level(mut self, level: OptLevel) -> BuildOpts {
+        match level {
+            OptLevel::Disabled => self.options.push(BuildOpt::CmplrOther("-cl-opt-disable".into())),
+            OptLevel::Default => (),
+            OptLevel::MadEnable => self.options.push(BuildOpt::CmplrOther("-cl-mad-enable".into())),
+        }
+        self
+    }
+
+    /// Adds `-cl-fast-relaxed-math` if `enable` is true.
+    pub fn fast_relaxed_math(mut self, enable: bool) -> BuildOpts {
+        if enable { self.options.push(BuildOpt::CmplrOther("-cl-fast-relaxed-math".into())); }
+        self
+    }
+
+    /// Selects the OpenCL C language version, formatted as
+    /// `-cl-std={version}` (e.g. `"CL1.2"` or `"CL2.0"`).
+    pub fn std<S: AsRef<str>>(mut self, version: S) -> BuildOpts {
+        self.options.push(BuildOpt::CmplrOther(format!("-cl-std={}", version.as_ref())));
+        self
+    }
+
+    /// Adds a raw compiler command line parameter (exact text).
+    pub fn other<S: Into<String>>(mut self, opt: S) -> BuildOpts {
+        self.options.push(BuildOpt::CmplrOther(opt.into()));
+        self
+    }
+}
+
+impl std::fmt::Display for BuildOpts {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let opts: Vec<String> = self.options.iter().filter_map(cmplr_opt_string).collect();
+        f.write_str(&opts.join(" "))
+    }
+}
+
 
 /// Options for program creation.
 #[allow(dead_code)]
@@ -194,6 +391,9 @@ pub struct ProgramBuilder<'b> {
     options: Vec<BuildOpt>,
     with: CreateWith<'b>,
     device_spec: Option<DeviceSpecifier>,
+    cache_dir: Option<PathBuf>,
+    unload_compiler: bool,
+    device_opts: Vec<(Device, BuildOpts)>,
 }
 
 impl<'b> ProgramBuilder<'b> {
@@ -203,6 +403,9 @@ impl<'b> ProgramBuilder<'b> {
             options: Vec::with_capacity(64),
             with: CreateWith::None,
             device_spec: None,
+            cache_dir: None,
+            unload_compiler: false,
+            device_opts: Vec::new(),
         }
     }
 
@@ -230,6 +433,17 @@ impl<'b> ProgramBuilder<'b> {
         self
     }
 
+    /// Adds each option from a pre-assembled set of compiler options.
+    ///
+    /// ## Example
+    ///
+    /// `...build_opts(BuildOpts::new().define("N", 256).std("CL1.2"))...`
+    ///
+    pub fn build_opts<'a>(&'a mut self, opts: BuildOpts) -> &'a mut ProgramBuilder<'b> {
+        self.options.extend(opts.options);
+        self
+    }
+
     /// Pushes pre-created build option to the list of options.
     ///
     /// If either `::il` or `::binaries` are used and raw source is added, it
@@ -347,6 +561,61 @@ impl<'b> ProgramBuilder<'b> {
         self
     }
 
+    /// Enables caching of compiled program binaries within the directory
+    /// `path` (created if necessary).
+    ///
+    /// When building from source, a binary is stored for each device, keyed
+    /// by a hash of the source, compiler options, and the device's name,
+    /// vendor, and driver version. Subsequent builds with an identical key
+    /// load the cached binaries instead of compiling. A driver update
+    /// changes the key, causing a fresh build. Cached binaries which fail to
+    /// load are ignored and rebuilt. Failing to store binaries (e.g. due to
+    /// a read-only directory) does not cause the build to fail.
+    ///
+    /// Has no effect when building from binaries or IL.
+    pub fn cache_dir<'a, P: Into<PathBuf>>(&'a mut self, path: P) -> &'a mut ProgramBuilder<'b> {
+        self.cache_dir = Some(path.into());
+        self
+    }
+
+    /// Unloads the platform compiler of each device once the program has
+    /// been built.
+    ///
+    /// Use this when building the last program within a context to release
+    /// the compiler's resources on memory constrained devices. Any programs
+    /// built afterwards will transparently cause the compiler to be
+    /// reloaded. See `Platform::unload_compiler`.
+    pub fn unload_compiler<'a>(&'a mut self) -> &'a mut ProgramBuilder<'b> {
+        self.unload_compiler = true;
+        self
+    }
+
+    /// Adds compiler options which apply only when building for `device`.
+    ///
+    /// These are appended to the options common to all devices (those added
+    /// with `::cmplr_opt`, `::build_opts`, etc.). Only compiler command line
+    /// options are used; options which modify the program source (such as
+    /// `BuildOpt::IncludeDefine`) have no per-device equivalent.
+    ///
+    /// Devices with their own options are built using a separate
+    /// `clBuildProgram` call for each. The resulting program and its kernels
+    /// are usable on every device. Only programs built from source may use
+    /// per-device options and such programs are not cached (see
+    /// `::cache_dir`).
+    ///
+    /// ## Example
+    ///
+    /// `...device_opts(gpu, BuildOpts::new().opt_level(OptLevel::MadEnable))...`
+    ///
+    pub fn device_opts<'a>(&'a mut self, device: Device, opts: BuildOpts)
+            -> &'a mut ProgramBuilder<'b> {
+        match self.device_opts.iter().position(|&(d, _)| d == device) {
+            Some(idx) => self.device_opts[idx].1.options.extend(opts.options),
+            None => self.device_opts.push((device, opts)),
+        }
+        self
+    }
+
     /// Returns the devices specified to be associated the program.
     pub fn get_device_spec(&self) -> &Option<DeviceSpecifier> {
         &self.device_spec
@@ -355,26 +624,7 @@ impl<'b> ProgramBuilder<'b> {
     /// Returns a concatenated string of command line options to be passed to
     /// the compiler when building this program.
     pub fn get_compiler_options(&self) -> OclResult<CString> {
-        let mut opts: Vec<String> = Vec::with_capacity(64);
-
-        for option in &self.options {
-            match *option {
-                BuildOpt::CmplrDefine { ref ident, ref val } => {
-                    opts.push(format!("-D {}={}", ident, val))
-                },
-
-                BuildOpt::CmplrInclDir { ref path } => {
-                    opts.push(format!("-I {}", path))
-                },
-
-                BuildOpt::CmplrOther(ref s) => {
-                    opts.push(s.clone())
-                },
-
-                _ => (),
-            }
-        }
-
+        let opts: Vec<String> = self.options.iter().filter_map(cmplr_opt_string).collect();
         CString::new(opts.join(" ").into_bytes()).map_err(OclError::from)
     }
 
@@ -460,6 +710,163 @@ impl<'b> ProgramBuilder<'b> {
         Ok(src_strings)
     }
 
+    /// Returns the path of the cached binary for each device in `devices`.
+    fn cache_paths(cache_dir: &Path, src_strings: &[CString], cmplr_opts: &CString,
+            devices: &[Device]) -> OclResult<Vec<PathBuf>> {
+        let mut src_hasher = CacheHasher::new();
+        src_hasher.write_u64(src_strings.len() as u64);
+        for src in src_strings {
+            src_hasher.write_delimited(src.as_bytes());
+        }
+        src_hasher.write_delimited(cmplr_opts.as_bytes());
+        let src_hash = src_hasher.finish();
+
+        devices.iter().map(|device| {
+            let mut hasher = CacheHasher::new();
+            hasher.write_u64(src_hash);
+            hasher.write_delimited(device.name()?.as_bytes());
+            hasher.write_delimited(device.vendor()?.as_bytes());
+            hasher.write_delimited(device.info(DeviceInfo::DriverVersion)?.to_string().as_bytes());
+            Ok(cache_dir.join(format!("{:016x}.bin", hasher.finish())))
+        }).collect()
+    }
+
+    /// Builds from source, loading from and storing binaries to `cache_dir`.
+    fn build_source_cached(&self, context: &Context, devices: &[Device], cache_dir: &Path)
+            -> OclResult<Program> {
+        let src_strings = self.get_src_strings()?;
+        let cmplr_opts = self.get_compiler_options()?;
+        let paths = Self::cache_paths(cache_dir, &src_strings, &cmplr_opts, devices)?;
+
+        if paths.iter().all(|path| path.is_file()) {
+            let mut bins = Vec::with_capacity(paths.len());
+            for path in &paths {
+                let mut bin = Vec::new();
+                File::open(path)?.read_to_end(&mut bin)?;
+                bins.push(bin);
+            }
+            let bin_refs: Vec<&[u8]> = bins.iter().map(|bin| &bin[..]).collect();
+
+            // Stale or corrupt binaries fall through to a source build:
+            if let Ok(program) = Program::with_binary(context, devices, &bin_refs, &cmplr_opts) {
+                return Ok(program);
+            }
+        }
+
+        let program = Program::with_source(context, &src_strings, Some(devices), &cmplr_opts)?;
+
+        // Storing binaries is best-effort: an unwritable cache directory
+        // must not fail an otherwise successful build.
+        let _ = Self::store_cached_binaries(&program, devices, &paths, cache_dir);
+
+        Ok(program)
+    }
+
+    /// Writes the binary of `program` for each device in `devices` to the
+    /// corresponding path in `paths`.
+    fn store_cached_binaries(program: &Program, devices: &[Device], paths: &[PathBuf],
+            cache_dir: &Path) -> OclResult<()> {
+        // Binaries are ordered by the program's device list which may differ
+        // from `devices`:
+        let prog_devices = match program.info(ProgramInfo::Devices)? {
+            ProgramInfoResult::Devices(d) => d,
+            _ => unreachable!(),
+        };
+        let binaries = match program.info(ProgramInfo::Binaries)? {
+            ProgramInfoResult::Binaries(b) => b,
+            _ => unreachable!(),
+        };
+
+        std::fs::create_dir_all(cache_dir)?;
+
+        for (device, path) in devices.iter().zip(paths.iter()) {
+            if let Some(idx) = prog_devices.iter().position(|d| d == device.as_core()) {
+                if !binaries[idx].is_empty() {
+                    Self::write_cached_binary(path, &binaries[idx])?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes `binary` to a temporary file then renames it to `path` so that
+    /// concurrent builds never observe (or load) a partially written binary.
+    /// The temporary file is removed upon failure.
+    fn write_cached_binary(path: &Path, binary: &[u8]) -> std::io::Result<()> {
+        let tmp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+
+        let res = File::create(&tmp_path)
+            .and_then(|mut file| file.write_all(binary))
+            .and_then(|_| std::fs::rename(&tmp_path, path));
+
+        if res.is_err() {
+            let _ = std::fs::remove_file(&tmp_path);
+        }
+        res
+    }
+
+    /// Returns the compiler options for `device`, including any per-device
+    /// options.
+    fn device_compiler_options(&self, device: &Device) -> OclResult<CString> {
+        let mut opts: Vec<String> = self.options.iter().filter_map(cmplr_opt_string).collect();
+
+        if let Some(&(_, ref dev_opts)) = self.device_opts.iter().find(|&&(d, _)| d == *device) {
+            opts.extend(dev_opts.options.iter().filter_map(cmplr_opt_string));
+        }
+
+        CString::new(opts.join(" ").into_bytes()).map_err(OclError::from)
+    }
+
+    /// Builds from source using a separate build for each device with its
+    /// own options and a single build for all remaining devices.
+    fn build_source_per_device(&self, context: &Context, devices: &[Device])
+            -> OclResult<Program> {
+        for &(device, _) in &self.device_opts {
+            if !devices.contains(&device) {
+                return Err(format!("ocl::ProgramBuilder::build: The device '{}' has build \
+                    options specified but is not within the program's device list.",
+                    device.name()?).into());
+            }
+        }
+
+        let program = core::create_program_with_source(context, &self.get_src_strings()?)?;
+
+        let common_devices: Vec<Device> = devices.iter()
+            .filter(|d| !self.device_opts.iter().any(|&(od, _)| od == **d))
+            .cloned()
+            .collect();
+
+        if !common_devices.is_empty() {
+            core::build_program(&program, Some(&common_devices[..]),
+                &self.get_compiler_options()?, None, None)?;
+        }
+
+        for &(device, _) in &self.device_opts {
+            core::build_program(&program, Some(&[device][..]),
+                &self.device_compiler_options(&device)?, None, None)?;
+        }
+
+        Ok(Program(program))
+    }
+
+    /// Unloads the compiler of each distinct platform among `devices`.
+    fn unload_platform_compilers(devices: &[Device]) -> OclResult<()> {
+        let mut platforms = Vec::with_capacity(1);
+
+        for device in devices {
+            match device.info(DeviceInfo::Platform)? {
+                DeviceInfoResult::Platform(p) => if !platforms.contains(&p) { platforms.push(p) },
+                _ => unreachable!(),
+            }
+        }
+
+        for platform in platforms {
+            Platform::new(platform).unload_compiler()?;
+        }
+        Ok(())
+    }
+
     /// Returns a newly built Program.
     //
     // * TODO: If the context is associated with more than one device,
@@ -475,16 +882,29 @@ impl<'b> ProgramBuilder<'b> {
         };
 
         match self.with {
+            CreateWith::Source(_) => (),
+            _ if !self.device_opts.is_empty() => {
+                return Err("ocl::ProgramBuilder::build: Per-device build options may only be \
+                    used when building from source.".into());
+            },
+            _ => (),
+        }
+
+        let program = match self.with {
             CreateWith::Il(_) => {
                 return Err("ocl::ProgramBuilder::build: Unreachable section (IL).".into());
             },
-            CreateWith::Source(_) => {
-                Program::with_source(
+            CreateWith::Source(_) if !self.device_opts.is_empty() => {
+                self.build_source_per_device(context, &device_list)
+            },
+            CreateWith::Source(_) => match self.cache_dir {
+                Some(ref cache_dir) => self.build_source_cached(context, &device_list, cache_dir),
+                None => Program::with_source(
                     context,
                     &self.get_src_strings()?,
                     Some(&device_list[..]),
                     &self.get_compiler_options()?,
-                ).map_err(OclError::from)
+                ),
             },
             CreateWith::Binaries(bins) => {
                 Program::with_binary(
@@ -496,7 +916,10 @@ impl<'b> ProgramBuilder<'b> {
             },
             CreateWith::None => return Err("Unable to build program: no source, binary, \
                 or IL has been specified".into()),
-        }
+        }?;
+
+        if self.unload_compiler { Self::unload_platform_compilers(&device_list)?; }
+        Ok(program)
     }
 
     /// Returns a newly built Program.
@@ -514,6 +937,15 @@ impl<'b> ProgramBuilder<'b> {
         };
 
         match self.with {
+            CreateWith::Source(_) => (),
+            _ if !self.device_opts.is_empty() => {
+                return Err("ocl::ProgramBuilder::build: Per-device build options may only be \
+                    used when building from source.".into());
+            },
+            _ => (),
+        }
+
+        let program = match self.with {
             CreateWith::Il(il) => {
                 Program::with_il(
                     il,
@@ -522,13 +954,17 @@ impl<'b> ProgramBuilder<'b> {
                     context
                 )
             },
-            CreateWith::Source(_) => {
-                Program::with_source(
+            CreateWith::Source(_) if !self.device_opts.is_empty() => {
+                self.build_source_per_device(context, &device_list)
+            },
+            CreateWith::Source(_) => match self.cache_dir {
+                Some(ref cache_dir) => self.build_source_cached(context, &device_list, cache_dir),
+                None => Program::with_source(
                     context,
                     &self.get_src_strings()?,
                     Some(&device_list[..]),
                     &self.get_compiler_options()?,
-                )
+                ),
             },
             CreateWith::Binaries(bins) => {
                 Program::with_binary(
@@ -540,7 +976,10 @@ impl<'b> ProgramBuilder<'b> {
             },
             CreateWith::None => return Err("Unable to build program: no source, binary, \
                 or IL has been specified".into()),
-        }
+        }?;
+
+        if self.unload_compiler { Self::unload_platform_compilers(&device_list)?; }
+        Ok(program)
     }
 }
 
@@ -1,15 +1,93 @@
 //! An `OpenCL` command queue.
 
 use std;
+use std::thread;
+use std::time::Duration;
 use std::ops::{Deref, DerefMut};
+use futures::Future;
 use core::{self, Result as OclCoreResult, CommandQueue as CommandQueueCore, CommandQueueInfo,
-    CommandQueueInfoResult, OpenclVersion, CommandQueueProperties, ClWaitListPtr, ClContextPtr};
+    CommandQueueInfoResult, OpenclVersion, CommandQueueProperties, ClWaitListPtr, ClContextPtr,
+    MemMigrationFlags, Mem as MemCore, Status};
 use error::{Error as OclError, Result as OclResult};
 use standard::{Context, Device, Event};
+#[cfg(feature = "opencl_version_2_0")]
+use core::{OclPrm, MapFlags};
+#[cfg(feature = "opencl_version_2_0")]
+use standard::SvmVec;
+
+/// A policy for retrying operations which fail with a transient error.
+///
+/// Only `CL_OUT_OF_RESOURCES` and `CL_MEM_OBJECT_ALLOCATION_FAILURE` are
+/// considered transient (see `::is_transient`). These can occur on busy
+/// devices and may succeed once outstanding work has completed. All other
+/// errors are returned immediately.
+///
+/// Set on a queue using `Queue::with_retry` and applied using
+/// `Queue::retry`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    attempts: u32,
+    backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Returns a new policy which retries a failed operation at most
+    /// `attempts` times, sleeping for `backoff` multiplied by the retry
+    /// number before each retry.
+    pub fn new(attempts: u32, backoff: Duration) -> RetryPolicy {
+        RetryPolicy { attempts, backoff }
+    }
+
+    /// Returns the maximum number of retries.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Returns the base backoff duration.
+    pub fn backoff(&self) -> Duration {
+        self.backoff
+    }
+
+    /// Returns true if `err` is a transient error which may succeed if
+    /// retried.
+    pub fn is_transient(err: &OclError) -> bool {
+        match err.api_status() {
+            Some(Status::CL_OUT_OF_RESOURCES) |
+                Some(Status::CL_MEM_OBJECT_ALLOCATION_FAILURE) => true,
+            _ => false,
+        }
+    }
+
+    /// Calls `op`, retrying according to this policy if it fails with a
+    /// transient error. `wait` is called before each retry (after the
+    /// backoff) and should wait for outstanding work to complete.
+    ///
+    /// Returns the result of the last call to `op`, or the error returned
+    /// by `wait`.
+    pub fn run<R, F, W>(&self, mut op: F, mut wait: W) -> OclResult<R>
+            where F: FnMut() -> OclResult<R>, W: FnMut() -> OclResult<()> {
+        let mut retry = 0;
+        loop {
+            match op() {
+                Err(ref err) if retry < self.attempts && RetryPolicy::is_transient(err) => {
+                    retry += 1;
+                    thread::sleep(self.backoff * retry);
+                    wait()?;
+                },
+                res => return res,
+            }
+        }
+    }
+}
+
 
 /// A command queue which manages all actions taken on kernels, buffers, and
 /// images.
 ///
+/// ## Destruction
+///
+/// Handled automatically. Clones retain the underlying queue and may be
+/// stored and used after the original has been dropped.
 ///
 //
 // * TODO: Consider implementing a constructor which accepts a DeviceIdCore and
@@ -20,9 +98,15 @@ use standard::{Context, Device, Event};
 pub struct Queue {
     obj_core: CommandQueueCore,
     device_version: OpenclVersion,
+    retry_policy: Option<RetryPolicy>,
 }
 
 impl Queue {
+    /// Returns a new `QueueBuilder`.
+    pub fn builder<'a>() -> QueueBuilder<'a> {
+        QueueBuilder::new()
+    }
+
     /// Returns a new Queue on the device specified by `device`.
     pub fn new(context: &Context, device: Device, properties: Option<CommandQueueProperties>)
             -> OclResult<Queue> {
@@ -32,9 +116,44 @@ impl Queue {
         Ok(Queue {
             obj_core: obj_core,
             device_version: device_version,
+            retry_policy: None,
         })
     }
 
+    /// Sets a policy used by `::retry` to retry operations which fail with
+    /// a transient error (`CL_OUT_OF_RESOURCES` or
+    /// `CL_MEM_OBJECT_ALLOCATION_FAILURE`) at most `attempts` times,
+    /// finishing this queue and sleeping for `backoff` multiplied by the
+    /// retry number before each retry (builder-style).
+    ///
+    /// The policy is copied along with clones of this queue.
+    ///
+    /// ```rust,ignore
+    /// let queue = queue.with_retry(3, Duration::from_millis(10));
+    /// queue.retry(|| unsafe { kernel.cmd().queue(&queue).enq() })?;
+    /// ```
+    pub fn with_retry(mut self, attempts: u32, backoff: Duration) -> Queue {
+        self.retry_policy = Some(RetryPolicy::new(attempts, backoff));
+        self
+    }
+
+    /// Returns the retry policy set with `::with_retry`, if any.
+    pub fn retry_policy(&self) -> Option<RetryPolicy> {
+        self.retry_policy
+    }
+
+    /// Calls `op` (typically an enqueue using this queue), retrying
+    /// according to the policy set with `::with_retry` if it fails with a
+    /// transient error. This queue is finished before each retry.
+    ///
+    /// Calls `op` exactly once if no policy has been set. Non-transient
+    /// errors are returned immediately.
+    pub fn retry<R, F>(&self, op: F) -> OclResult<R>
+            where F: FnMut() -> OclResult<R> {
+        self.retry_policy.unwrap_or(RetryPolicy::new(0, Duration::from_millis(0)))
+            .run(op, || self.finish())
+    }
+
     /// Issues all previously queued OpenCL commands to the device.
     pub fn flush(&self) -> OclResult<()> {
         core::flush(&self.obj_core).map_err(OclError::from)
@@ -45,6 +164,38 @@ impl Queue {
         core::finish(&self.obj_core).map_err(OclError::from)
     }
 
+    /// Returns a future which resolves once all previously enqueued commands
+    /// have completed, without blocking the calling thread.
+    ///
+    /// Enqueues a marker, flushes this queue, then returns the marker's event
+    /// (`Event` implements `Future` and is completed using an event
+    /// callback). Requires OpenCL 1.2 or higher.
+    pub fn finish_async(&self) -> OclResult<Event> {
+        let marker = self.enqueue_marker(None::<&Event>)?;
+        self.flush()?;
+        Ok(marker)
+    }
+
+    /// Enqueues a marker over all previously enqueued commands and returns
+    /// both its event and a future which resolves once those commands have
+    /// completed.
+    ///
+    /// Intended for frame synchronization: the event can be passed as a wait
+    /// list to later commands (e.g. those which present a frame) while the
+    /// future paces the host. Like `::finish_async`, this flushes the queue
+    /// and requires OpenCL 1.2 or higher.
+    ///
+    /// ```rust,ignore
+    /// let (frame_done, frame_future) = queue.frame_marker()?;
+    /// present_cmd.ewait(&frame_done).enq()?;
+    /// pool.spawn(frame_future).wait()?;
+    /// ```
+    pub fn frame_marker(&self) -> OclResult<(Event, impl Future<Item = (), Error = OclError>)> {
+        let marker = self.finish_async()?;
+        let future = marker.clone();
+        Ok((marker, future))
+    }
+
     /// Enqueues a marker command which waits for either a list of events to
     /// complete, or all previously enqueued commands to complete.
     pub fn enqueue_marker<Ewl>(&self, ewait: Option<Ewl>) -> OclResult<Event>
@@ -56,6 +207,172 @@ impl Queue {
             .map_err(OclError::from)
     }
 
+    /// Enqueues a barrier command which waits for either a list of events to
+    /// complete, or all previously enqueued commands to complete.
+    ///
+    /// Unlike a marker, a barrier also blocks all commands enqueued after it
+    /// from executing until it completes, making it suitable for fencing an
+    /// out-of-order queue without finishing it.
+    pub fn enqueue_barrier<Ewl>(&self, ewait: Option<Ewl>) -> OclResult<Event>
+            where Ewl: ClWaitListPtr
+    {
+        let mut barrier_event = Event::empty();
+        core::enqueue_barrier_with_wait_list(&self.obj_core, ewait, Some(&mut barrier_event),
+                Some(&self.device_version)).map(|_| barrier_event)
+            .map_err(OclError::from)
+    }
+
+    /// Enqueues a command which migrates the memory objects in `mem_objs` to
+    /// the device associated with this queue, returning its event.
+    ///
+    /// Useful for prefetching buffers to the device which will consume them
+    /// next when a context contains more than one device. Use
+    /// `MIGRATE_MEM_OBJECT_HOST` to migrate to the host instead.
+    ///
+    /// Requires OpenCL 1.2 or higher.
+    pub fn enqueue_migrate<M, Ewl>(&self, mem_objs: &[M], flags: MemMigrationFlags,
+            ewait: Option<Ewl>) -> OclResult<Event>
+            where M: AsRef<MemCore>, Ewl: ClWaitListPtr
+    {
+        let mem_objs: Vec<MemCore> = mem_objs.iter().map(|m| m.as_ref().clone()).collect();
+        let mut migrate_event = Event::empty();
+        core::enqueue_migrate_mem_objects(&self.obj_core, &mem_objs, flags, ewait,
+                Some(&mut migrate_event), Some(&self.device_version)).map(|_| migrate_event)
+            .map_err(OclError::from)
+    }
+
+    /// Enqueues a command which acquires the memory objects in `mem_objs`,
+    /// previously created from OpenGL objects (e.g. with
+    /// `Buffer::from_gl_buffer` or `Image::from_gl_texture`), for use by
+    /// OpenCL, returning its event.
+    ///
+    /// OpenGL must have finished using the objects (e.g. with `glFinish`)
+    /// beforehand. Requires a context created with an associated OpenGL
+    /// context (`ContextBuilder::gl_context`) and the `cl_khr_gl_sharing`
+    /// extension.
+    #[cfg(not(feature="opencl_vendor_mesa"))]
+    pub fn enqueue_acquire_gl_objects<M, Ewl>(&self, mem_objs: &[M], ewait: Option<Ewl>)
+            -> OclResult<Event>
+            where M: AsRef<MemCore>, Ewl: ClWaitListPtr
+    {
+        let mem_objs: Vec<MemCore> = mem_objs.iter().map(|m| m.as_ref().clone()).collect();
+        let mut acquire_event = Event::empty();
+        core::enqueue_acquire_gl_objects(&self.obj_core, &mem_objs, ewait,
+                Some(&mut acquire_event)).map(|_| acquire_event)
+            .map_err(OclError::from)
+    }
+
+    /// Enqueues a command which releases the memory objects in `mem_objs`,
+    /// previously acquired with `::enqueue_acquire_gl_objects`, back to
+    /// OpenGL, returning its event.
+    ///
+    /// The objects must not be used by OpenGL until the returned event has
+    /// completed.
+    #[cfg(not(feature="opencl_vendor_mesa"))]
+    pub fn enqueue_release_gl_objects<M, Ewl>(&self, mem_objs: &[M], ewait: Option<Ewl>)
+            -> OclResult<Event>
+            where M: AsRef<MemCore>, Ewl: ClWaitListPtr
+    {
+        let mem_objs: Vec<MemCore> = mem_objs.iter().map(|m| m.as_ref().clone()).collect();
+        let mut release_event = Event::empty();
+        core::enqueue_release_gl_objects(&self.obj_core, &mem_objs, ewait,
+                Some(&mut release_event)).map(|_| release_event)
+            .map_err(OclError::from)
+    }
+
+    /// Enqueues a command which acquires the memory objects in `mem_objs`,
+    /// previously created from Direct3D 11 resources (e.g. with
+    /// `Buffer::from_d3d11_buffer` or `Image::from_d3d11_texture2d`), for use
+    /// by OpenCL, returning its event.
+    ///
+    /// Requires a context created with an associated Direct3D 11 device
+    /// (`ContextBuilder::d3d11_device`) and the `cl_khr_d3d11_sharing`
+    /// extension.
+    ///
+    /// [Feature: `d3d11_interop`, Windows only]
+    #[cfg(all(feature = "d3d11_interop", windows))]
+    pub fn enqueue_acquire_d3d11_objects<M, Ewl>(&self, mem_objs: &[M], ewait: Option<Ewl>)
+            -> OclResult<Event>
+            where M: AsRef<MemCore>, Ewl: ClWaitListPtr
+    {
+        let mem_objs: Vec<MemCore> = mem_objs.iter().map(|m| m.as_ref().clone()).collect();
+        let mut acquire_event = Event::empty();
+        core::enqueue_acquire_d3d11_objects(&self.obj_core, &mem_objs, ewait,
+                Some(&mut acquire_event)).map(|_| acquire_event)
+            .map_err(OclError::from)
+    }
+
+    /// Enqueues a command which releases the memory objects in `mem_objs`,
+    /// previously acquired with `::enqueue_acquire_d3d11_objects`, back to
+    /// Direct3D 11, returning its event.
+    ///
+    /// [Feature: `d3d11_interop`, Windows only]
+    #[cfg(all(feature = "d3d11_interop", windows))]
+    pub fn enqueue_release_d3d11_objects<M, Ewl>(&self, mem_objs: &[M], ewait: Option<Ewl>)
+            -> OclResult<Event>
+            where M: AsRef<MemCore>, Ewl: ClWaitListPtr
+    {
+        let mem_objs: Vec<MemCore> = mem_objs.iter().map(|m| m.as_ref().clone()).collect();
+        let mut release_event = Event::empty();
+        core::enqueue_release_d3d11_objects(&self.obj_core, &mem_objs, ewait,
+                Some(&mut release_event)).map(|_| release_event)
+            .map_err(OclError::from)
+    }
+
+    /// Maps the contents of `svm` for access by the host, blocking until the
+    /// map is complete.
+    ///
+    /// Required before accessing a coarse-grained SVM vector from the host.
+    /// Call `::svm_unmap` before using it within a kernel again.
+    #[cfg(feature = "opencl_version_2_0")]
+    pub fn svm_map<T, Ewl>(&self, svm: &SvmVec<T>, flags: MapFlags, ewait: Option<Ewl>)
+            -> OclResult<()>
+            where T: OclPrm, Ewl: ClWaitListPtr
+    {
+        unsafe {
+            core::enqueue_svm_map(&self.obj_core, true, flags, svm.as_void_ptr(),
+                svm.len() * std::mem::size_of::<T>(), ewait, None::<&mut Event>)
+                .map_err(OclError::from)
+        }
+    }
+
+    /// Enqueues a command which unmaps `svm`, previously mapped with
+    /// `::svm_map`, returning its event.
+    ///
+    /// The host must not access a coarse-grained SVM vector after it has
+    /// been unmapped.
+    #[cfg(feature = "opencl_version_2_0")]
+    pub fn svm_unmap<T, Ewl>(&self, svm: &SvmVec<T>, ewait: Option<Ewl>) -> OclResult<Event>
+            where T: OclPrm, Ewl: ClWaitListPtr
+    {
+        let mut unmap_event = Event::empty();
+        unsafe {
+            core::enqueue_svm_unmap(&self.obj_core, svm.as_void_ptr(), ewait,
+                Some(&mut unmap_event)).map(|_| unmap_event).map_err(OclError::from)
+        }
+    }
+
+    /// Enqueues a host closure as a native kernel, returning its event.
+    ///
+    /// The closure runs once, on a host thread, when all events in `ewait`
+    /// have completed, allowing host-side work to take part in the same
+    /// dependency graph as other commands. The memory objects in `mem_objs`
+    /// are synchronized with the command as with any other kernel.
+    ///
+    /// The device associated with this queue must support native kernels
+    /// (`DeviceExecCapabilities::NATIVE_KERNEL`), generally only CPU devices
+    /// do.
+    pub fn enqueue_native<F, M, Ewl>(&self, func: F, mem_objs: &[M], ewait: Option<Ewl>)
+            -> OclResult<Event>
+            where F: FnMut() + Send + 'static, M: AsRef<MemCore>, Ewl: ClWaitListPtr
+    {
+        let mem_objs: Vec<MemCore> = mem_objs.iter().map(|m| m.as_ref().clone()).collect();
+        let mut native_event = Event::empty();
+        core::enqueue_native_kernel(&self.obj_core, func, &mem_objs, ewait,
+                Some(&mut native_event)).map(|_| native_event)
+            .map_err(OclError::from)
+    }
+
     /// Returns a reference to the core pointer wrapper, usable by functions in
     /// the `core` module.
     #[inline]
@@ -83,6 +400,31 @@ impl Queue {
         core::get_command_queue_info(&self.obj_core, info_kind)
     }
 
+    /// Returns the current reference count of the underlying command queue object.
+    ///
+    /// Intended for debugging leaks; the value may be stale as soon as it is
+    /// returned.
+    pub fn reference_count(&self) -> OclResult<u32> {
+        match self.info(CommandQueueInfo::ReferenceCount)? {
+            CommandQueueInfoResult::ReferenceCount(count) => Ok(count),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the properties this queue was created with.
+    pub fn properties(&self) -> OclResult<CommandQueueProperties> {
+        match self.info(CommandQueueInfo::Properties)? {
+            CommandQueueInfoResult::Properties(props) => Ok(props),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns true if this queue was created with out-of-order execution
+    /// enabled.
+    pub fn is_out_of_order(&self) -> OclResult<bool> {
+        self.properties().map(|props| props.contains(core::QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE))
+    }
+
     fn fmt_info(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         f.debug_struct("Queue")
             .field("Context", &self.info(CommandQueueInfo::Context))
@@ -130,4 +472,106 @@ unsafe impl<'a> ClContextPtr for &'a Queue {
         self.context_ptr().expect("<&Queue as ClContextPtr>::as_ptr: \
             Unable to obtain a context pointer.")
     }
-}
\ No newline at end of file
+}
+
+/// A command queue builder.
+///
+/// Uses `clCreateCommandQueueWithProperties` when the `opencl_version_2_0`
+/// feature is enabled and the device supports it, falling back to
+/// `clCreateCommandQueue` otherwise.
+///
+/// ## Defaults
+///
+/// - `device`: The first device associated with the context
+/// - `properties`: None (in-order, profiling disabled)
+///
+#[must_use = "builders do nothing unless '::build' is called"]
+#[derive(Debug)]
+pub struct QueueBuilder<'a> {
+    context: Option<&'a Context>,
+    device: Option<Device>,
+    properties: Option<CommandQueueProperties>,
+    on_device_size: Option<u32>,
+}
+
+impl<'a> QueueBuilder<'a> {
+    /// Returns a new queue builder.
+    pub fn new() -> QueueBuilder<'a> {
+        QueueBuilder {
+            context: None,
+            device: None,
+            properties: None,
+            on_device_size: None,
+        }
+    }
+
+    /// Sets the context with which to associate the queue. Required.
+    pub fn context<'o>(mut self, context: &'o Context) -> QueueBuilder<'a>
+            where 'o: 'a {
+        self.context = Some(context);
+        self
+    }
+
+    /// Sets the device on which commands will be executed.
+    pub fn device(mut self, device: Device) -> QueueBuilder<'a> {
+        self.device = Some(device);
+        self
+    }
+
+    /// Sets the queue properties (out-of-order execution, profiling, etc.).
+    pub fn properties(mut self, properties: CommandQueueProperties) -> QueueBuilder<'a> {
+        self.properties = Some(properties);
+        self
+    }
+
+    /// Creates an on-device queue (used for device-side enqueue) with a size
+    /// of `queue_size` bytes.
+    ///
+    /// `ON_DEVICE` and `OUT_OF_ORDER_EXEC_MODE_ENABLE` are added to any other
+    /// properties specified. Requires OpenCL 2.0+ and the
+    /// `opencl_version_2_0` feature.
+    pub fn on_device(mut self, queue_size: u32) -> QueueBuilder<'a> {
+        self.on_device_size = Some(queue_size);
+        self
+    }
+
+    /// Builds and returns a new `Queue`.
+    pub fn build(self) -> OclResult<Queue> {
+        let context = self.context.ok_or_else(|| OclError::from("ocl::QueueBuilder::build: \
+            A context must be set with '.context(...)'."))?;
+
+        let device = match self.device {
+            Some(d) => d,
+            None => context.devices().first().cloned().ok_or_else(|| OclError::from(
+                "ocl::QueueBuilder::build: The context has no associated devices."))?,
+        };
+
+        let device_version = device.version()?;
+
+        let properties = match self.on_device_size {
+            Some(_) => Some(self.properties.unwrap_or(CommandQueueProperties::empty())
+                | CommandQueueProperties::ON_DEVICE
+                | CommandQueueProperties::OUT_OF_ORDER_EXEC_MODE_ENABLE),
+            None => self.properties,
+        };
+
+        #[cfg(feature = "opencl_version_2_0")]
+        let obj_core = core::create_command_queue_with_properties(context, &device, properties,
+            self.on_device_size, Some(&device_version))?;
+
+        #[cfg(not(feature = "opencl_version_2_0"))]
+        let obj_core = {
+            if self.on_device_size.is_some() {
+                return Err("ocl::QueueBuilder::build: On-device queues require the \
+                    'opencl_version_2_0' feature.".into());
+            }
+            core::create_command_queue(context, &device, properties)?
+        };
+
+        Ok(Queue {
+            obj_core: obj_core,
+            device_version: device_version,
+            retry_policy: None,
+        })
+    }
+}
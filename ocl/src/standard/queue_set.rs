@@ -0,0 +1,138 @@
+//! A set of queues, one per device, for spreading work across devices.
+
+use core::CommandQueueProperties;
+use error::{Result as OclResult};
+use standard::{Context, Queue, Event, EventList};
+
+
+/// The policy used by `QueueSet::next` to select a queue.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QueueSetPolicy {
+    /// Cycle through each queue in turn.
+    RoundRobin,
+    /// Select the queue with the fewest outstanding (incomplete) tracked
+    /// events, cycling through queues with equal counts.
+    LeastBusy,
+}
+
+
+/// A set of queues, typically one for each device in a context, used to
+/// distribute independent commands (e.g. kernel dispatches) across devices.
+///
+/// Events of commands enqueued on a queue returned by `::next` can be
+/// registered with `::track_event`. The number of tracked events which have
+/// not yet completed is used by the `QueueSetPolicy::LeastBusy` policy.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// let mut queues = QueueSet::new(&context, None)?.with_policy(QueueSetPolicy::LeastBusy);
+///
+/// for kernel in kernels.iter() {
+///     let mut event = Event::empty();
+///     unsafe { kernel.cmd().queue(queues.next()?).enew(&mut event).enq()?; }
+///     queues.track_event(event)?;
+/// }
+///
+/// queues.finish()?;
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct QueueSet {
+    queues: Vec<Queue>,
+    outstanding: Vec<EventList>,
+    policy: QueueSetPolicy,
+    next_idx: usize,
+}
+
+impl QueueSet {
+    /// Returns a new set containing one queue (created with `properties`)
+    /// for each device in `context`, using the round-robin policy.
+    pub fn new(context: &Context, properties: Option<CommandQueueProperties>)
+            -> OclResult<QueueSet> {
+        let queues = context.devices().into_iter()
+            .map(|device| Queue::new(context, device, properties))
+            .collect::<OclResult<Vec<_>>>()?;
+        QueueSet::from_queues(queues)
+    }
+
+    /// Returns a new set containing `queues`, using the round-robin policy.
+    ///
+    /// Queues may belong to different contexts.
+    pub fn from_queues(queues: Vec<Queue>) -> OclResult<QueueSet> {
+        if queues.is_empty() {
+            return Err("QueueSet::from_queues: At least one queue is required.".into());
+        }
+
+        Ok(QueueSet {
+            outstanding: queues.iter().map(|_| EventList::new()).collect(),
+            queues: queues,
+            policy: QueueSetPolicy::RoundRobin,
+            next_idx: 0,
+        })
+    }
+
+    /// Sets the policy used to select queues.
+    pub fn with_policy(mut self, policy: QueueSetPolicy) -> QueueSet {
+        self.policy = policy;
+        self
+    }
+
+    /// Returns the policy used to select queues.
+    pub fn policy(&self) -> QueueSetPolicy {
+        self.policy
+    }
+
+    /// Returns the queue to use for the next command according to the
+    /// policy of this set.
+    pub fn next(&mut self) -> OclResult<&Queue> {
+        let idx = match self.policy {
+            QueueSetPolicy::RoundRobin => self.next_idx,
+            QueueSetPolicy::LeastBusy => {
+                let counts = self.outstanding()?;
+                let len = self.queues.len();
+                // Starting from `next_idx` breaks ties in round-robin order:
+                (0..len).map(|i| (self.next_idx + i) % len)
+                    .min_by_key(|&idx| counts[idx])
+                    .expect("QueueSet::next: Empty set.")
+            },
+        };
+
+        self.next_idx = (idx + 1) % self.queues.len();
+        Ok(&self.queues[idx])
+    }
+
+    /// Registers the event of a command enqueued on one of the queues in
+    /// this set. The event counts towards its queue's outstanding events
+    /// until it completes.
+    pub fn track_event(&mut self, event: Event) -> OclResult<()> {
+        let queue_core = event.queue_core()?;
+        let idx = self.queues.iter()
+            .position(|q| q.as_core().as_ptr() == queue_core.as_ptr())
+            .ok_or("QueueSet::track_event: The event's command queue is not in this set.")?;
+        self.outstanding[idx].push(event);
+        Ok(())
+    }
+
+    /// Returns the number of tracked events which have not yet completed for
+    /// each queue, in order. Completed events are no longer tracked.
+    pub fn outstanding(&mut self) -> OclResult<Vec<usize>> {
+        self.outstanding.iter_mut()
+            .map(|events| events.clear_completed().map(|_| events.len()))
+            .collect()
+    }
+
+    /// Blocks until all commands in every queue have completed.
+    pub fn finish(&mut self) -> OclResult<()> {
+        for (queue, events) in self.queues.iter().zip(self.outstanding.iter_mut()) {
+            queue.finish()?;
+            events.clear();
+        }
+        Ok(())
+    }
+
+    /// Returns the queues in this set.
+    pub fn queues(&self) -> &[Queue] {
+        &self.queues
+    }
+}
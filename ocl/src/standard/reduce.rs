@@ -0,0 +1,317 @@
+//! Buffer reductions using generated tree-reduction kernels.
+
+use std;
+use std::any::TypeId;
+use std::cell::RefCell;
+use core::{self, OclPrm, DeviceInfo, DeviceInfoResult, KernelWorkGroupInfo,
+    KernelWorkGroupInfoResult};
+use error::{Result as OclResult};
+use standard::{Buffer, Queue, Program, Kernel, Context, Device, Event};
+
+
+/// A reduction operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReduceOp {
+    Sum,
+    Min,
+    Max,
+    Product,
+}
+
+impl ReduceOp {
+    /// Returns the OpenCL C expression combining `a` and `b`.
+    fn expr(&self) -> &'static str {
+        match *self {
+            ReduceOp::Sum => "((a) + (b))",
+            ReduceOp::Min => "min((a), (b))",
+            ReduceOp::Max => "max((a), (b))",
+            ReduceOp::Product => "((a) * (b))",
+        }
+    }
+
+    /// Drops every reduction program cached on the current thread (see
+    /// `Buffer::reduce`), releasing the contexts they retain.
+    pub fn clear_programs() {
+        REDUCE_PROGRAMS.with(|cache| cache.borrow_mut().clear());
+    }
+
+    /// Drops the reduction programs cached on the current thread for
+    /// `context`, releasing the references they hold to it.
+    pub fn evict_programs(context: &Context) {
+        REDUCE_PROGRAMS.with(|cache| {
+            cache.borrow_mut().retain(|entry| entry.context.as_ptr() != context.as_ptr())
+        });
+    }
+}
+
+
+/// The OpenCL C name and identity values of a scalar type.
+struct ClScalar {
+    name: &'static str,
+    min: &'static str,
+    max: &'static str,
+    is_double: bool,
+}
+
+impl ClScalar {
+    /// Returns the scalar description for `T` or `None` if `T` is not a
+    /// supported (real, scalar) type.
+    fn of<T: OclPrm>() -> Option<ClScalar> {
+        let id = TypeId::of::<T>();
+        let int_64 = std::mem::size_of::<usize>() == 8;
+
+        let (name, min, max) = if id == TypeId::of::<i8>() {
+            ("char", "CHAR_MIN", "CHAR_MAX")
+        } else if id == TypeId::of::<u8>() {
+            ("uchar", "0", "UCHAR_MAX")
+        } else if id == TypeId::of::<i16>() {
+            ("short", "SHRT_MIN", "SHRT_MAX")
+        } else if id == TypeId::of::<u16>() {
+            ("ushort", "0", "USHRT_MAX")
+        } else if id == TypeId::of::<i32>() || (id == TypeId::of::<isize>() && !int_64) {
+            ("int", "INT_MIN", "INT_MAX")
+        } else if id == TypeId::of::<u32>() || (id == TypeId::of::<usize>() && !int_64) {
+            ("uint", "0", "UINT_MAX")
+        } else if id == TypeId::of::<i64>() || id == TypeId::of::<isize>() {
+            ("long", "LONG_MIN", "LONG_MAX")
+        } else if id == TypeId::of::<u64>() || id == TypeId::of::<usize>() {
+            ("ulong", "0", "ULONG_MAX")
+        } else if id == TypeId::of::<f32>() {
+            ("float", "-INFINITY", "INFINITY")
+        } else if id == TypeId::of::<f64>() {
+            ("double", "-INFINITY", "INFINITY")
+        } else {
+            return None;
+        };
+
+        Some(ClScalar { name, min, max, is_double: name == "double" })
+    }
+
+    /// Returns the identity element of `op` for this type.
+    fn identity(&self, op: ReduceOp) -> &'static str {
+        match op {
+            ReduceOp::Sum => "0",
+            ReduceOp::Product => "1",
+            ReduceOp::Min => self.max,
+            ReduceOp::Max => self.min,
+        }
+    }
+}
+
+
+/// Returns the source of a kernel which reduces `len` elements of `src`
+/// into one element per work group, written to `dst[group_id]`.
+///
+/// Each work item first folds a grid-strided subset of `src` (padding with
+/// the identity element beyond `len`) after which the work group performs a
+/// tree reduction within local memory. The local work size must be a power
+/// of two.
+fn reduce_src(scalar: &ClScalar, op: ReduceOp) -> String {
+    format!(r#"
+        {pragma}
+        #define T {ty}
+        #define OP(a, b) {expr}
+
+        __kernel void reduce(__global const T* src, __global T* dst, ulong len,
+                __local T* scratch)
+        {{
+            size_t lid = get_local_id(0);
+            T acc = {identity};
+
+            for (size_t i = get_global_id(0); i < len; i += get_global_size(0)) {{
+                acc = OP(acc, src[i]);
+            }}
+
+            scratch[lid] = acc;
+            barrier(CLK_LOCAL_MEM_FENCE);
+
+            for (size_t s = get_local_size(0) / 2; s > 0; s >>= 1) {{
+                if (lid < s) {{
+                    scratch[lid] = OP(scratch[lid], scratch[lid + s]);
+                }}
+                barrier(CLK_LOCAL_MEM_FENCE);
+            }}
+
+            if (lid == 0) {{
+                dst[get_group_id(0)] = scratch[0];
+            }}
+        }}
+    "#,
+        pragma = if scalar.is_double { "#pragma OPENCL EXTENSION cl_khr_fp64 : enable" } else { "" },
+        ty = scalar.name,
+        expr = op.expr(),
+        identity = scalar.identity(op))
+}
+
+
+/// The maximum number of reduction programs cached per thread. The least
+/// recently used program is dropped when exceeded.
+const REDUCE_PROGRAMS_MAX: usize = 32;
+
+/// A compiled reduction program along with the context, device, element
+/// type, and operation it was built for, and the work group size to use.
+///
+/// The context is retained for the lifetime of the entry, so its pointer
+/// cannot be reused by another context while the entry exists.
+struct ReduceCacheEntry {
+    context: Context,
+    device: Device,
+    ty: TypeId,
+    op: ReduceOp,
+    program: Program,
+    lws: usize,
+}
+
+thread_local! {
+    // Ordered from least to most recently used.
+    static REDUCE_PROGRAMS: RefCell<Vec<ReduceCacheEntry>> = RefCell::new(Vec::new());
+}
+
+
+/// Returns true if `device` supports double precision floating point.
+fn supports_fp64(device: Device) -> OclResult<bool> {
+    match device.info(DeviceInfo::Extensions)? {
+        DeviceInfoResult::Extensions(exts) => {
+            Ok(exts.split_whitespace().any(|ext| ext == "cl_khr_fp64"))
+        },
+        _ => unreachable!(),
+    }
+}
+
+
+/// Returns the work group size for the reduction kernel within `program`:
+/// the largest power of two (required by the tree reduction) within the
+/// limits of the kernel and device, up to 256.
+fn reduce_lws(program: &Program, device: Device) -> OclResult<usize> {
+    let kernel = core::create_kernel(program, "reduce")?;
+    let kernel_max = match core::get_kernel_work_group_info(&kernel, device,
+            KernelWorkGroupInfo::WorkGroupSize)? {
+        KernelWorkGroupInfoResult::WorkGroupSize(s) => s,
+        r => return Err(format!("Buffer::reduce: Work group size unavailable: {}", r).into()),
+    };
+    let item_max = match device.info(DeviceInfo::MaxWorkItemSizes)? {
+        DeviceInfoResult::MaxWorkItemSizes(s) => s[0],
+        _ => unreachable!(),
+    };
+
+    let max_wg_size = std::cmp::min(std::cmp::min(kernel_max, item_max), 256);
+    let mut lws = 1;
+    while lws * 2 <= max_wg_size { lws *= 2; }
+    Ok(lws)
+}
+
+
+/// Returns a cached reduction program for `T` and `op`, along with its work
+/// group size, or builds one.
+fn reduce_program<T: OclPrm>(queue: &Queue, op: ReduceOp) -> OclResult<(Program, usize)> {
+    let scalar = match ClScalar::of::<T>() {
+        Some(s) => s,
+        None => return Err("Buffer::reduce: Unsupported element type. Only real scalar types \
+            (integers, 'f32', and 'f64') can be reduced.".into()),
+    };
+
+    let context = queue.context();
+    let device = queue.device();
+    let ty = TypeId::of::<T>();
+
+    let cached = REDUCE_PROGRAMS.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let pos = cache.iter().position(|e| e.context.as_ptr() == context.as_ptr() &&
+            e.device == device && e.ty == ty && e.op == op);
+        pos.map(|pos| {
+            let entry = cache.remove(pos);
+            let cached = (entry.program.clone(), entry.lws);
+            cache.push(entry);
+            cached
+        })
+    });
+
+    if let Some(cached) = cached {
+        return Ok(cached);
+    }
+
+    if scalar.is_double && !supports_fp64(device)? {
+        return Err(format!("Buffer::reduce: Reducing 'f64' requires a device supporting \
+            'cl_khr_fp64' (device: '{}').", device.name()?).into());
+    }
+
+    let program = Program::builder()
+        .src(reduce_src(&scalar, op))
+        .devices(device)
+        .build(&context)?;
+    let lws = reduce_lws(&program, device)?;
+
+    REDUCE_PROGRAMS.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if cache.len() >= REDUCE_PROGRAMS_MAX {
+            cache.remove(0);
+        }
+        cache.push(ReduceCacheEntry { context: context, device: device, ty: ty, op: op,
+            program: program.clone(), lws: lws });
+    });
+    Ok((program, lws))
+}
+
+
+/// Builds a reduction kernel instance from `program`.
+fn reduce_kernel<'b, T: OclPrm>(program: &'b Program, queue: &Queue, src: &'b Buffer<T>,
+        dst: &'b Buffer<T>, len: usize, lws: usize) -> OclResult<Kernel> {
+    Kernel::builder()
+        .program(program)
+        .name("reduce")
+        .queue(queue.clone())
+        .arg(src)
+        .arg(dst)
+        .arg(len as u64)
+        .arg_local::<T>(lws)
+        .build()
+}
+
+
+/// Reduces the contents of `buffer` to a single value using `op`.
+///
+/// Runs in two passes: the first reduces the buffer to (at most) one value
+/// per work item of a single work group and the second reduces those
+/// partial results using that single work group.
+pub fn reduce_buffer<T: OclPrm>(buffer: &Buffer<T>, queue: Option<&Queue>, op: ReduceOp)
+        -> OclResult<T> {
+    let queue = match queue.or(buffer.default_queue()) {
+        Some(q) => q.clone(),
+        None => return Err("Buffer::reduce: No queue specified.".into()),
+    };
+
+    let (program, lws) = reduce_program::<T>(&queue, op)?;
+    let len = buffer.len();
+
+    let group_count = std::cmp::max(1, std::cmp::min((len + lws - 1) / lws, lws));
+
+    let partials = Buffer::<T>::builder()
+        .queue(queue.clone())
+        .len(group_count)
+        .build()?;
+
+    let result = Buffer::<T>::builder()
+        .queue(queue.clone())
+        .len(1)
+        .build()?;
+
+    let pass_0 = reduce_kernel(&program, &queue, buffer, &partials, len, lws)?;
+    let pass_1 = reduce_kernel(&program, &queue, &partials, &result, group_count, lws)?;
+
+    // Each command waits on the previous one in case `queue` is
+    // out-of-order. The work group size is known to be within the kernel and
+    // device limits:
+    let mut pass_0_event = Event::empty();
+    let mut pass_1_event = Event::empty();
+
+    unsafe {
+        pass_0.cmd().gws(group_count * lws).lws(lws).unchecked()
+            .enew(&mut pass_0_event).enq()?;
+        pass_1.cmd().gws(lws).lws(lws).unchecked()
+            .ewait(&pass_0_event).enew(&mut pass_1_event).enq()?;
+    }
+
+    let mut out = vec![T::default(); 1];
+    result.read(&mut out).ewait(&pass_1_event).enq()?;
+    Ok(out[0])
+}
@@ -8,11 +8,20 @@ use error::{Error as OclError, Result as OclResult};
 use standard::Context;
 
 /// An image sampler used to process images.
+///
+/// ## Destruction
+///
+/// Handled automatically. Clones retain the underlying sampler.
 #[derive(Clone, Debug)]
 #[repr(C)]
 pub struct Sampler(SamplerCore);
 
 impl Sampler {
+    /// Returns a new `SamplerBuilder`.
+    pub fn builder<'a>() -> SamplerBuilder<'a> {
+        SamplerBuilder::new()
+    }
+
     /// Creates and returns a new sampler.
     ///
     /// ## Enum Quick Reference
@@ -65,6 +74,38 @@ impl Sampler {
         core::get_sampler_info(&self.0, info_kind)
     }
 
+    /// Returns whether or not this sampler uses normalized coordinates.
+    pub fn normalized_coords(&self) -> OclResult<bool> {
+        match self.info(SamplerInfo::NormalizedCoords)? {
+            SamplerInfoResult::NormalizedCoords(nc) => Ok(nc),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the addressing mode of this sampler.
+    pub fn addressing_mode(&self) -> OclResult<AddressingMode> {
+        match self.info(SamplerInfo::AddressingMode)? {
+            SamplerInfoResult::AddressingMode(am) => Ok(am),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the filter mode of this sampler.
+    pub fn filter_mode(&self) -> OclResult<FilterMode> {
+        match self.info(SamplerInfo::FilterMode)? {
+            SamplerInfoResult::FilterMode(fm) => Ok(fm),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns a reference to the core pointer wrapper, usable by functions in
+    /// the `core` module (e.g. with `ArgVal::sampler`).
+    ///
+    #[inline]
+    pub fn as_core(&self) -> &SamplerCore {
+        &self.0
+    }
+
     fn fmt_info(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         f.debug_struct("Sampler")
             .field("ReferenceCount", &self.info(SamplerInfo::ReferenceCount))
@@ -95,3 +136,65 @@ impl DerefMut for Sampler {
         &mut self.0
     }
 }
+
+
+/// A sampler builder.
+///
+/// ## Defaults
+///
+/// - `normalize_coords`: false
+/// - `addressing_mode`: `AddressingMode::None`
+/// - `filter_mode`: `FilterMode::Nearest`
+///
+#[must_use = "builders do nothing unless '::build' is called"]
+#[derive(Debug)]
+pub struct SamplerBuilder<'a> {
+    context: Option<&'a Context>,
+    normalize_coords: bool,
+    addressing_mode: AddressingMode,
+    filter_mode: FilterMode,
+}
+
+impl<'a> SamplerBuilder<'a> {
+    /// Returns a new sampler builder.
+    pub fn new() -> SamplerBuilder<'a> {
+        SamplerBuilder {
+            context: None,
+            normalize_coords: false,
+            addressing_mode: AddressingMode::None,
+            filter_mode: FilterMode::Nearest,
+        }
+    }
+
+    /// Sets the context with which to associate the sampler. Required.
+    pub fn context<'o>(mut self, context: &'o Context) -> SamplerBuilder<'a>
+            where 'o: 'a {
+        self.context = Some(context);
+        self
+    }
+
+    /// Specifies whether or not image coordinates are normalized.
+    pub fn normalized_coords(mut self, normalize_coords: bool) -> SamplerBuilder<'a> {
+        self.normalize_coords = normalize_coords;
+        self
+    }
+
+    /// Specifies how out-of-range image coordinates are handled.
+    pub fn addressing_mode(mut self, addressing_mode: AddressingMode) -> SamplerBuilder<'a> {
+        self.addressing_mode = addressing_mode;
+        self
+    }
+
+    /// Specifies the type of filter applied when reading an image.
+    pub fn filter_mode(mut self, filter_mode: FilterMode) -> SamplerBuilder<'a> {
+        self.filter_mode = filter_mode;
+        self
+    }
+
+    /// Builds and returns a new `Sampler`.
+    pub fn build(self) -> OclResult<Sampler> {
+        let context = self.context.ok_or_else(|| OclError::from("ocl::SamplerBuilder::build: \
+            A context must be set with '.context(...)'."))?;
+        Sampler::new(context, self.normalize_coords, self.addressing_mode, self.filter_mode)
+    }
+}
@@ -0,0 +1,152 @@
+//! Shared virtual memory (OpenCL 2.0+).
+
+use std;
+use core::{self, OclPrm, MemFlags};
+use core::ffi::c_void;
+use error::{Result as OclResult};
+use standard::Context;
+
+
+/// A fixed-length vector allocated in shared virtual memory (SVM), usable by
+/// the host as a slice (see `::as_slice`) and by kernels as a pointer (see
+/// `Kernel::set_arg_svm`).
+///
+/// Create using `Context::svm_alloc`.
+///
+/// ## Host Access
+///
+/// Unless allocated with `MemFlags::SVM_FINE_GRAIN_BUFFER` (and supported by
+/// the device), the contents may only be accessed by the host between a
+/// `Queue::svm_map` and the corresponding `Queue::svm_unmap`, and not while
+/// in use by a kernel. Because this cannot be checked, host access is only
+/// available through the unsafe `::as_slice` and `::as_mut_slice` methods
+/// (or through the raw pointers).
+///
+/// The contents are uninitialized upon creation.
+///
+/// ## Destruction
+///
+/// The allocation is freed when dropped. Ensure that all commands using it
+/// have completed beforehand.
+///
+pub struct SvmVec<T: OclPrm> {
+    ptr: *mut T,
+    len: usize,
+    flags: MemFlags,
+    context: Context,
+}
+
+impl<T: OclPrm> SvmVec<T> {
+    /// Allocates a new SVM vector of `len` elements shared between the host
+    /// and all devices within `context`.
+    ///
+    /// Returns an error if any device within `context` does not support
+    /// OpenCL 2.0, if the size in bytes overflows, or if the allocation
+    /// fails.
+    ///
+    /// ## Safety
+    ///
+    /// The contents are uninitialized. Every element must be written (e.g.
+    /// through `::as_mut_ptr` while mapped, or by a kernel) before being
+    /// read by the host.
+    pub unsafe fn new(context: &Context, len: usize, flags: MemFlags) -> OclResult<SvmVec<T>> {
+        let size = len.checked_mul(std::mem::size_of::<T>())
+            .ok_or("SvmVec::new: Allocation size overflows 'usize'.")?;
+        let ptr = core::svm_alloc(context, flags, size, 0, None)?;
+
+        Ok(SvmVec {
+            ptr: ptr as *mut T,
+            len: len,
+            flags: flags,
+            context: context.clone(),
+        })
+    }
+
+    /// Returns the number of elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if this vector contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the flags used to allocate this vector.
+    #[inline]
+    pub fn flags(&self) -> MemFlags {
+        self.flags
+    }
+
+    /// Returns the context this vector was allocated within.
+    #[inline]
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    /// Returns the contents as a slice.
+    ///
+    /// ## Safety
+    ///
+    /// Unless this vector is fine-grained, it must currently be mapped for
+    /// reading (see `Queue::svm_map`). It must not be written by any kernel
+    /// for the lifetime of the returned slice, and every element must have
+    /// been initialized.
+    #[inline]
+    pub unsafe fn as_slice(&self) -> &[T] {
+        std::slice::from_raw_parts(self.ptr, self.len)
+    }
+
+    /// Returns the contents as a mutable slice.
+    ///
+    /// ## Safety
+    ///
+    /// Unless this vector is fine-grained, it must currently be mapped for
+    /// writing (see `Queue::svm_map`). It must not be accessed by any kernel
+    /// for the lifetime of the returned slice, and every element must have
+    /// been initialized.
+    #[inline]
+    pub unsafe fn as_mut_slice(&mut self) -> &mut [T] {
+        std::slice::from_raw_parts_mut(self.ptr, self.len)
+    }
+
+    /// Returns a pointer to the first element.
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr
+    }
+
+    /// Returns a mutable pointer to the first element.
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.ptr
+    }
+
+    /// Returns the allocation pointer, as passed to the SVM functions within
+    /// the `core` module.
+    #[inline]
+    pub fn as_void_ptr(&self) -> *mut c_void {
+        self.ptr as *mut c_void
+    }
+}
+
+impl<T: OclPrm> std::fmt::Debug for SvmVec<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("SvmVec")
+            .field("ptr", &self.ptr)
+            .field("len", &self.len)
+            .field("flags", &self.flags)
+            .finish()
+    }
+}
+
+impl<T: OclPrm> Drop for SvmVec<T> {
+    fn drop(&mut self) {
+        unsafe { core::svm_free(&self.context, self.as_void_ptr()); }
+    }
+}
+
+unsafe impl<T: OclPrm> Send for SvmVec<T> {}
+unsafe impl<T: OclPrm> Sync for SvmVec<T> {}
@@ -0,0 +1,167 @@
+//! A lightweight command dependency graph.
+
+use error::{Result as OclResult};
+use standard::{Event, EventList};
+
+
+/// A task graph related error.
+#[derive(Debug, Fail)]
+pub enum TaskGraphError {
+    #[fail(display = "Invalid task graph node id: {} (node count: {}).", node, node_count)]
+    InvalidNode { node: usize, node_count: usize },
+}
+
+
+/// A node within a `TaskGraph`.
+#[derive(Debug, Clone)]
+struct TaskNode {
+    predecessors: Vec<usize>,
+    event: Option<Event>,
+}
+
+
+/// A dependency graph of commands which produces the wait list for each
+/// command at enqueue time.
+///
+/// Each node represents a command (or sequence of commands) and stores the
+/// event of its most recent enqueue. The wait list for a node contains the
+/// most recent event of each of its predecessors.
+///
+/// ### Cycles
+///
+/// Cyclical tasks are expressed by adding a predecessor which is enqueued
+/// after the node itself within each iteration (using `::add_predecessor`).
+/// Such a predecessor contributes its event from the **previous** iteration
+/// and is ignored until it has been enqueued for the first time.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// let mut graph = TaskGraph::new();
+/// let write = graph.add_node(&[])?;
+/// let kernel = graph.add_node(&[write])?;
+/// let read = graph.add_node(&[kernel])?;
+///
+/// // The write must not overwrite data still in use by the previous
+/// // iteration's kernel and the kernel must not overwrite results still
+/// // being read by the previous iteration's read:
+/// graph.add_predecessor(write, kernel)?;
+/// graph.add_predecessor(kernel, read)?;
+///
+/// for _ in 0..iters {
+///     graph.enq(write, |ewait, enew| buffer.write(&data).ewait(ewait).enew(enew).enq())?;
+///     graph.enq(kernel, |ewait, enew| unsafe { kernel.cmd().ewait(ewait).enew(enew).enq() })?;
+///     graph.enq(read, |ewait, enew| buffer.read(&mut data).ewait(ewait).enew(enew).enq())?;
+/// }
+/// ```
+///
+#[derive(Debug, Clone, Default)]
+pub struct TaskGraph {
+    nodes: Vec<TaskNode>,
+}
+
+impl TaskGraph {
+    /// Returns a new, empty graph.
+    pub fn new() -> TaskGraph {
+        TaskGraph { nodes: Vec::new() }
+    }
+
+    /// Adds a node which depends on each of `predecessors`, all of which
+    /// must already exist, and returns its id.
+    pub fn add_node(&mut self, predecessors: &[usize]) -> OclResult<usize> {
+        for &pred in predecessors {
+            self.verify_node(pred)?;
+        }
+
+        self.nodes.push(TaskNode { predecessors: predecessors.to_vec(), event: None });
+        Ok(self.nodes.len() - 1)
+    }
+
+    /// Adds `predecessor` to the list of nodes which `node` depends on.
+    ///
+    /// Use this to add dependencies on nodes which are enqueued later within
+    /// each iteration, such as when a command depends on the previous
+    /// iteration's event.
+    pub fn add_predecessor(&mut self, node: usize, predecessor: usize) -> OclResult<()> {
+        self.verify_node(predecessor)?;
+        self.verify_node(node)?;
+
+        let preds = &mut self.nodes[node].predecessors;
+        if !preds.contains(&predecessor) { preds.push(predecessor); }
+        Ok(())
+    }
+
+    /// Returns the ids of the nodes which `node` depends on.
+    pub fn predecessors(&self, node: usize) -> OclResult<&[usize]> {
+        self.verify_node(node)?;
+        Ok(&self.nodes[node].predecessors)
+    }
+
+    /// Returns a list containing the most recent event of each predecessor
+    /// of `node` which has been enqueued at least once.
+    pub fn wait_list(&self, node: usize) -> OclResult<EventList> {
+        self.verify_node(node)?;
+
+        Ok(self.nodes[node].predecessors.iter()
+            .filter_map(|&pred| self.nodes[pred].event.clone())
+            .collect())
+    }
+
+    /// Sets the most recent event for `node`.
+    pub fn set_event(&mut self, node: usize, event: Event) -> OclResult<()> {
+        self.verify_node(node)?;
+        self.nodes[node].event = Some(event);
+        Ok(())
+    }
+
+    /// Returns the most recent event for `node` if it has been enqueued.
+    pub fn event(&self, node: usize) -> OclResult<Option<&Event>> {
+        self.verify_node(node)?;
+        Ok(self.nodes[node].event.as_ref())
+    }
+
+    /// Enqueues the command(s) for `node` using `enq`, which is passed the
+    /// wait list for `node` and a new, empty event to be used with
+    /// `.ewait(..)` and `.enew(..)` respectively, then stores that event.
+    pub fn enq<F>(&mut self, node: usize, enq: F) -> OclResult<()>
+            where F: FnOnce(&EventList, &mut Event) -> OclResult<()> {
+        let wait_list = self.wait_list(node)?;
+        let mut event = Event::empty();
+        enq(&wait_list, &mut event)?;
+        self.set_event(node, event)
+    }
+
+    /// Waits for the most recent event of every node to complete.
+    pub fn wait(&self) -> OclResult<()> {
+        for node in self.nodes.iter() {
+            if let Some(ref event) = node.event { event.wait()?; }
+        }
+        Ok(())
+    }
+
+    /// Clears the most recent event of every node, as if none had ever been
+    /// enqueued.
+    pub fn clear_events(&mut self) {
+        for node in self.nodes.iter_mut() {
+            node.event = None;
+        }
+    }
+
+    /// Returns the number of nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns true if this graph contains no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn verify_node(&self, node: usize) -> OclResult<()> {
+        if node < self.nodes.len() {
+            Ok(())
+        } else {
+            Err(TaskGraphError::InvalidNode { node, node_count: self.nodes.len() }.into())
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! Asynchronous (non-blocking) buffer reads and writes.
+
+extern crate futures_cpupool;
+
+use futures::Future;
+use self::futures_cpupool::CpuPool;
+use standard::{ProQue, Buffer};
+use async::RwVec;
+use error::{Error as OclError};
+
+const LEN: usize = 1 << 10;
+const FILL_VAL: i32 = 37;
+
+
+/// Reads a filled buffer into an owned `Vec` using a future resolved on a
+/// thread pool.
+#[test]
+fn buffer_read_async() {
+    let proque = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims(LEN)
+        .build().unwrap();
+
+    let buffer = Buffer::<i32>::builder()
+        .queue(proque.queue().clone())
+        .len(LEN)
+        .fill_val(FILL_VAL)
+        .build().unwrap();
+
+    let future_read = buffer.cmd().read(vec![0i32; LEN]).enq_async().unwrap();
+
+    let pool = CpuPool::new(1);
+    let data = pool.spawn(future_read.and_then(|guard| Ok::<_, OclError>(guard.to_vec())))
+        .wait().unwrap();
+
+    assert_eq!(data.len(), LEN);
+    assert!(data.iter().all(|&v| v == FILL_VAL));
+}
+
+
+/// Writes an owned `Vec` asynchronously then verifies the contents with a
+/// subsequent blocking read.
+#[test]
+fn buffer_write_async() {
+    let proque = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims(LEN)
+        .build().unwrap();
+
+    let buffer = Buffer::<i32>::builder()
+        .queue(proque.queue().clone())
+        .len(LEN)
+        .fill_val(0i32)
+        .build().unwrap();
+
+    let src: Vec<i32> = (0..LEN as i32).collect();
+
+    let future_write = buffer.cmd().write(src.clone()).enq_async().unwrap();
+    future_write.wait().unwrap();
+
+    let mut data = vec![0i32; LEN];
+    buffer.read(&mut data).enq().unwrap();
+    assert_eq!(data, src);
+}
+
+
+/// Fills an `RwVec` under a blocking write lock, uploads it, reads it back
+/// into a second `RwVec` and checks the result under a blocking read lock.
+#[test]
+fn rw_vec_sync_locks() {
+    let proque = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims(LEN)
+        .build().unwrap();
+
+    let buffer = Buffer::<i32>::builder()
+        .queue(proque.queue().clone())
+        .len(LEN)
+        .fill_val(0i32)
+        .build().unwrap();
+
+    let src_vec = RwVec::from(vec![0i32; LEN]);
+    {
+        let mut guard = src_vec.write_lock().unwrap();
+        for (i, val) in guard.as_mut_slice().iter_mut().enumerate() {
+            *val = i as i32;
+        }
+    }
+
+    buffer.write(&src_vec).enq_async().unwrap().wait().unwrap();
+
+    let dst_vec = RwVec::from(vec![0i32; LEN]);
+    buffer.read(&dst_vec).enq_async().unwrap().wait().unwrap();
+
+    let guard = dst_vec.read_lock().unwrap();
+    assert!(guard.as_slice().iter().enumerate().all(|(i, &v)| v == i as i32));
+}
@@ -0,0 +1,43 @@
+//! Chunked buffer downloads.
+
+use standard::ProQue;
+
+const DATASET_SIZE: usize = 1 << 20;
+const CHUNK_LEN: usize = 1 << 16;
+
+
+/// Downloads a buffer in chunks and reassembles it.
+#[test]
+fn read_chunks_reassemble() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void iota(__global int* buffer) { \
+            buffer[get_global_id(0)] = get_global_id(0); }")
+        .dims(DATASET_SIZE)
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<i32>().unwrap();
+    let kernel = pro_que.kernel_builder("iota").arg(&buffer).build().unwrap();
+    unsafe { kernel.enq().unwrap(); }
+
+    let mut vec = Vec::with_capacity(DATASET_SIZE);
+    let mut chunk_count = 0;
+
+    for chunk in buffer.read_chunks(None, CHUNK_LEN) {
+        let chunk = chunk.unwrap();
+        assert_eq!(chunk.len(), CHUNK_LEN);
+        vec.extend_from_slice(&chunk);
+        chunk_count += 1;
+    }
+
+    assert_eq!(chunk_count, DATASET_SIZE / CHUNK_LEN);
+    assert_eq!(vec.len(), DATASET_SIZE);
+    for (i, &val) in vec.iter().enumerate() {
+        assert_eq!(val, i as i32);
+    }
+
+    // An uneven final chunk and early termination:
+    let lens: Vec<usize> = buffer.read_chunks(Some(pro_que.queue()), 300_000)
+        .map(|c| c.unwrap().len()).collect();
+    assert_eq!(lens, vec![300_000, 300_000, 300_000, DATASET_SIZE - 900_000]);
+    assert_eq!(buffer.read_chunks(None, CHUNK_LEN).take(1).count(), 1);
+}
@@ -123,3 +123,42 @@ fn buffer_copy_standard() {
         }
     }
 }
+
+#[test]
+fn buffer_copy_back_half_to_front_half() {
+    use standard::{ProQue, Buffer};
+
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims(1024)
+        .build().unwrap();
+
+    let len = pro_que.dims().to_len();
+    let half = len / 2;
+
+    let src_data: Vec<f32> = (0..len).map(|i| i as f32).collect();
+    let src_buffer = Buffer::<f32>::builder()
+        .queue(pro_que.queue().clone())
+        .len(len)
+        .copy_host_slice(&src_data)
+        .build().unwrap();
+
+    let dst_buffer = Buffer::<f32>::builder()
+        .queue(pro_que.queue().clone())
+        .len(len)
+        .fill_val(-1.0f32)
+        .build().unwrap();
+
+    src_buffer.cmd().copy(&dst_buffer, Some(0), Some(half)).offset(half).enq().unwrap();
+
+    let mut dst_vec = vec![0.0f32; len];
+    dst_buffer.read(&mut dst_vec).enq().unwrap();
+
+    for i in 0..len {
+        if i < half {
+            assert_eq!(dst_vec[i], (i + half) as f32, "idx: {}", i);
+        } else {
+            assert_eq!(dst_vec[i], -1.0f32, "idx: {}", i);
+        }
+    }
+}
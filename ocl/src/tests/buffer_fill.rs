@@ -87,3 +87,113 @@ fn fill_with_float4() {
         assert_eq!(ele, final_val);
     }
 }
+
+/// Fills the first half of a buffer after waiting on a kernel which writes to
+/// the entire buffer.
+#[test]
+fn fill_with_wait_list() {
+    use standard::Event;
+
+    let src = r#"
+        __kernel void set(__global float* buffer, float val) {
+            buffer[get_global_id(0)] = val;
+        }
+    "#;
+
+    let pro_que = ProQue::builder()
+        .src(src)
+        .dims(DATASET_SIZE)
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let kernel = pro_que.kernel_builder("set")
+        .arg(&buffer)
+        .arg(10.0f32)
+        .build().unwrap();
+
+    let mut kernel_event = Event::empty();
+    unsafe { kernel.cmd().enew(&mut kernel_event).enq().unwrap(); }
+
+    let mut fill_event = Event::empty();
+    buffer.cmd().fill(5.0f32, Some(DATASET_SIZE / 2))
+        .ewait(&kernel_event)
+        .enew(&mut fill_event)
+        .enq().unwrap();
+
+    let mut vec = vec![0.0f32; buffer.len()];
+    buffer.read(&mut vec).ewait(&fill_event).enq().unwrap();
+
+    assert!(kernel_event.is_complete().unwrap());
+    assert!(vec[..DATASET_SIZE / 2].iter().all(|&ele| ele == 5.0f32));
+    assert!(vec[DATASET_SIZE / 2..].iter().all(|&ele| ele == 10.0f32));
+}
+
+#[test]
+fn write_offset_second_half() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims(1024)
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    let len = buffer.len();
+    let half = len / 2;
+
+    buffer.cmd().fill(-1.0f32, None).enq().unwrap();
+
+    // Write only the second half of a full-length source:
+    let src: Vec<f32> = (0..len).map(|i| i as f32).collect();
+    buffer.write(&src).src_offset(half).len(half).offset(half).enq().unwrap();
+
+    let mut vec = vec![0.0f32; len];
+    buffer.read(&mut vec).enq().unwrap();
+
+    for (i, &ele) in vec.iter().enumerate() {
+        if i < half {
+            assert_eq!(ele, -1.0f32, "idx: {}", i);
+        } else {
+            assert_eq!(ele, i as f32, "idx: {}", i);
+        }
+    }
+
+    // Read back just the second half:
+    let mut back_half = vec![0.0f32; half];
+    buffer.read(&mut back_half).offset(half).enq().unwrap();
+    assert_eq!(&back_half[..], &src[half..]);
+
+    // `offset + len` exceeding the buffer length must error:
+    assert!(buffer.write(&src).len(half + 1).offset(half).enq().is_err());
+    assert!(buffer.read(&mut vec).len(half + 1).offset(half).enq().is_err());
+}
+
+#[test]
+fn zeroed() {
+    use standard::Buffer;
+    use prm::Int4;
+
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims(DATASET_SIZE)
+        .build().unwrap();
+
+    let buffer = Buffer::<f32>::builder()
+        .queue(pro_que.queue().clone())
+        .len(DATASET_SIZE)
+        .zeroed()
+        .build().unwrap();
+
+    let mut vec = vec![1.0f32; DATASET_SIZE];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 0.0));
+
+    let buffer = Buffer::<Int4>::builder()
+        .queue(pro_que.queue().clone())
+        .len(DATASET_SIZE)
+        .zeroed()
+        .build().unwrap();
+
+    let mut vec = vec![Int4::new(1, 1, 1, 1); DATASET_SIZE];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == Int4::default()));
+}
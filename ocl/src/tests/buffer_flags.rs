@@ -0,0 +1,143 @@
+//! Tests flag and allocation size validation in `BufferBuilder::build`.
+
+use flags::MemFlags;
+use standard::{ProQue, Buffer};
+
+#[test]
+fn mem_flags_shortcuts() {
+    assert_eq!(MemFlags::read_write_alloc(), MemFlags::READ_WRITE | MemFlags::ALLOC_HOST_PTR);
+    assert_eq!(MemFlags::read_only_alloc(), MemFlags::READ_ONLY | MemFlags::ALLOC_HOST_PTR);
+    assert_eq!(MemFlags::write_only_alloc(), MemFlags::WRITE_ONLY | MemFlags::ALLOC_HOST_PTR);
+    assert_eq!(MemFlags::read_only_host_write(), MemFlags::READ_ONLY | MemFlags::HOST_WRITE_ONLY);
+    assert_eq!(MemFlags::write_only_host_read(), MemFlags::WRITE_ONLY | MemFlags::HOST_READ_ONLY);
+
+    assert!(MemFlags::read_write_alloc().verify().is_ok());
+    assert!(MemFlags::read_only_alloc().verify().is_ok());
+    assert!(MemFlags::write_only_alloc().verify().is_ok());
+    assert!(MemFlags::read_only_host_write().verify().is_ok());
+    assert!(MemFlags::write_only_host_read().verify().is_ok());
+    assert!(MemFlags::new().read_only().copy_host_ptr().alloc_host_ptr().verify().is_ok());
+}
+
+#[test]
+fn buffer_build_rejects_contradictory_flags() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims(64)
+        .build().unwrap();
+
+    let contradictory = [
+        MemFlags::new().read_only().write_only(),
+        MemFlags::new().read_write().read_only(),
+        MemFlags::new().read_write().host_read_only().host_no_access(),
+        MemFlags::new().read_write().use_host_ptr().alloc_host_ptr(),
+        MemFlags::new().read_write().use_host_ptr().copy_host_ptr(),
+    ];
+
+    for &flags in contradictory.iter() {
+        assert!(flags.verify().is_err());
+
+        let res = Buffer::<f32>::builder()
+            .queue(pro_que.queue().clone())
+            .flags(flags)
+            .len(pro_que.dims())
+            .build();
+        assert!(res.is_err(), "Build succeeded with flags: {:?}", flags);
+    }
+
+    // `use_host_slice` adds `USE_HOST_PTR`, which conflicts with
+    // `ALLOC_HOST_PTR` even though the flags alone are valid:
+    let host_vec = vec![0.0f32; pro_que.dims().to_len()];
+    let res = unsafe {
+        Buffer::<f32>::builder()
+            .queue(pro_que.queue().clone())
+            .flags(MemFlags::read_write_alloc())
+            .len(pro_que.dims())
+            .use_host_slice(&host_vec)
+            .build()
+    };
+    assert!(res.is_err());
+
+    let buffer = Buffer::<f32>::builder()
+        .queue(pro_que.queue().clone())
+        .flags(MemFlags::read_write_alloc())
+        .len(pro_que.dims())
+        .build().unwrap();
+    assert_eq!(buffer.flags().unwrap(), MemFlags::read_write_alloc());
+}
+
+#[test]
+fn buffer_build_rejects_oversized_alloc() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims(64)
+        .build().unwrap();
+
+    let max_size = pro_que.queue().device().max_mem_alloc_size().unwrap();
+    assert!(max_size > 0);
+
+    // Far larger than any device will allow:
+    let len = (max_size as usize / 4).saturating_mul(64);
+    let err = Buffer::<f32>::builder()
+        .queue(pro_que.queue().clone())
+        .len(len)
+        .build().unwrap_err();
+    assert!(format!("{}", err).contains("exceeds the maximum allocation size"),
+        "Unexpected error: {}", err);
+
+    // The same request via a context checks each of its devices:
+    let err = Buffer::<f32>::builder()
+        .context(pro_que.context())
+        .len(len)
+        .build().unwrap_err();
+    assert!(format!("{}", err).contains("exceeds the maximum allocation size"));
+
+    // Skipping the check passes the request on to the driver:
+    assert!(Buffer::<f32>::builder()
+        .queue(pro_que.queue().clone())
+        .len(pro_que.dims())
+        .skip_alloc_size_check()
+        .build().is_ok());
+}
+
+#[test]
+fn buffer_from_slice() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims(64)
+        .build().unwrap();
+
+    let buffer = Buffer::from_slice(pro_que.queue(), None, &[1.0f32, 2.0, 3.0]).unwrap();
+    assert_eq!(buffer.len(), 3);
+    assert!(buffer.flags().unwrap().contains(MemFlags::COPY_HOST_PTR | MemFlags::READ_WRITE));
+
+    let mut vec = vec![0.0f32; 3];
+    buffer.read(&mut vec).enq().unwrap();
+    assert_eq!(vec, [1.0, 2.0, 3.0]);
+
+    // Created from a context (without a default queue):
+    let buffer = Buffer::from_slice(pro_que.context(), Some(MemFlags::new().read_only()),
+        &[4i32, 5]).unwrap();
+    let mut vec = vec![0i32; 2];
+    buffer.read(&mut vec).queue(pro_que.queue()).enq().unwrap();
+    assert_eq!(vec, [4, 5]);
+
+    assert!(Buffer::from_slice(pro_que.queue(), Some(MemFlags::new().use_host_ptr()),
+        &[1.0f32]).is_err());
+    assert!(Buffer::<f32>::from_slice(pro_que.queue(), None, &[]).is_err());
+}
+
+#[test]
+fn buffer_build_rejects_zero_len() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims(64)
+        .build().unwrap();
+
+    let err = Buffer::<f32>::builder().queue(pro_que.queue().clone()).len(0).build()
+        .unwrap_err();
+    assert!(err.to_string().contains("cannot be zero"), "{}", err);
+
+    // Unset length:
+    assert!(Buffer::<f32>::builder().queue(pro_que.queue().clone()).build().is_err());
+}
@@ -0,0 +1,150 @@
+//! Blocking buffer maps.
+
+use flags;
+use standard::{Context, Queue, Buffer};
+
+const LEN: usize = 1 << 10;
+
+
+/// Maps a buffer for writing, mutates it through the guard, drops the guard
+/// (unmapping on a second queue), then verifies the contents with a read.
+#[test]
+fn map_sync_write_then_read() {
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+    let queue = Queue::new(&context, device, None).unwrap();
+    let unmap_queue = Queue::new(&context, device, None).unwrap();
+
+    let buffer = Buffer::<i32>::builder()
+        .queue(queue.clone())
+        .flags(flags::MEM_READ_WRITE | flags::MEM_ALLOC_HOST_PTR)
+        .len(LEN)
+        .fill_val(0i32)
+        .build().unwrap();
+
+    {
+        let mut guard = unsafe { buffer.map_sync(None, flags::MAP_WRITE).unwrap() };
+        assert_eq!(guard.len(), LEN);
+        guard.unmap_queue(unmap_queue.clone());
+
+        for (i, val) in guard.iter_mut().enumerate() {
+            *val = i as i32;
+        }
+    }
+
+    let mut vec = vec![0i32; LEN];
+    buffer.read(&mut vec).enq().unwrap();
+
+    for (i, &val) in vec.iter().enumerate() {
+        assert_eq!(val, i as i32);
+    }
+
+    // Mapping for reading using an explicit queue:
+    let guard = unsafe { buffer.map_sync(Some(&unmap_queue), flags::MAP_READ).unwrap() };
+    assert_eq!(guard[LEN - 1], (LEN - 1) as i32);
+    guard.unmap().unwrap();
+}
+
+
+/// Writes a buffer, maps it read-only asynchronously, and verifies the
+/// contents through the read-only view.
+#[test]
+fn map_async_read_only() {
+    use futures::Future;
+
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+    let queue = Queue::new(&context, device, None).unwrap();
+
+    let buffer = Buffer::<i32>::builder()
+        .queue(queue.clone())
+        .flags(flags::MEM_READ_WRITE | flags::MEM_ALLOC_HOST_PTR)
+        .len(LEN)
+        .build().unwrap();
+
+    let vec: Vec<i32> = (0..LEN as i32).map(|i| i * 3).collect();
+    buffer.write(&vec).enq().unwrap();
+
+    let future_map = unsafe { buffer.map().enq_async_read().unwrap() };
+    let mut map = future_map.wait().unwrap();
+    assert_eq!(&map[..], &vec[..]);
+    map.unmap().enq().unwrap();
+    assert!(map.is_unmapped());
+
+    // Explicit read flags are accepted, write flags are not:
+    let future_map = unsafe { buffer.map().flags(flags::MAP_READ).enq_async_read().unwrap() };
+    assert_eq!(future_map.wait().unwrap()[LEN - 1], (LEN as i32 - 1) * 3);
+    assert!(unsafe { buffer.map().write().enq_async_read().is_err() });
+    assert!(unsafe { buffer.map().write_invalidate().enq_async_read().is_err() });
+}
+
+
+/// Fills a buffer directly from an iterator and reads it back.
+#[test]
+fn write_iter_fill() {
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+    let queue = Queue::new(&context, device, None).unwrap();
+
+    let buffer = Buffer::<f32>::builder()
+        .queue(queue.clone())
+        .len(LEN)
+        .fill_val(-1.0f32)
+        .build().unwrap();
+
+    buffer.write_iter(None, (0..LEN).map(|i| i as f32)).unwrap();
+
+    let mut vec = vec![0.0f32; LEN];
+    buffer.read(&mut vec).enq().unwrap();
+
+    for (i, &val) in vec.iter().enumerate() {
+        assert_eq!(val, i as f32);
+    }
+
+    // Length mismatches are rejected without modifying the buffer:
+    assert!(buffer.write_iter(Some(&queue), (0..LEN - 1).map(|i| i as f32)).is_err());
+    assert!(buffer.write_iter(None, vec![0.0f32; LEN + 1]).is_err());
+
+    buffer.read(&mut vec).enq().unwrap();
+    assert_eq!(vec[LEN - 1], (LEN - 1) as f32);
+}
+
+
+/// Maps with plain (non-invalidating) write, modifies one element, and
+/// verifies that all other elements retain their prior values.
+#[test]
+fn map_write_preserves_contents() {
+    use core::MapFlags;
+
+    assert_eq!(MapFlags::new().write(), flags::MAP_WRITE);
+    assert!(!MapFlags::new().write().contains(flags::MAP_WRITE_INVALIDATE_REGION));
+
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+    let queue = Queue::new(&context, device, None).unwrap();
+
+    let buffer = Buffer::<i32>::builder()
+        .queue(queue.clone())
+        .len(LEN)
+        .build().unwrap();
+
+    let vec: Vec<i32> = (0..LEN as i32).collect();
+    buffer.write(&vec).enq().unwrap();
+
+    {
+        let mut guard = unsafe { buffer.map_sync(None, MapFlags::new().write()).unwrap() };
+        assert_eq!(guard[7], 7);
+        guard[7] = -7;
+    }
+
+    let mut result = vec![0i32; LEN];
+    buffer.read(&mut result).enq().unwrap();
+
+    for (i, &val) in result.iter().enumerate() {
+        if i == 7 {
+            assert_eq!(val, -7);
+        } else {
+            assert_eq!(val, i as i32);
+        }
+    }
+}
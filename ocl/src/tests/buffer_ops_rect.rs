@@ -360,3 +360,50 @@ fn buffer_ops_rect() {
 
     println!("{} total test runs complete.\n", ttl_runs);
 }
+
+/// Copies a 3x3 block from the center of one 5x5 buffer into the lower right
+/// corner of another, ensuring the surrounding data is untouched.
+#[test]
+fn buffer_copy_rect_block() {
+    const W: usize = 5;
+
+    let proque = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([W, W])
+        .build().unwrap();
+
+    let src_data: Vec<f32> = (0..W * W).map(|i| i as f32).collect();
+    let buf_src = Buffer::<f32>::builder()
+        .queue(proque.queue().clone())
+        .len(W * W)
+        .copy_host_slice(&src_data)
+        .build().unwrap();
+
+    let buf_dst = Buffer::<f32>::builder()
+        .queue(proque.queue().clone())
+        .len(W * W)
+        .fill_val(-1.0f32)
+        .build().unwrap();
+
+    let row_pitch_bytes = W * mem::size_of::<f32>();
+    let slc_pitch_bytes = W * row_pitch_bytes;
+
+    buf_src.cmd().copy(&buf_dst, None, None)
+        .rect([1, 1, 0], [2, 2, 0], [3, 3, 1], row_pitch_bytes, slc_pitch_bytes,
+            row_pitch_bytes, slc_pitch_bytes)
+        .enq().unwrap();
+
+    let mut vec_dst = vec![0.0f32; W * W];
+    buf_dst.read(&mut vec_dst).enq().unwrap();
+
+    for y in 0..W {
+        for x in 0..W {
+            let expected = if x >= 2 && y >= 2 {
+                src_data[(y - 1) * W + (x - 1)]
+            } else {
+                -1.0
+            };
+            assert_eq!(vec_dst[y * W + x], expected, "[{}, {}]", x, y);
+        }
+    }
+}
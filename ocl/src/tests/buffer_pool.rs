@@ -0,0 +1,125 @@
+//! Buffer pool recycling.
+
+use standard::{ProQue, Event, UserEvent, BufferPool};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+const LEN: usize = 1 << 12;
+
+
+#[test]
+fn buffer_pool_reuse() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(LEN)
+        .build().unwrap();
+
+    let pool = BufferPool::<f32>::new(pro_que.queue().clone(), None);
+    assert_eq!(pool.free_count(), 0);
+
+    let buffer = pool.acquire(LEN).unwrap();
+    assert_eq!(buffer.len(), LEN);
+    let mem_ptr = buffer.as_core().as_ptr();
+    drop(buffer);
+    assert_eq!(pool.free_count(), 1);
+
+    // Re-acquiring the same length reuses the same allocation:
+    let buffer = pool.acquire(LEN).unwrap();
+    assert_eq!(buffer.as_core().as_ptr(), mem_ptr);
+    assert_eq!(pool.free_count(), 0);
+
+    // A different length (or a second buffer) requires a new allocation:
+    let other = pool.acquire(LEN / 2).unwrap();
+    assert!(other.as_core().as_ptr() != mem_ptr);
+    let second = pool.acquire(LEN).unwrap();
+    assert!(second.as_core().as_ptr() != mem_ptr);
+
+    drop(buffer);
+    drop(other);
+    drop(second);
+    assert_eq!(pool.free_count(), 3);
+
+    pool.clear();
+    assert_eq!(pool.free_count(), 0);
+}
+
+#[test]
+fn buffer_pool_tracks_events() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(LEN)
+        .build().unwrap();
+
+    let pool = BufferPool::<f32>::new(pro_que.queue().clone(), None);
+
+    let mut buffer = pool.acquire(LEN).unwrap();
+    buffer.cmd().fill(0.0f32, None).enq().unwrap();
+    let mem_ptr = buffer.as_core().as_ptr();
+
+    let kernel = pro_que.kernel_builder("add")
+        .arg(&*buffer)
+        .arg(1.0f32)
+        .build().unwrap();
+
+    // Gate the kernel on a user event so that it cannot complete until
+    // released:
+    let gate = Event::user(pro_que.context()).unwrap();
+    let mut event = Event::empty();
+    unsafe { kernel.cmd().ewait(&gate).enew(&mut event).enq().unwrap(); }
+    buffer.track_event(event.clone());
+    drop(buffer);
+
+    // The pooled buffer is still in use and must not be handed out:
+    let busy = pool.acquire(LEN).unwrap();
+    assert!(busy.as_core().as_ptr() != mem_ptr);
+    drop(busy);
+
+    gate.set_complete().unwrap();
+    event.wait_for().unwrap();
+    pro_que.queue().finish().unwrap();
+
+    // Once the kernel has completed, the original buffer (returned to the
+    // pool first) is reused along with its contents:
+    let reused = pool.acquire(LEN).unwrap();
+    assert_eq!(reused.as_core().as_ptr(), mem_ptr);
+    let mut vec = vec![0.0f32; LEN];
+    reused.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 1.0));
+}
+
+#[test]
+fn buffer_pool_terminated_events_and_max_free() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(LEN)
+        .build().unwrap();
+
+    let pool = BufferPool::<f32>::new(pro_que.queue().clone(), None).with_max_free(2);
+
+    // A buffer whose tracked command terminated with an error is reusable:
+    let mut buffer = pool.acquire(LEN).unwrap();
+    let mem_ptr = buffer.as_core().as_ptr();
+    let gate = UserEvent::new(pro_que.context()).unwrap();
+    buffer.track_event(gate.clone());
+    gate.set_error(-1).unwrap();
+    drop(buffer);
+
+    let buffer = pool.acquire(LEN).unwrap();
+    assert_eq!(buffer.as_core().as_ptr(), mem_ptr);
+
+    // The least recently returned buffer is released once full:
+    let others: Vec<_> = (0..2).map(|_| pool.acquire(LEN).unwrap()).collect();
+    let other_ptrs: Vec<_> = others.iter().map(|b| b.as_core().as_ptr()).collect();
+    drop(buffer);
+    drop(others);
+    assert_eq!(pool.free_count(), 2);
+
+    let reused: Vec<_> = (0..2).map(|_| pool.acquire(LEN).unwrap()).collect();
+    let reused_ptrs: Vec<_> = reused.iter().map(|b| b.as_core().as_ptr()).collect();
+    assert!(!reused_ptrs.contains(&mem_ptr));
+    assert!(other_ptrs.iter().all(|ptr| reused_ptrs.contains(ptr)));
+}
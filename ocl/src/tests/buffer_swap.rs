@@ -0,0 +1,57 @@
+//! Tests for swapping buffers between iterations (double-buffering).
+
+use standard::{ProQue, Buffer};
+
+static SRC: &'static str = r#"
+    __kernel void add_one(__global float const* src, __global float* dst) {
+        uint idx = get_global_id(0);
+        dst[idx] = src[idx] + 1.0f;
+    }
+
+    __kernel void double_it(__global float const* src, __global float* dst) {
+        uint idx = get_global_id(0);
+        dst[idx] = src[idx] * 2.0f;
+    }
+"#;
+
+const LEN: usize = 1 << 12;
+
+#[test]
+fn buffer_swap_ping_pong() {
+    let pro_que = ProQue::builder().src(SRC).dims(LEN).build().unwrap();
+
+    let mut src = Buffer::<f32>::builder().queue(pro_que.queue().clone()).len(LEN)
+        .fill_val(1.0f32).build().unwrap();
+    let mut dst = Buffer::<f32>::builder().queue(pro_que.queue().clone()).len(LEN)
+        .fill_val(0.0f32).build().unwrap();
+
+    let add_one = pro_que.kernel_builder("add_one")
+        .arg_named("src", &src)
+        .arg_named("dst", &dst)
+        .build().unwrap();
+    let double_it = pro_que.kernel_builder("double_it")
+        .arg_named("src", &src)
+        .arg_named("dst", &dst)
+        .build().unwrap();
+
+    let mut expected = 1.0f32;
+    let mut vec = vec![0.0f32; LEN];
+
+    for _ in 0..3 {
+        for kernel in &[&add_one, &double_it] {
+            kernel.set_arg("src", &src).unwrap();
+            kernel.set_arg("dst", &dst).unwrap();
+            unsafe { kernel.enq().unwrap(); }
+            src.swap(&mut dst);
+        }
+        expected = (expected + 1.0) * 2.0;
+
+        // The most recent result is always in `src`:
+        src.read(&mut vec).enq().unwrap();
+        assert!(vec.iter().all(|&v| v == expected), "expected: {}, found: {}", expected, vec[0]);
+    }
+
+    assert_eq!(expected, 22.0);
+    assert_eq!(src.len(), LEN);
+    assert_eq!(dst.len(), LEN);
+}
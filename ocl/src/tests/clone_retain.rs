@@ -0,0 +1,106 @@
+//! Ensures that clones of each handle type retain the underlying object and
+//! remain usable after the original has been dropped.
+
+use flags;
+use enums::{ImageChannelOrder, ImageChannelDataType, MemObjectType, AddressingMode, FilterMode};
+use standard::{Context, Queue, Program, Kernel, Buffer, Image, Sampler, Event};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global int* buffer, int addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+const LEN: usize = 64;
+
+
+#[test]
+fn clone_context() {
+    let context = Context::builder().build().unwrap();
+    let count = context.reference_count().unwrap();
+    let context_clone = context.clone();
+    assert_eq!(context_clone.reference_count().unwrap(), count + 1);
+    drop(context);
+
+    assert_eq!(context_clone.reference_count().unwrap(), count);
+    let device = context_clone.devices()[0];
+    Queue::new(&context_clone, device, None).unwrap();
+}
+
+#[test]
+fn clone_queue_and_buffer() {
+    let context = Context::builder().build().unwrap();
+    let queue = Queue::new(&context, context.devices()[0], None).unwrap();
+    let queue_clone = queue.clone();
+    assert_eq!(queue_clone.reference_count().unwrap(), queue.reference_count().unwrap());
+    drop(queue);
+
+    let buffer = Buffer::<i32>::builder().queue(queue_clone.clone()).len(LEN).fill_val(5i32)
+        .build().unwrap();
+    let buffer_clone = buffer.clone();
+    drop(buffer);
+
+    // The cloned queue can still enqueue commands on the cloned buffer:
+    let mut vec = vec![0i32; LEN];
+    buffer_clone.read(&mut vec).queue(&queue_clone).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 5));
+    queue_clone.finish().unwrap();
+}
+
+#[test]
+fn clone_program_and_event() {
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+    let queue = Queue::new(&context, device, None).unwrap();
+    let program = Program::builder().src(SRC).devices(device).build(&context).unwrap();
+    let program_clone = program.clone();
+    drop(program);
+
+    let buffer = Buffer::<i32>::builder().queue(queue.clone()).len(LEN).fill_val(0i32)
+        .build().unwrap();
+    let kernel = Kernel::builder().program(&program_clone).name("add").queue(queue)
+        .global_work_size(LEN)
+        .arg(&buffer)
+        .arg(3i32)
+        .build().unwrap();
+
+    let mut event = Event::empty();
+    unsafe { kernel.cmd().enew(&mut event).enq().unwrap(); }
+    let event_clone = event.clone();
+    assert_eq!(event_clone.reference_count().unwrap(), event.reference_count().unwrap());
+    drop(event);
+    event_clone.wait_for().unwrap();
+
+    let mut vec = vec![0i32; LEN];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 3));
+}
+
+#[test]
+fn clone_image_and_sampler() {
+    let context = Context::builder().build().unwrap();
+    let queue = Queue::new(&context, context.devices()[0], None).unwrap();
+
+    let image = Image::<u8>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::UnormInt8)
+        .image_type(MemObjectType::Image2d)
+        .dims((8, 8))
+        .flags(flags::MEM_READ_WRITE)
+        .queue(queue)
+        .build().unwrap();
+    let image_clone = image.clone();
+    drop(image);
+
+    let src: Vec<u8> = (0..image_clone.element_count()).map(|i| i as u8).collect();
+    let mut dst = vec![0u8; image_clone.element_count()];
+    image_clone.write(&src).enq().unwrap();
+    image_clone.read(&mut dst).enq().unwrap();
+    assert_eq!(src, dst);
+
+    let sampler = Sampler::new(&context, false, AddressingMode::ClampToEdge,
+        FilterMode::Nearest).unwrap();
+    let sampler_clone = sampler.clone();
+    drop(sampler);
+    assert_eq!(sampler_clone.addressing_mode().unwrap(), AddressingMode::ClampToEdge);
+}
@@ -1,7 +1,11 @@
-//! Test adding multiple context properties.
+//! Test adding multiple context properties and enumerating platforms and
+//! devices.
 
+use core::{self, ContextPropertyValue, ContextProperties, DeviceId as DeviceIdCore,
+    ContextInfo, ContextInfoResult, PlatformId, DeviceInfo, DeviceInfoResult, DeviceType};
+use ffi;
+use flags;
 use standard::{Platform, Device, Context};
-use core::{ContextPropertyValue};
 
 // static SRC: &'static str = r#"
 //     __kernel void multiply(__global float* buffer, float coeff) {
@@ -69,4 +73,193 @@ fn test_context_props() {
         //     event.wait().unwrap();
         // }
     }
-}
\ No newline at end of file
+}
+
+/// Ensures the device list of a context, including one created from a device
+/// type rather than an explicit device list, round-trips.
+#[test]
+fn context_devices_round_trip() {
+    let platform = Platform::default();
+    let devices = Device::list_all(&platform).unwrap();
+    let devices = &devices[..::std::cmp::min(devices.len(), 2)];
+
+    let context = Context::builder()
+        .platform(platform)
+        .devices(devices)
+        .build().unwrap();
+
+    assert_eq!(context.devices().len(), devices.len());
+    assert_eq!(context.devices(), devices);
+    assert_eq!(context.num_devices().unwrap() as usize, devices.len());
+    assert_eq!(context.properties().unwrap().get_platform(), Some(PlatformId::from(platform)));
+    assert!(context.reference_count().unwrap() >= 1);
+
+    let props = ContextProperties::new().platform(platform);
+    let context_core = core::create_context_from_type::<DeviceIdCore>(Some(&props),
+        flags::DEVICE_TYPE_ALL, None, None).unwrap();
+    let context = Context::from(context_core);
+
+    assert_eq!(context.devices().len(), Device::list_all(&platform).unwrap().len());
+}
+
+#[test]
+fn platform_device_enumeration() {
+    let platforms = Platform::list();
+    assert!(platforms.contains(&Platform::default()));
+
+    if platforms.len() == 1 {
+        assert_eq!(platforms[0], Platform::default());
+    }
+
+    let device_count: usize = platforms.iter()
+        .map(|p| Device::list_all(p).unwrap().len())
+        .sum();
+    assert_eq!(Device::list_all_platforms().unwrap().len(), device_count);
+}
+
+/// Ensures a platform property round-trips through context creation and the
+/// `ContextInfo::Properties` query.
+#[test]
+fn context_props_platform_round_trip() {
+    let platform = Platform::default();
+    let devices = Device::list_all(&platform).unwrap();
+
+    let props = ContextProperties::new().platform(platform);
+    let props_raw = props.to_raw();
+    assert_eq!(props_raw.len(), 3);
+    assert_eq!(*props_raw.last().unwrap(), 0);
+
+    let context = core::create_context(Some(&props), &devices, None, None).unwrap();
+
+    match core::get_context_info(&context, ContextInfo::Properties).unwrap() {
+        ContextInfoResult::Properties(props) => {
+            assert_eq!(props.get_platform(), Some(PlatformId::from(platform)));
+        },
+        _ => unreachable!(),
+    }
+}
+
+/// Ensures that device selection filters narrow the candidate list and that
+/// an impossible filter returns an error.
+#[test]
+fn device_select() {
+    let all_devices = Device::list_all_platforms().unwrap();
+    let first = Device::first(Platform::default()).unwrap();
+
+    let compute_units = |device: &Device| match device.info(DeviceInfo::MaxComputeUnits).unwrap() {
+        DeviceInfoResult::MaxComputeUnits(cu) => cu,
+        _ => unreachable!(),
+    };
+    let is_gpu = |device: &Device| device.is_gpu().unwrap();
+
+    // No filters:
+    let selected = Device::select().resolve().unwrap();
+    assert!(all_devices.contains(&selected));
+    assert_eq!(Device::select().filter(&all_devices).unwrap(), all_devices);
+
+    // Name:
+    let name = first.name().unwrap();
+    let by_name = Device::select().name_contains(name.to_uppercase()).filter(&all_devices).unwrap();
+    assert!(by_name.contains(&first));
+    assert!(by_name.iter().all(|d| d.name().unwrap().contains(&name)));
+
+    // Compute units:
+    let cu = compute_units(&first);
+    let by_cu = Device::select().min_compute_units(cu).filter(&all_devices).unwrap();
+    assert!(by_cu.contains(&first));
+    assert!(by_cu.iter().all(|d| compute_units(d) >= cu));
+
+    // Preferences:
+    let selected = Device::select().prefer_gpu().resolve().unwrap();
+    if all_devices.iter().any(|d| is_gpu(d)) {
+        assert!(is_gpu(&selected));
+    }
+
+    // Impossible filters:
+    assert!(Device::select().name_contains("nonexistent-device-name").resolve().is_err());
+    assert!(Device::select().min_compute_units(::std::u32::MAX).resolve().is_err());
+    assert!(Device::select().resolve_from(&[]).is_err());
+}
+
+/// Ensures the default device reports a sensible device type.
+#[test]
+fn device_type_queries() {
+    let device = Device::first(Platform::default()).unwrap();
+    let device_type = device.device_type().unwrap();
+
+    assert!(device_type.intersects(DeviceType::CPU | DeviceType::GPU | DeviceType::ACCELERATOR |
+        DeviceType::CUSTOM));
+    assert!(!device_type.to_string().is_empty());
+    assert_eq!(device.is_gpu().unwrap(), device_type.contains(DeviceType::GPU));
+    assert_eq!(device.is_cpu().unwrap(), device_type.contains(DeviceType::CPU));
+    assert_eq!(device.is_accelerator().unwrap(), device_type.contains(DeviceType::ACCELERATOR));
+}
+
+/// Ensures `Platform::extension_fn` refuses unadvertised extensions and
+/// resolves an advertised one.
+#[test]
+fn platform_extension_fn() {
+    type IcdGetPlatformIdsFn = extern "system" fn(u32, *mut ffi::cl_platform_id, *mut u32) -> i32;
+
+    let platform = Platform::default();
+
+    if platform.as_core().version().unwrap() < [1, 2].into() {
+        println!("Skipping 'tests::context_props::platform_extension_fn': \
+            OpenCL 1.2 or higher is required.");
+        return;
+    }
+
+    let res = unsafe {
+        platform.extension_fn::<IcdGetPlatformIdsFn>("cl_ocl_nonexistent_ext", "clIcdGetPlatformIDsKHR")
+    };
+    assert!(res.is_err());
+
+    if platform.extensions().unwrap().contains("cl_khr_icd") {
+        unsafe {
+            platform.extension_fn::<IcdGetPlatformIdsFn>("cl_khr_icd", "clIcdGetPlatformIDsKHR")
+                .unwrap();
+        }
+    }
+}
+
+/// Ensures malformed raw property lists are rejected before reaching
+/// `clCreateContext`.
+#[test]
+fn context_props_verify_raw() {
+    let platform = Platform::default();
+    let props = ContextProperties::new().platform(platform).interop_user_sync(true);
+    let raw = props.to_raw();
+    ContextProperties::verify_raw(&raw).unwrap();
+
+    // Missing terminator:
+    let unterminated = &raw[..raw.len() - 1];
+    assert!(ContextProperties::verify_raw(unterminated).is_err());
+    assert!(unsafe { ContextProperties::from_raw(unterminated) }.is_err());
+
+    // Missing value:
+    let key_only = [core::ContextProperty::Platform as isize, 0];
+    assert!(ContextProperties::verify_raw(&key_only).is_err());
+
+    // Invalid `cl_bool`:
+    let bad_bool = [core::ContextProperty::InteropUserSync as isize, 7, 0];
+    assert!(ContextProperties::verify_raw(&bad_bool).is_err());
+}
+
+/// Ensures that `core::verify_context` accepts a live context and rejects an
+/// invalid one.
+///
+/// A null pointer stands in for an invalid context as querying a context
+/// which has actually been released is undefined behavior.
+#[test]
+fn context_verify() {
+    let context = Context::builder().build().unwrap();
+    core::verify_context(&context).unwrap();
+    core::verify_context(context.as_core()).unwrap();
+
+    // A clone shares (and retains) the same context:
+    let clone = context.clone();
+    drop(context);
+    core::verify_context(&clone).unwrap();
+
+    assert!(core::verify_context(::std::ptr::null_mut() as ffi::cl_context).is_err());
+}
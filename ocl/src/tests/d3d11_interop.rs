@@ -0,0 +1,25 @@
+//! Direct3D 11 interop (`d3d11_interop` feature, Windows only).
+//!
+//! Creating a Direct3D 11 device is outside the scope of these tests. This
+//! only ensures that the interop API is present and has the expected
+//! signatures.
+
+use ffi::c_void;
+use core::{MemFlags, Mem as MemCore};
+use standard::{Buffer, Image, Queue, Event, ContextBuilder};
+use error::{Result as OclResult};
+
+
+#[test]
+fn d3d11_interop_signatures() {
+    let _: fn(&mut ContextBuilder, *mut c_void) -> &mut ContextBuilder =
+        ContextBuilder::d3d11_device;
+    let _: unsafe fn(&Queue, Option<MemFlags>, *mut c_void) -> OclResult<Buffer<f32>> =
+        Buffer::<f32>::from_d3d11_buffer::<&Queue>;
+    let _: unsafe fn(&Queue, MemFlags, *mut c_void, u32) -> OclResult<Image<u8>> =
+        Image::<u8>::from_d3d11_texture2d::<&Queue>;
+    let _: fn(&Queue, &[MemCore], Option<Event>) -> OclResult<Event> =
+        Queue::enqueue_acquire_d3d11_objects::<MemCore, Event>;
+    let _: fn(&Queue, &[MemCore], Option<Event>) -> OclResult<Event> =
+        Queue::enqueue_release_d3d11_objects::<MemCore, Event>;
+}
@@ -0,0 +1,25 @@
+//! Device and host timers (OpenCL 2.1+).
+
+use standard::{Platform, Device};
+
+
+/// Ensures that the device and host timers are monotonic across successive
+/// calls.
+#[test]
+fn device_and_host_timer_monotonic() {
+    let device = Device::first(Platform::default()).unwrap();
+
+    if device.version().unwrap() < [2, 1].into() {
+        println!("Skipping 'tests::device_timer::device_and_host_timer_monotonic': \
+            OpenCL 2.1+ is required.");
+        return;
+    }
+
+    let (device_ts_0, host_ts_0) = device.device_and_host_timer().unwrap();
+    let host_ts_1 = device.host_timer().unwrap();
+    let (device_ts_1, host_ts_2) = device.device_and_host_timer().unwrap();
+
+    assert!(device_ts_1 >= device_ts_0);
+    assert!(host_ts_1 >= host_ts_0);
+    assert!(host_ts_2 >= host_ts_1);
+}
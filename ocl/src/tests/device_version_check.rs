@@ -1,4 +1,37 @@
-//! Implement me
-//!
-//! Check that device.info(DeviceInfo::OpenclCVersion == "OpenCL C 1.2") or greater
-//! Perhaps add something to parse the OpenCL C version into a number.
+//! OpenCL version parsing and detection.
+
+use core::OpenclVersion;
+use standard::{Platform, Device};
+
+#[test]
+fn opencl_version_parse() {
+    let parse = |s| OpenclVersion::from_info_str(s).unwrap();
+
+    assert_eq!(parse("OpenCL 1.2 CUDA 10.1.120"), [1, 2].into());
+    assert_eq!(parse("OpenCL 2.0 AMD-APP (2766.4)"), [2, 0].into());
+    assert_eq!(parse("OpenCL 1.1 Mesa 18.0.5"), [1, 1].into());
+    assert_eq!(parse("opencl 3.0 pocl").to_raw(), (3, 0));
+
+    assert!(parse("OpenCL 1.2") < parse("OpenCL 2.0"));
+    assert!(parse("OpenCL 2.1") > parse("OpenCL 2.0"));
+
+    assert!(OpenclVersion::from_info_str("OpenGL 4.5").is_err());
+    assert!(OpenclVersion::from_info_str("OpenCL one.two").is_err());
+}
+
+#[test]
+fn platform_device_versions() {
+    let platform = Platform::default();
+    let platform_ver = platform.opencl_version().unwrap();
+    assert!(platform_ver >= [1, 0].into());
+
+    for device in Device::list_all(&platform).unwrap() {
+        let device_ver = device.version().unwrap();
+        let (major, minor) = device_ver.to_raw();
+
+        assert!(device.supports(1, 0));
+        assert!(device.supports(major, minor));
+        assert!(!device.supports(major + 1, 0));
+        assert_eq!(device.supports(2, 0), device_ver >= [2, 0].into());
+    }
+}
@@ -0,0 +1,74 @@
+//! Tests for closures registered on event completion.
+
+use std::sync::mpsc;
+use std::time::Duration;
+use core::CommandExecutionStatus;
+use standard::{Context, Event, ProQue};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float scalar) {
+        buffer[get_global_id(0)] += scalar;
+    }
+"#;
+
+
+#[test]
+fn on_complete_user_event() {
+    let context = Context::builder().build().unwrap();
+    let event = Event::user(&context).unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    event.on_complete(move |status| tx.send(status).unwrap()).unwrap();
+
+    // Not yet complete:
+    assert!(rx.try_recv().is_err());
+
+    event.set_complete().unwrap();
+
+    let status = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(status, CommandExecutionStatus::Complete);
+}
+
+#[test]
+fn on_complete_already_complete() {
+    let context = Context::builder().build().unwrap();
+    let event = Event::user(&context).unwrap();
+    event.set_complete().unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    event.on_complete(move |status| tx.send(status).unwrap()).unwrap();
+
+    let status = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(status, CommandExecutionStatus::Complete);
+}
+
+#[test]
+fn set_callback_status_running_and_complete() {
+    let pro_que = ProQue::builder().src(SRC).dims(1 << 16).build().unwrap();
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    let kernel = pro_que.kernel_builder("add")
+        .arg(&buffer)
+        .arg(10.0f32)
+        .build().unwrap();
+
+    let gate = Event::user(pro_que.context()).unwrap();
+    let mut event = Event::empty();
+    unsafe { kernel.cmd().ewait(&gate).enew(&mut event).enq().unwrap(); }
+
+    let (tx, rx) = mpsc::channel();
+    let tx_running = tx.clone();
+    event.set_callback_status(CommandExecutionStatus::Running,
+        move |status| tx_running.send(status).unwrap()).unwrap();
+    event.set_callback_status(CommandExecutionStatus::Complete,
+        move |status| tx.send(status).unwrap()).unwrap();
+
+    // Callbacks may only be triggered by `Submitted`, `Running`, or `Complete`:
+    assert!(event.set_callback_status(CommandExecutionStatus::Queued, |_| ()).is_err());
+
+    gate.set_complete().unwrap();
+
+    let first = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    let second = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(first, CommandExecutionStatus::Running);
+    assert_eq!(second, CommandExecutionStatus::Complete);
+}
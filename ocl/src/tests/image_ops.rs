@@ -1,12 +1,12 @@
 //! Tests various image operations.
 //!
-//! * TODO: Test fill and copy to buffer.
+//! * TODO: Test fill.
 //!
 //! Runs both the core function and the 'standard' method call for each.
 
 use core::{self, DeviceInfo, DeviceInfoResult};
 use flags;
-use standard::{ProQue, Image, Sampler, Platform, Device};
+use standard::{ProQue, Image, Sampler, Platform, Device, Buffer};
 use enums::{AddressingMode, FilterMode, ImageChannelOrder, ImageChannelDataType, MemObjectType};
 use prm::{Int4};
 use tests;
@@ -245,3 +245,460 @@ fn image_ops() {
 
     println!("{} total test runs complete.\n", ttl_runs);
 }
+
+/// Uploads a 2D RGBA image, reads it back, and compares pixel-for-pixel.
+#[test]
+fn image_2d_read_write() {
+    const IMG_DIMS: [usize; 2] = [16, 16];
+
+    let proque = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims(IMG_DIMS)
+        .build().unwrap();
+
+    let image = Image::<u8>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::UnormInt8)
+        .image_type(MemObjectType::Image2d)
+        .dims(IMG_DIMS)
+        .flags(flags::MEM_READ_WRITE)
+        .queue(proque.queue().clone())
+        .build().unwrap();
+
+    let src: Vec<u8> = (0..image.element_count()).map(|i| (i % 251) as u8).collect();
+    let mut dst = vec![0u8; image.element_count()];
+
+    image.write(&src).enq().unwrap();
+    image.read(&mut dst).enq().unwrap();
+    assert_eq!(src, dst);
+
+    // Read back a sub-region (the lower right quadrant):
+    let (origin, region) = ([8, 8, 0], [8, 8, 1]);
+    let mut quad = vec![0u8; 8 * 8 * 4];
+    image.read(&mut quad).origin(origin).region(region).enq().unwrap();
+
+    for y in 0..8 {
+        for x in 0..8 {
+            for c in 0..4 {
+                let src_idx = (((y + 8) * IMG_DIMS[0]) + (x + 8)) * 4 + c;
+                assert_eq!(quad[(y * 8 + x) * 4 + c], src[src_idx]);
+            }
+        }
+    }
+}
+
+/// Round-trips data from an image to a buffer and back to another image.
+#[test]
+fn image_buffer_copy_round_trip() {
+    const IMG_DIMS: [usize; 2] = [16, 16];
+
+    let proque = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims(IMG_DIMS)
+        .build().unwrap();
+
+    let build_image = || Image::<u8>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::UnormInt8)
+        .image_type(MemObjectType::Image2d)
+        .dims(IMG_DIMS)
+        .flags(flags::MEM_READ_WRITE)
+        .queue(proque.queue().clone())
+        .build().unwrap();
+
+    let img_src = build_image();
+    let img_dst = build_image();
+
+    let buffer = Buffer::<u8>::builder()
+        .queue(proque.queue().clone())
+        .len(img_src.element_count())
+        .fill_val(0u8)
+        .build().unwrap();
+
+    let src: Vec<u8> = (0..img_src.element_count()).map(|i| (i % 253) as u8).collect();
+    img_src.write(&src).enq().unwrap();
+
+    // Image -> buffer:
+    img_src.cmd().copy_to_buffer(&buffer, 0).enq().unwrap();
+
+    let mut vec = vec![0u8; buffer.len()];
+    buffer.read(&mut vec).enq().unwrap();
+    assert_eq!(src, vec);
+
+    // Buffer -> image:
+    buffer.cmd().copy_to_image(&img_dst, [0, 0, 0], [IMG_DIMS[0], IMG_DIMS[1], 1]).enq().unwrap();
+
+    let mut dst = vec![0u8; img_dst.element_count()];
+    img_dst.read(&mut dst).enq().unwrap();
+    assert_eq!(src, dst);
+}
+
+/// Ensures a buffer too small (from its offset) to fill the copy region is
+/// rejected before reaching the driver.
+#[test]
+fn buffer_to_image_copy_len() {
+    const IMG_DIMS: [usize; 2] = [16, 16];
+
+    let proque = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims(IMG_DIMS)
+        .build().unwrap();
+
+    let image = Image::<u8>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::UnormInt8)
+        .image_type(MemObjectType::Image2d)
+        .dims(IMG_DIMS)
+        .flags(flags::MEM_READ_WRITE)
+        .queue(proque.queue().clone())
+        .build().unwrap();
+
+    let build_buffer = |len: usize| Buffer::<u8>::builder()
+        .queue(proque.queue().clone())
+        .len(len)
+        .fill_val(9u8)
+        .build().unwrap();
+
+    let region = [IMG_DIMS[0], IMG_DIMS[1], 1];
+    let region_len = image.element_count();
+
+    let short = build_buffer(region_len - 4);
+    let err = short.cmd().copy_to_image(&image, [0, 0, 0], region).enq().unwrap_err();
+    assert!(format!("{}", err).contains("too small"), "Unexpected error: {}", err);
+
+    // Long enough only when starting at the beginning:
+    let exact = build_buffer(region_len);
+    assert!(exact.cmd().copy_to_image(&image, [0, 0, 0], region).offset(4).enq().is_err());
+    exact.cmd().copy_to_image(&image, [0, 0, 0], region).enq().unwrap();
+
+    let mut dst = vec![0u8; region_len];
+    image.read(&mut dst).enq().unwrap();
+    assert!(dst.iter().all(|&v| v == 9));
+}
+
+/// Builds a 2D image from a correctly sized host slice and ensures an
+/// undersized host slice is rejected.
+#[test]
+fn image_builder_host_slice_len() {
+    const IMG_DIMS: [usize; 2] = [32, 16];
+
+    let proque = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims(IMG_DIMS)
+        .build().unwrap();
+
+    let builder = || Image::<u8>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::UnormInt8)
+        .image_type(MemObjectType::Image2d)
+        .dims(IMG_DIMS)
+        .flags(flags::MEM_READ_WRITE)
+        .queue(proque.queue().clone());
+
+    let src = vec![7u8; IMG_DIMS[0] * IMG_DIMS[1] * 4];
+    let image = builder().copy_host_slice(&src).build().unwrap();
+
+    let mut dst = vec![0u8; image.element_count()];
+    image.read(&mut dst).enq().unwrap();
+    assert_eq!(src, dst);
+
+    let short = vec![7u8; IMG_DIMS[0] * IMG_DIMS[1]];
+    assert!(builder().copy_host_slice(&short).build().is_err());
+}
+
+/// Copies an 8x8 region from one image into the corner of another.
+#[test]
+fn image_copy_region() {
+    const IMG_DIMS: [usize; 2] = [16, 16];
+
+    let proque = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims(IMG_DIMS)
+        .build().unwrap();
+
+    let build_image = |val: u8| {
+        let data = vec![val; IMG_DIMS[0] * IMG_DIMS[1] * 4];
+        Image::<u8>::builder()
+            .channel_order(ImageChannelOrder::Rgba)
+            .channel_data_type(ImageChannelDataType::UnormInt8)
+            .image_type(MemObjectType::Image2d)
+            .dims(IMG_DIMS)
+            .flags(flags::MEM_READ_WRITE)
+            .copy_host_slice(&data)
+            .queue(proque.queue().clone())
+            .build().unwrap()
+    };
+
+    let img_src = build_image(200);
+    let img_dst = build_image(10);
+
+    // Copy the center 8x8 region of `img_src` into the upper left corner of
+    // `img_dst`:
+    img_src.cmd().copy(&img_dst, [0, 0, 0])
+        .origin([4, 4, 0])
+        .region([8, 8, 1])
+        .enq().unwrap();
+
+    let mut dst = vec![0u8; img_dst.element_count()];
+    img_dst.read(&mut dst).enq().unwrap();
+
+    for y in 0..IMG_DIMS[1] {
+        for x in 0..IMG_DIMS[0] {
+            let expected = if x < 8 && y < 8 { 200 } else { 10 };
+            for c in 0..4 {
+                assert_eq!(dst[(y * IMG_DIMS[0] + x) * 4 + c], expected,
+                    "pixel: [{}, {}]", x, y);
+            }
+        }
+    }
+}
+
+/// Maps a 2D image, writes a diagonal pattern row by row (respecting the
+/// row pitch), unmaps it, then reads it back.
+#[test]
+fn image_map_diagonal() {
+    const IMG_DIMS: [usize; 2] = [24, 24];
+    const CHANNELS: usize = 4;
+
+    let proque = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims(IMG_DIMS)
+        .build().unwrap();
+
+    let image = Image::<u8>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::UnormInt8)
+        .image_type(MemObjectType::Image2d)
+        .dims(IMG_DIMS)
+        .flags(flags::MEM_READ_WRITE)
+        .queue(proque.queue().clone())
+        .build().unwrap();
+
+    {
+        let mut map = unsafe { image.map().flags(flags::MAP_WRITE).enq().unwrap() };
+        assert_eq!(map.row_len(), IMG_DIMS[0] * CHANNELS);
+        assert!(map.row_pitch_bytes() >= IMG_DIMS[0] * CHANNELS);
+
+        for y in 0..IMG_DIMS[1] {
+            let row = map.row_mut(y, 0);
+            for x in 0..IMG_DIMS[0] {
+                let val = if x == y { 255 } else { 0 };
+                for c in 0..CHANNELS { row[x * CHANNELS + c] = val; }
+            }
+        }
+
+        map.unmap().unwrap();
+        assert!(map.unmap().is_err());
+    }
+
+    let mut dst = vec![1u8; image.element_count()];
+    image.read(&mut dst).enq().unwrap();
+
+    for y in 0..IMG_DIMS[1] {
+        for x in 0..IMG_DIMS[0] {
+            let expected = if x == y { 255 } else { 0 };
+            let idx = (y * IMG_DIMS[0] + x) * CHANNELS;
+            assert!(dst[idx..idx + CHANNELS].iter().all(|&v| v == expected),
+                "pixel: [{}, {}]", x, y);
+        }
+    }
+}
+
+/// Ensures that the pixel size math of `ImageFormat` is correct and agrees
+/// with the element size reported by the platform.
+#[test]
+fn image_format_bytes_per_pixel() {
+    use core::{ImageFormat, ImageInfo, ImageInfoResult};
+
+    assert_eq!(ImageFormat::rgba8().channel_count(), 4);
+    assert_eq!(ImageFormat::rgba8().bytes_per_pixel(), 4);
+    assert_eq!(ImageFormat::bgra8().bytes_per_pixel(), 4);
+    assert_eq!(ImageFormat::rgba_f32().bytes_per_pixel(), 16);
+    assert_eq!(ImageFormat::r8().bytes_per_pixel(), 1);
+    assert_eq!(ImageFormat::r_f32().channel_count(), 1);
+    assert_eq!(ImageFormat::r_f32().bytes_per_pixel(), 4);
+    assert_eq!(ImageFormat::new(ImageChannelOrder::Rg, ImageChannelDataType::HalfFloat)
+        .bytes_per_pixel(), 4);
+    assert_eq!(ImageFormat::new(ImageChannelOrder::Rgb, ImageChannelDataType::UnormShort565)
+        .bytes_per_pixel(), 2);
+    assert_eq!(ImageFormat::new(ImageChannelOrder::Rgbx, ImageChannelDataType::UnormInt101010)
+        .bytes_per_pixel(), 4);
+
+    let proque = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([16, 16])
+        .build().unwrap();
+
+    for format in vec![ImageFormat::rgba8(), ImageFormat::rgba_f32(), ImageFormat::r_f32()] {
+        let image = Image::<u8>::builder()
+            .image_format(format.clone())
+            .image_type(MemObjectType::Image2d)
+            .dims([16, 16])
+            .flags(flags::MEM_READ_WRITE)
+            .queue(proque.queue().clone())
+            .build().unwrap();
+
+        match image.info(ImageInfo::ElementSize).unwrap() {
+            ImageInfoResult::ElementSize(s) => assert_eq!(s, format.bytes_per_pixel(),
+                "format: {:?}", format),
+            r => panic!("Unexpected 'ImageInfoResult' variant: {:?}", r),
+        }
+    }
+}
+
+/// Creates a 1D image buffer over an existing buffer and reads its texels
+/// from a kernel.
+#[test]
+fn image_1d_buffer() {
+    use core::ImageFormat;
+
+    const LEN: usize = 64;
+
+    let src = r#"
+        __kernel void read_texels(read_only image1d_buffer_t img, __global float* out) {
+            int idx = get_global_id(0);
+            out[idx] = read_imagef(img, idx).x;
+        }
+    "#;
+
+    let proque = ProQue::builder()
+        .src(src)
+        .dims(LEN)
+        .build().unwrap();
+
+    if proque.queue().device().version().unwrap() < [1, 2].into() {
+        println!("Skipping 'tests::image_ops::image_1d_buffer': OpenCL 1.2 or higher \
+            is required.");
+        return;
+    }
+
+    let data: Vec<f32> = (0..LEN).map(|i| i as f32).collect();
+    let buffer = proque.buffer_builder::<f32>().copy_host_slice(&data).build().unwrap();
+    let out = proque.buffer_builder::<f32>().fill_val(-1.0f32).build().unwrap();
+
+    let image = Image::<f32>::builder()
+        .image_format(ImageFormat::r_f32())
+        .buffer(&buffer)
+        .flags(flags::MEM_READ_ONLY)
+        .queue(proque.queue().clone())
+        .build().unwrap();
+    assert_eq!(image.dims().to_lens().unwrap(), [LEN, 1, 1]);
+
+    let kernel = proque.kernel_builder("read_texels")
+        .arg(&image)
+        .arg(&out)
+        .build().unwrap();
+
+    unsafe { kernel.enq().unwrap(); }
+
+    let mut vec = vec![0.0f32; LEN];
+    out.read(&mut vec).enq().unwrap();
+    assert_eq!(vec, data);
+
+    // The width must fit within the buffer:
+    assert!(Image::<f32>::builder()
+        .image_format(ImageFormat::r_f32())
+        .buffer(&buffer)
+        .dims(LEN + 1)
+        .queue(proque.queue().clone())
+        .build().is_err());
+
+    // A buffer is required:
+    assert!(Image::<f32>::builder()
+        .image_format(ImageFormat::r_f32())
+        .image_type(MemObjectType::Image1dBuffer)
+        .dims(LEN)
+        .queue(proque.queue().clone())
+        .build().is_err());
+}
+
+/// Samples a 2D image with a linear-filtering sampler at fractional
+/// coordinates and verifies the bilinearly interpolated results.
+#[test]
+fn image_sampler_bilinear() {
+    let src = r#"
+        __kernel void sample(
+                    sampler_t sampler,
+                    __read_only image2d_t img,
+                    __global float const* coords,
+                    __global float* results)
+        {
+            uint idx = get_global_id(0);
+            float2 coord = vload2(idx, coords);
+            results[idx] = read_imagef(img, sampler, coord).x;
+        }
+    "#;
+
+    // Texel centers are at (x + 0.5, y + 0.5) with unnormalized coordinates:
+    let coords: Vec<f32> = vec![
+        0.5, 0.5,
+        1.5, 0.5,
+        1.0, 0.5,
+        0.75, 0.5,
+        1.0, 1.0,
+        1.25, 1.0,
+        1.5, 1.25,
+    ];
+    let expected = [0.0f32, 10.0, 5.0, 2.5, 15.0, 17.5, 25.0];
+    let sample_count = expected.len();
+
+    let proque = ProQue::builder()
+        .src(src)
+        .dims(sample_count)
+        .build().unwrap();
+
+    // A 2x2 image with texel values: [[0, 10], [20, 30]] (red channel only):
+    let texels: Vec<f32> = [0.0f32, 10.0, 20.0, 30.0].iter()
+        .flat_map(|&v| vec![v, 0.0, 0.0, 1.0])
+        .collect();
+
+    let image = Image::<f32>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::Float)
+        .image_type(MemObjectType::Image2d)
+        .dims([2, 2])
+        .flags(flags::MEM_READ_ONLY | flags::MEM_COPY_HOST_PTR)
+        .copy_host_slice(&texels)
+        .queue(proque.queue().clone())
+        .build().unwrap();
+
+    let sampler = Sampler::builder()
+        .context(proque.context())
+        .normalized_coords(false)
+        .addressing_mode(AddressingMode::ClampToEdge)
+        .filter_mode(FilterMode::Linear)
+        .build().unwrap();
+    assert_eq!(sampler.filter_mode().unwrap(), FilterMode::Linear);
+    assert!(unsafe { !sampler.as_core().as_ptr().is_null() });
+
+    let coords = Buffer::builder()
+        .queue(proque.queue().clone())
+        .copy_host_slice(&coords)
+        .len(sample_count * 2)
+        .build().unwrap();
+    let results = proque.create_buffer::<f32>().unwrap();
+
+    let kernel = proque.kernel_builder("sample")
+        .arg_sampler(&sampler)
+        .arg(&image)
+        .arg(&coords)
+        .arg(&results)
+        .build().unwrap();
+
+    unsafe { kernel.enq().unwrap(); }
+
+    let mut vec = vec![0.0f32; sample_count];
+    results.read(&mut vec).enq().unwrap();
+
+    // Linear filtering is permitted reduced (e.g. 8-bit fixed point)
+    // interpolation weight precision:
+    for (i, (&res, &exp)) in vec.iter().zip(expected.iter()).enumerate() {
+        assert!((res - exp).abs() < 0.2, "sample {}: expected: {}, found: {}", i, exp, res);
+    }
+
+    // Re-setting the sampler after the kernel has been built:
+    kernel.set_arg_sampler_named(0, Some(&sampler)).unwrap();
+    unsafe { kernel.enq().unwrap(); }
+    results.read(&mut vec).enq().unwrap();
+    assert!((vec[4] - 15.0).abs() < 0.2);
+}
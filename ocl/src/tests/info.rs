@@ -0,0 +1,328 @@
+//! Tests for decoded info results.
+
+use core;
+use flags;
+use standard::{ProQue, Program, Buffer, Image, Event, Sampler};
+use enums::{ProgramInfo, ProgramInfoResult, MemInfo, MemInfoResult, ImageInfo, ImageInfoResult,
+    ImageChannelOrder, ImageChannelDataType, MemObjectType, KernelInfo, KernelInfoResult,
+    CommandExecutionStatus, CommandType, AddressingMode, FilterMode, KernelArgInfo,
+    KernelArgInfoResult, KernelArgAddressQualifier};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+
+    __kernel void multiply(__global float* buffer, float coeff) {
+        buffer[get_global_id(0)] *= coeff;
+    }
+"#;
+
+
+#[test]
+fn program_info_kernel_names() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(1024)
+        .build().unwrap();
+
+    match pro_que.program().info(ProgramInfo::KernelNames).unwrap() {
+        ProgramInfoResult::KernelNames(names) => {
+            assert!(names.iter().any(|n| n == "add"));
+            assert!(names.iter().any(|n| n == "multiply"));
+        },
+        _ => panic!("Unexpected 'ProgramInfoResult' variant."),
+    }
+
+    assert_eq!(pro_que.program().num_kernels().unwrap(), 2);
+}
+
+#[test]
+fn kernel_wg_info_preferred_multiple() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(1024)
+        .build().unwrap();
+
+    let kernel = pro_que.kernel_builder("add")
+        .arg(None::<&Buffer<f32>>)
+        .arg(10.0f32)
+        .build().unwrap();
+
+    let device = pro_que.queue().device();
+    let max_wg_size = kernel.max_wg_size(device).unwrap();
+    let multiple = kernel.preferred_wg_size_multiple(device).unwrap();
+
+    assert!(multiple > 0);
+    assert!(multiple <= max_wg_size);
+}
+
+#[test]
+fn kernel_info_name_and_num_args() {
+    let src = r#"
+        __kernel void multiply(__global float* buffer, float coeff, __global float* result) {
+            result[get_global_id(0)] = buffer[get_global_id(0)] * coeff;
+        }
+    "#;
+
+    let pro_que = ProQue::builder()
+        .src(src)
+        .dims(1024)
+        .build().unwrap();
+
+    let kernel = pro_que.kernel_builder("multiply")
+        .arg(None::<&Buffer<f32>>)
+        .arg(10.0f32)
+        .arg(None::<&Buffer<f32>>)
+        .build().unwrap();
+
+    assert_eq!(kernel.num_args().unwrap(), 3);
+    assert_eq!(kernel.name().unwrap(), "multiply");
+
+    match kernel.info(KernelInfo::FunctionName).unwrap() {
+        KernelInfoResult::FunctionName(name) => assert_eq!(name, "multiply"),
+        _ => panic!("Unexpected 'KernelInfoResult' variant."),
+    }
+    match kernel.info(KernelInfo::ReferenceCount).unwrap() {
+        KernelInfoResult::ReferenceCount(count) => assert!(count > 0),
+        _ => panic!("Unexpected 'KernelInfoResult' variant."),
+    }
+    match kernel.info(KernelInfo::Program).unwrap() {
+        KernelInfoResult::Program(program) => {
+            assert_eq!(program.as_ptr(), pro_que.program().as_ptr())
+        },
+        _ => panic!("Unexpected 'KernelInfoResult' variant."),
+    }
+    match kernel.info(KernelInfo::Context).unwrap() {
+        KernelInfoResult::Context(context) => {
+            assert_eq!(context.as_ptr(), pro_que.context().as_ptr())
+        },
+        _ => panic!("Unexpected 'KernelInfoResult' variant."),
+    }
+}
+
+#[test]
+fn buffer_mem_info_size() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(1024)
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    match buffer.mem_info(MemInfo::Size).unwrap() {
+        MemInfoResult::Size(size) => assert_eq!(size, 4096),
+        _ => panic!("Unexpected 'MemInfoResult' variant."),
+    }
+
+    assert_eq!(buffer.mem_size().unwrap(), 4096);
+    assert!(buffer.flags().unwrap().contains(flags::MEM_READ_WRITE));
+}
+
+#[test]
+fn image_info_dims() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(1024)
+        .build().unwrap();
+
+    let image = Image::<u8>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::UnormInt8)
+        .image_type(MemObjectType::Image2d)
+        .dims((64, 32))
+        .flags(flags::MEM_READ_WRITE)
+        .queue(pro_que.queue().clone())
+        .build().unwrap();
+
+    match image.info(ImageInfo::Width).unwrap() {
+        ImageInfoResult::Width(width) => assert_eq!(width, 64),
+        _ => panic!("Unexpected 'ImageInfoResult' variant."),
+    }
+
+    match image.info(ImageInfo::Height).unwrap() {
+        ImageInfoResult::Height(height) => assert_eq!(height, 32),
+        _ => panic!("Unexpected 'ImageInfoResult' variant."),
+    }
+
+    match image.info(ImageInfo::ElementSize).unwrap() {
+        ImageInfoResult::ElementSize(size) => assert_eq!(size, 4),
+        _ => panic!("Unexpected 'ImageInfoResult' variant."),
+    }
+
+    assert!(image.row_pitch_bytes().unwrap() >= 64 * 4);
+}
+
+#[test]
+fn program_create_all_kernels() {
+    let src = r#"
+        __kernel void add(__global float* buffer, float addend) {
+            buffer[get_global_id(0)] += addend;
+        }
+
+        __kernel void multiply(__global float* buffer, float coeff) {
+            buffer[get_global_id(0)] *= coeff;
+        }
+
+        __kernel void negate(__global float* buffer) {
+            buffer[get_global_id(0)] = -buffer[get_global_id(0)];
+        }
+    "#;
+
+    let pro_que = ProQue::builder()
+        .src(src)
+        .dims(1024)
+        .build().unwrap();
+
+    let kernels = pro_que.program().create_all_kernels().unwrap();
+    assert_eq!(kernels.len(), 3);
+
+    let mut kernel_names: Vec<String> = kernels.iter().map(|k| {
+        match core::get_kernel_info(k, KernelInfo::FunctionName).unwrap() {
+            KernelInfoResult::FunctionName(name) => name,
+            _ => panic!("Unexpected 'KernelInfoResult' variant."),
+        }
+    }).collect();
+    kernel_names.sort();
+
+    let mut program_names = pro_que.program().kernel_names().unwrap();
+    program_names.sort();
+
+    assert_eq!(kernel_names, program_names);
+}
+
+#[test]
+fn event_info_status() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(1024)
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    let kernel = pro_que.kernel_builder("add")
+        .arg(&buffer)
+        .arg(10.0f32)
+        .build().unwrap();
+
+    let mut event = Event::empty();
+    unsafe { kernel.cmd().enew(&mut event).enq().unwrap(); }
+    event.wait_for().unwrap();
+
+    assert_eq!(event.status().unwrap(), CommandExecutionStatus::Complete);
+    assert_eq!(event.command_type().unwrap(), CommandType::NdrangeKernel);
+}
+
+#[test]
+fn sampler_info_modes() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(1024)
+        .build().unwrap();
+
+    let sampler = Sampler::builder()
+        .context(pro_que.context())
+        .normalized_coords(true)
+        .addressing_mode(AddressingMode::ClampToEdge)
+        .filter_mode(FilterMode::Linear)
+        .build().unwrap();
+
+    assert!(sampler.normalized_coords().unwrap());
+    assert_eq!(sampler.addressing_mode().unwrap(), AddressingMode::ClampToEdge);
+    assert_eq!(sampler.filter_mode().unwrap(), FilterMode::Linear);
+
+    assert!(Sampler::builder().build().is_err());
+}
+
+#[test]
+fn kernel_arg_info_names() {
+    let src = r#"
+        __kernel void add_to(__global int const* in, int addend, __global int* out) {
+            out[get_global_id(0)] = in[get_global_id(0)] + addend;
+        }
+    "#;
+
+    let mut prog_bldr = Program::builder();
+    prog_bldr.src(src).cmplr_opt("-cl-kernel-arg-info");
+
+    let pro_que = ProQue::builder()
+        .prog_bldr(prog_bldr)
+        .dims(1024)
+        .build().unwrap();
+
+    if pro_que.queue().device().version().unwrap() < [1, 2].into() {
+        println!("Skipping 'tests::info::kernel_arg_info_names': \
+            OpenCL 1.2 or higher is required.");
+        return;
+    }
+
+    let kernel = pro_que.kernel_builder("add_to")
+        .arg(None::<&Buffer<i32>>)
+        .arg(0i32)
+        .arg(None::<&Buffer<i32>>)
+        .build().unwrap();
+
+    assert_eq!(kernel.arg_names().unwrap(), vec!["in", "addend", "out"]);
+    assert_eq!(kernel.named_arg_idx("out"), Some(2));
+
+    match kernel.arg_info(0, KernelArgInfo::AddressQualifier).unwrap() {
+        KernelArgInfoResult::AddressQualifier(aq) =>
+            assert_eq!(aq, KernelArgAddressQualifier::Global),
+        _ => unreachable!(),
+    }
+
+    match kernel.arg_info(1, KernelArgInfo::AddressQualifier).unwrap() {
+        KernelArgInfoResult::AddressQualifier(aq) =>
+            assert_eq!(aq, KernelArgAddressQualifier::Private),
+        _ => unreachable!(),
+    }
+
+    match kernel.arg_info(1, KernelArgInfo::TypeName).unwrap() {
+        KernelArgInfoResult::TypeName(ty_name) => assert_eq!(ty_name, "int"),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn device_summary() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(1024)
+        .build().unwrap();
+
+    let device = pro_que.queue().device();
+    let summary = device.summary().unwrap();
+
+    assert!(summary.max_compute_units > 0);
+    assert!(summary.to_string().contains(&device.name().unwrap()));
+}
+
+#[test]
+fn reference_counts() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(1024)
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    let count = buffer.reference_count().unwrap();
+
+    let buffer_clone = buffer.clone();
+    assert_eq!(buffer.reference_count().unwrap(), count + 1);
+    drop(buffer_clone);
+    assert_eq!(buffer.reference_count().unwrap(), count);
+
+    assert!(pro_que.context().reference_count().unwrap() >= 1);
+    assert!(pro_que.queue().reference_count().unwrap() >= 1);
+    assert!(pro_que.program().reference_count().unwrap() >= 1);
+
+    let kernel = pro_que.kernel_builder("add")
+        .arg(&buffer)
+        .arg(1.0f32)
+        .build().unwrap();
+    assert!(kernel.reference_count().unwrap() >= 1);
+
+    let mut event = Event::empty();
+    unsafe { kernel.cmd().enew(&mut event).enq().unwrap(); }
+    assert!(event.reference_count().unwrap() >= 1);
+    event.wait_for().unwrap();
+}
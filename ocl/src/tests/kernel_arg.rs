@@ -143,4 +143,285 @@ fn kernel_arg_float_size() {
             None => panic!("{}", err),
         }
     };
-}
\ No newline at end of file
+}
+/// Ensure that enqueuing a kernel with an unset argument returns a
+/// recoverable status code.
+#[test]
+fn kernel_arg_unset_status_code() {
+    use core::Status;
+
+    let src = r#"__kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }"#;
+    let pq = ProQue::builder().src(src).dims(1024).build().unwrap();
+    let kernel = pq.kernel_builder("add")
+        .arg(None::<&Buffer<f32>>)
+        .arg(10.0f32)
+        .build().unwrap();
+
+    match unsafe { kernel.enq() } {
+        Ok(_) => panic!("Kernel with an unset argument incorrectly enqueued."),
+        Err(err) => assert_eq!(err.status_code(), Some(Status::CL_INVALID_KERNEL_ARGS as i32)),
+    }
+}
+
+/// Ensure that arguments can be swapped between enqueues, by index and by
+/// the name used in the kernel source.
+#[test]
+fn kernel_arg_swap_between_enqueues() {
+    let src = r#"__kernel void copy_add(__global float const* src, __global float* dst) {
+        dst[get_global_id(0)] += src[get_global_id(0)];
+    }"#;
+    let pq = ProQue::builder().src(src).dims(1024).build().unwrap();
+    let src_a = pq.buffer_builder::<f32>().fill_val(1.0f32).build().unwrap();
+    let src_b = pq.buffer_builder::<f32>().fill_val(10.0f32).build().unwrap();
+    let dst = pq.buffer_builder::<f32>().fill_val(0.0f32).build().unwrap();
+
+    let kernel = pq.kernel_builder("copy_add")
+        .arg(&src_a)
+        .arg(&dst)
+        .build().unwrap();
+
+    unsafe { kernel.enq().unwrap(); }
+
+    kernel.set_arg(0, &src_b).unwrap();
+    unsafe { kernel.enq().unwrap(); }
+
+    let mut vec = vec![0.0f32; dst.len()];
+    dst.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 11.0));
+
+    // Argument names are only available on some platforms:
+    if kernel.named_arg_idx("src").is_some() {
+        kernel.set_arg("src", &src_a).unwrap();
+        unsafe { kernel.enq().unwrap(); }
+        dst.read(&mut vec).enq().unwrap();
+        assert!(vec.iter().all(|&v| v == 12.0));
+    }
+}
+
+/// Ensure that arguments can be set using the names from the kernel source
+/// without having declared them using the builder.
+#[test]
+fn kernel_arg_set_by_src_name() {
+    use standard::Program;
+
+    let src = r#"__kernel void add(__global int const* in, int addend, __global int* out) {
+        out[get_global_id(0)] = in[get_global_id(0)] + addend;
+    }"#;
+
+    let mut prog_bldr = Program::builder();
+    prog_bldr.src(src).cmplr_opt("-cl-kernel-arg-info");
+    let pq = ProQue::builder().prog_bldr(prog_bldr).dims(1024).build().unwrap();
+
+    if pq.queue().device().version().unwrap() < [1, 2].into() {
+        println!("Skipping 'tests::kernel_arg::kernel_arg_set_by_src_name': \
+            OpenCL 1.2 or higher is required.");
+        return;
+    }
+
+    let input = pq.buffer_builder::<i32>().fill_val(50i32).build().unwrap();
+    let output = pq.buffer_builder::<i32>().fill_val(0i32).build().unwrap();
+
+    let kernel = pq.kernel_builder("add")
+        .arg(None::<&Buffer<i32>>)
+        .arg(0i32)
+        .arg(None::<&Buffer<i32>>)
+        .build().unwrap();
+
+    kernel.set_arg("in", &input).unwrap();
+    kernel.set_arg("addend", 100i32).unwrap();
+    kernel.set_arg("out", &output).unwrap();
+    assert!(kernel.set_arg("nonexistent", 0i32).is_err());
+
+    unsafe { kernel.enq().unwrap(); }
+
+    let mut vec = vec![0i32; output.len()];
+    output.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 150));
+}
+
+/// Ensure that kernels created with `::clone_fresh` have independent
+/// argument state and can be dispatched concurrently.
+#[test]
+fn kernel_clone_fresh_concurrent() {
+    let src = r#"__kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }"#;
+    let pq = ProQue::builder().src(src).dims(1024).build().unwrap();
+    let buf_a = pq.buffer_builder::<f32>().fill_val(0.0f32).build().unwrap();
+    let buf_b = pq.buffer_builder::<f32>().fill_val(0.0f32).build().unwrap();
+
+    let kern_a = pq.kernel_builder("add")
+        .arg(&buf_a)
+        .arg(1.0f32)
+        .build().unwrap();
+
+    let kern_b = kern_a.clone_fresh().unwrap();
+    kern_b.set_arg(0, &buf_b).unwrap();
+    kern_b.set_arg(1, 2.0f32).unwrap();
+
+    let threads: Vec<_> = vec![kern_a, kern_b].into_iter().map(|kern| {
+        thread::spawn(move || {
+            for _ in 0..8 {
+                unsafe { kern.enq().unwrap(); }
+            }
+            kern.default_queue().unwrap().finish().unwrap();
+        })
+    }).collect();
+
+    for th in threads { th.join().unwrap(); }
+
+    let mut vec = vec![0.0f32; buf_a.len()];
+    buf_a.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 8.0));
+    buf_b.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 16.0));
+}
+
+/// Ensure that binding a value of the wrong type to an argument is rejected
+/// with a descriptive error when argument info is available.
+#[test]
+fn kernel_arg_type_mismatch() {
+    use standard::Program;
+
+    let mut prog_bldr = Program::builder();
+    prog_bldr.src(SRC_0).cmplr_opt("-cl-kernel-arg-info");
+    let pq = ProQue::builder().prog_bldr(prog_bldr).dims(1024).build().unwrap();
+
+    if pq.queue().device().version().unwrap() < [1, 2].into() {
+        println!("Skipping 'tests::kernel_arg::kernel_arg_type_mismatch': \
+            OpenCL 1.2 or higher is required.");
+        return;
+    }
+
+    let buffer = pq.buffer_builder::<f32>().fill_val(0.0f32).build().unwrap();
+    let int_buffer = pq.buffer_builder::<i32>().fill_val(0i32).build().unwrap();
+
+    let kernel = pq.kernel_builder("add")
+        .arg(&buffer)
+        .arg(10.0f32)
+        .build().unwrap();
+
+    // Argument info (and therefore type checking) is only available on some
+    // platforms:
+    if kernel.named_arg_idx("addend").is_none() {
+        println!("Skipping 'tests::kernel_arg::kernel_arg_type_mismatch': \
+            Kernel argument info is unavailable.");
+        return;
+    }
+
+    let err = kernel.set_arg("addend", 10i32).unwrap_err();
+    let msg = err.to_string();
+    assert!(msg.contains("Kernel argument type mismatch"), "{}", msg);
+    assert!(msg.contains("'addend'"), "{}", msg);
+    assert!(msg.contains("float"), "{}", msg);
+
+    assert!(kernel.set_arg(0, &int_buffer).is_err());
+    assert!(pq.kernel_builder("add").arg(&int_buffer).arg(10.0f32).build().is_err());
+
+    // Correctly typed arguments are still accepted:
+    kernel.set_arg("addend", 5.0f32).unwrap();
+    kernel.set_arg(0, &buffer).unwrap();
+}
+
+#[test]
+fn kernel_arg_local_runtime_sized() {
+    use core::{DeviceInfo, DeviceInfoResult};
+
+    static SRC: &'static str = r#"
+        __kernel void group_sum(__global float const* src, __global float* sums,
+                __local float* scratch)
+        {
+            uint lid = get_local_id(0);
+            scratch[lid] = src[get_global_id(0)];
+            barrier(CLK_LOCAL_MEM_FENCE);
+
+            if (lid == 0) {
+                float sum = 0.0f;
+                for (uint i = 0; i < get_local_size(0); i++) {
+                    sum += scratch[i];
+                }
+                sums[get_group_id(0)] = sum;
+            }
+        }
+    "#;
+
+    const LEN: usize = 1024;
+
+    let pq = ProQue::builder().src(SRC).dims(LEN).build().unwrap();
+    let src_vec: Vec<f32> = (0..LEN).map(|i| (i % 7) as f32).collect();
+    let src = pq.buffer_builder::<f32>().copy_host_slice(&src_vec).build().unwrap();
+    let sums = pq.buffer_builder::<f32>().fill_val(0.0f32).build().unwrap();
+
+    let kernel = pq.kernel_builder("group_sum")
+        .arg(&src)
+        .arg(&sums)
+        .arg_local::<f32>(1)
+        .build().unwrap();
+
+    // Size the scratch space to a different local work size each enqueue:
+    for &lws in &[8usize, 16, 32] {
+        kernel.set_arg_local::<f32, _>(2, lws).unwrap();
+        unsafe { kernel.cmd().local_work_size(lws).enq().unwrap(); }
+
+        let mut sums_vec = vec![0.0f32; LEN / lws];
+        sums.read(&mut sums_vec).len(LEN / lws).enq().unwrap();
+
+        for (group, &sum) in sums_vec.iter().enumerate() {
+            let expected: f32 = src_vec[group * lws..(group + 1) * lws].iter().sum();
+            assert_eq!(sum, expected, "lws: {}, group: {}", lws, group);
+        }
+    }
+
+    // Allocations larger than the device's local memory are rejected:
+    let local_mem_size = match pq.queue().device().info(DeviceInfo::LocalMemSize).unwrap() {
+        DeviceInfoResult::LocalMemSize(s) => s as usize,
+        _ => unreachable!(),
+    };
+    let err = kernel.set_arg_local::<f32, _>(2, local_mem_size).unwrap_err();
+    assert!(err.to_string().contains("exceeds the local memory size"), "{}", err);
+}
+
+#[test]
+fn kernel_arg_struct() {
+    static SRC: &'static str = r#"
+        typedef struct Params {
+            float a;
+            int b;
+        } Params;
+
+        __kernel void apply(__global float* buffer, Params params) {
+            buffer[get_global_id(0)] = params.a * (float)params.b;
+        }
+    "#;
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug)]
+    struct Params {
+        a: f32,
+        b: i32,
+    }
+
+    let pq = ProQue::builder().src(SRC).dims(1024).build().unwrap();
+    let buffer = pq.buffer_builder::<f32>().fill_val(0.0f32).build().unwrap();
+
+    // `Params` is `#[repr(C)]` plain data without padding:
+    let kernel = unsafe {
+        pq.kernel_builder("apply")
+            .arg(&buffer)
+            .arg_struct(Params { a: 1.5, b: 4 })
+            .build().unwrap()
+    };
+
+    let mut vec = vec![0.0f32; buffer.len()];
+    unsafe { kernel.enq().unwrap(); }
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 6.0), "{}", vec[0]);
+
+    // Both fields must be read from the re-set value:
+    unsafe { kernel.set_arg_struct(1, &Params { a: -2.0, b: 3 }).unwrap(); }
+    unsafe { kernel.enq().unwrap(); }
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == -6.0), "{}", vec[0]);
+}
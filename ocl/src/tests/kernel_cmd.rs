@@ -0,0 +1,419 @@
+//! Kernel enqueue commands.
+
+use core::{DeviceInfo, DeviceInfoResult};
+use standard::{ProQue, Buffer, Event, SpatialDims, Kernel, Queue};
+
+static SRC: &'static str = r#"
+    __kernel void finalize(__global int const* partials, uint len, __global int* total) {
+        int sum = 0;
+        for (uint i = 0; i < len; i++) {
+            sum += partials[i];
+        }
+        total[0] = sum + (int)get_global_size(0) - 1;
+    }
+
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+
+    __kernel void mark_tile(__global uint* hits, __global uint* offsets) {
+        hits[get_global_id(0)] += 1;
+        offsets[get_global_id(0)] = (uint)get_global_offset(0);
+    }
+
+    __kernel void add_bounded(__global float* buffer, uint len, float addend,
+            __global uint* global_size)
+    {
+        if (get_global_id(0) == 0) {
+            global_size[0] = (uint)get_global_size(0);
+        }
+        if (get_global_id(0) >= len) {
+            return;
+        }
+        buffer[get_global_id(0)] += addend;
+    }
+
+    __kernel void record_local_size(__global uint* local_size) {
+        if (get_global_id(0) == 0) {
+            local_size[0] = (uint)get_local_size(0);
+        }
+    }
+
+    __kernel void linear_idx(__global uint* buffer) {
+        size_t idx = get_global_id(0) + get_global_size(0) *
+            (get_global_id(1) + get_global_size(1) * get_global_id(2));
+        buffer[idx] = get_work_dim() * 1000 + (uint)idx;
+    }
+"#;
+
+const LEN: usize = 64;
+
+
+/// Runs a reduction finalization step as a single work-item.
+#[test]
+fn kernel_single_task() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(LEN)
+        .build().unwrap();
+
+    let partials = Buffer::<i32>::builder().queue(pro_que.queue().clone()).len(LEN)
+        .fill_val(3i32).build().unwrap();
+    let total = Buffer::<i32>::builder().queue(pro_que.queue().clone()).len(1)
+        .fill_val(0i32).build().unwrap();
+
+    // The default work size (`LEN`) is overridden:
+    let kernel = pro_que.kernel_builder("finalize")
+        .arg(&partials)
+        .arg(LEN as u32)
+        .arg(&total)
+        .build().unwrap();
+
+    let mut event = Event::empty();
+    unsafe { kernel.cmd().single_task().enew(&mut event).enq().unwrap(); }
+    event.wait().unwrap();
+
+    let mut result = vec![0i32];
+    total.read(&mut result).enq().unwrap();
+    assert_eq!(result[0], 3 * LEN as i32);
+}
+
+
+/// Ensures that an oversized local work size is caught before enqueuing and
+/// reported with a descriptive error.
+#[test]
+fn kernel_lws_oversized() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(LEN)
+        .build().unwrap();
+
+    let device = pro_que.queue().device();
+    let max_item_size_0 = match device.info(DeviceInfo::MaxWorkItemSizes).unwrap() {
+        DeviceInfoResult::MaxWorkItemSizes(s) => s[0],
+        _ => unreachable!(),
+    };
+
+    let kernel = pro_que.kernel_builder("add")
+        .arg(None::<&Buffer<f32>>)
+        .arg(1.0f32)
+        .build().unwrap();
+    let max_wg_size = kernel.max_wg_size(device).unwrap();
+
+    // Exceeds the maximum work item size of the first dimension:
+    let lws = max_item_size_0 * 2;
+    let err = unsafe { kernel.cmd().global_work_size(lws).local_work_size(lws).enq() }
+        .unwrap_err();
+    let msg = err.to_string();
+    assert!(msg.contains("dimension 0"), "Unexpected error: {}", msg);
+
+    // Exceeds the maximum work group size of the kernel:
+    if max_wg_size < max_item_size_0 {
+        let lws = max_wg_size + 1;
+        let err = unsafe { kernel.cmd().global_work_size(lws).local_work_size(lws).enq() }
+            .unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("maximum work group size"), "Unexpected error: {}", msg);
+    }
+
+    // A local size of two dimensions, each within limits, exceeding the
+    // maximum work group size in total:
+    let side = (max_wg_size as f64).sqrt() as usize + 1;
+    if side <= max_item_size_0 {
+        let err = unsafe {
+            kernel.cmd().global_work_size((side, side)).local_work_size((side, side)).enq()
+        }.unwrap_err();
+        assert!(err.to_string().contains("maximum work group size"));
+    }
+}
+
+
+/// Pads a global work size of 1000 to the next multiple of a local work
+/// size of 64 and ensures that work items beyond the bound do nothing.
+#[test]
+fn kernel_gws_padded() {
+    const N: usize = 1000;
+    const LWS: usize = 64;
+    const PADDED: usize = 1024;
+
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(PADDED)
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    let global_size = Buffer::<u32>::builder().queue(pro_que.queue().clone()).len(1)
+        .fill_val(0u32).build().unwrap();
+
+    let kernel = pro_que.kernel_builder("add_bounded")
+        .arg(&buffer)
+        .arg(N as u32)
+        .arg(1.0f32)
+        .arg(&global_size)
+        .build().unwrap();
+
+    if kernel.max_wg_size(pro_que.queue().device()).unwrap() < LWS {
+        println!("Skipping 'tests::kernel_cmd::kernel_gws_padded': A maximum work group \
+            size of at least {} is required.", LWS);
+        return;
+    }
+
+    unsafe { kernel.cmd().gws_padded(N).local_work_size(LWS).enq().unwrap(); }
+
+    let mut size = vec![0u32];
+    global_size.read(&mut size).enq().unwrap();
+    assert_eq!(size[0] as usize, PADDED);
+
+    let mut vec = vec![0.0f32; PADDED];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec[..N].iter().all(|&v| v == 1.0));
+    assert!(vec[N..].iter().all(|&v| v == 0.0));
+
+    // Without a local work size, the preferred multiple is used:
+    unsafe { kernel.cmd().gws_padded(N).enq().unwrap(); }
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec[..N].iter().all(|&v| v == 2.0));
+    assert!(vec[N..].iter().all(|&v| v == 0.0));
+}
+
+
+/// Dispatches the same number of work items as one, two, and three
+/// dimensional work sizes.
+#[test]
+fn kernel_dispatch_spatial_dims() {
+    let dims_list: [SpatialDims; 3] = [LEN.into(), (8usize, 8usize).into(), [4usize, 4, 4].into()];
+
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(LEN)
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<u32>().unwrap();
+
+    let kernel = pro_que.kernel_builder("linear_idx")
+        .arg(&buffer)
+        .build().unwrap();
+
+    for (i, dims) in dims_list.iter().enumerate() {
+        assert_eq!(dims.dim_count() as usize, i + 1);
+        assert_eq!(dims.to_len(), LEN);
+
+        unsafe { kernel.cmd().gws(dims).enq().unwrap(); }
+
+        let mut vec = vec![0u32; LEN];
+        buffer.read(&mut vec).enq().unwrap();
+
+        for (idx, &val) in vec.iter().enumerate() {
+            assert_eq!(val, dims.dim_count() * 1000 + idx as u32);
+        }
+    }
+}
+
+/// Sets a kernel's default queue once after creation then enqueues
+/// repeatedly without specifying a queue.
+#[test]
+fn kernel_set_default_queue() {
+    const ITERS: usize = 8;
+
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(LEN)
+        .build().unwrap();
+
+    let buffer = Buffer::<f32>::builder().queue(pro_que.queue().clone()).len(LEN)
+        .fill_val(0.0f32).build().unwrap();
+
+    let mut kernel = Kernel::builder()
+        .program(pro_que.program())
+        .name("add")
+        .global_work_size(LEN)
+        .arg(&buffer)
+        .arg(1.0f32)
+        .build().unwrap();
+
+    assert!(kernel.default_queue().is_none());
+    assert!(unsafe { kernel.enq() }.is_err());
+
+    kernel.set_default_queue(pro_que.queue().clone());
+    assert_eq!(kernel.default_queue().unwrap().as_ptr(), pro_que.queue().as_ptr());
+
+    for _ in 0..ITERS {
+        unsafe { kernel.enq().unwrap(); }
+    }
+
+    let mut vec = vec![0.0f32; LEN];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == ITERS as f32));
+}
+
+
+/// Chains two kernels enqueued on separate queues, the second waiting on the
+/// event returned by the first.
+#[test]
+fn kernel_enq_enew_chain() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(LEN)
+        .build().unwrap();
+
+    let queue_1 = Queue::new(pro_que.context(), pro_que.queue().device(), None).unwrap();
+
+    let buffer = Buffer::<f32>::builder().queue(pro_que.queue().clone()).len(LEN)
+        .fill_val(0.0f32).build().unwrap();
+
+    let kernel_0 = pro_que.kernel_builder("add")
+        .arg(&buffer)
+        .arg(1.0f32)
+        .build().unwrap();
+
+    let kernel_1 = pro_que.kernel_builder("add")
+        .arg(&buffer)
+        .arg(10.0f32)
+        .build().unwrap();
+
+    let event_0 = unsafe { kernel_0.cmd().enq_enew().unwrap() };
+    let event_1 = unsafe { kernel_1.cmd().queue(&queue_1).ewait(&event_0).enq_enew().unwrap() };
+    event_1.wait_for().unwrap();
+    assert!(event_0.is_complete().unwrap());
+
+    let mut vec = vec![0.0f32; LEN];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 11.0));
+
+    // Combining with `::enew` is an error:
+    let mut event = Event::empty();
+    assert!(unsafe { kernel_0.cmd().enew(&mut event).enq_enew().is_err() });
+}
+
+#[test]
+fn kernel_enq_tiled() {
+    const TILE: usize = 256;
+
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(LEN)
+        .build().unwrap();
+
+    let hits = pro_que.buffer_builder::<u32>().len(1024).fill_val(0u32).build().unwrap();
+    let offsets = pro_que.buffer_builder::<u32>().len(1024).fill_val(0u32).build().unwrap();
+
+    let kernel = pro_que.kernel_builder("mark_tile")
+        .arg(&hits)
+        .arg(&offsets)
+        .build().unwrap();
+
+    let events = unsafe { kernel.cmd().enq_tiled(1024, TILE).unwrap() };
+    assert_eq!(events.len(), 4);
+    events.wait_for().unwrap();
+
+    let mut hits_vec = vec![0u32; 1024];
+    let mut offsets_vec = vec![0u32; 1024];
+    hits.read(&mut hits_vec).enq().unwrap();
+    offsets.read(&mut offsets_vec).enq().unwrap();
+
+    for i in 0..1024 {
+        assert_eq!(hits_vec[i], 1, "idx: {}", i);
+        assert_eq!(offsets_vec[i] as usize, (i / TILE) * TILE, "idx: {}", i);
+    }
+
+    // A domain which is not a multiple of the tile size truncates the last
+    // tile:
+    let events = unsafe { kernel.cmd().enq_tiled(1000, TILE).unwrap() };
+    assert_eq!(events.len(), 4);
+    hits.read(&mut hits_vec).enq().unwrap();
+
+    for i in 0..1024 {
+        assert_eq!(hits_vec[i], if i < 1000 { 2 } else { 1 }, "idx: {}", i);
+    }
+
+    // Mismatched dimensions are an error:
+    assert!(unsafe { kernel.cmd().enq_tiled([32, 32], TILE).is_err() });
+
+    // With a local work size, tiles and the domain must consist of whole
+    // work groups (the 1000 element domain would leave a partial edge tile):
+    let events = unsafe { kernel.cmd().lws(64).enq_tiled(1024, TILE).unwrap() };
+    assert_eq!(events.len(), 4);
+    assert!(unsafe { kernel.cmd().lws(64).enq_tiled(1000, TILE).is_err() });
+    assert!(unsafe { kernel.cmd().lws(64).enq_tiled(1024, 96).is_err() });
+    pro_que.queue().finish().unwrap();
+    hits.read(&mut hits_vec).enq().unwrap();
+
+    for i in 0..1024 {
+        assert_eq!(hits_vec[i], if i < 1000 { 3 } else { 2 }, "idx: {}", i);
+    }
+}
+
+/// Ensures that a zero-sized global work range is rejected before reaching
+/// the driver.
+#[test]
+fn kernel_gws_zero() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(LEN)
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    let kernel = pro_que.kernel_builder("add")
+        .arg(&buffer)
+        .arg(1.0f32)
+        .build().unwrap();
+
+    for &gws in &[SpatialDims::One(0), SpatialDims::Two(LEN, 0), SpatialDims::Three(1, 0, 1)] {
+        let err = unsafe { kernel.cmd().global_work_size(gws).enq() }.unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("cannot be zero"), "Unexpected error: {}", msg);
+    }
+
+    // The default global work size is unaffected:
+    unsafe { kernel.enq().unwrap(); }
+}
+
+/// Ensures that an automatically selected local work size evenly divides
+/// the global work size and does not exceed the kernel's maximum.
+#[test]
+fn kernel_lws_auto() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(LEN)
+        .build().unwrap();
+
+    let local_size = Buffer::<u32>::builder()
+        .queue(pro_que.queue().clone())
+        .len(1)
+        .fill_val(0u32)
+        .build().unwrap();
+
+    let kernel = pro_que.kernel_builder("record_local_size")
+        .arg(&local_size)
+        .build().unwrap();
+
+    let device = pro_que.queue().device();
+    let max_wg_size = kernel.max_wg_size(device).unwrap();
+    let multiple = kernel.preferred_wg_size_multiple(device).unwrap();
+    let mut vec = vec![0u32; 1];
+
+    for &gws in &[1024usize, 1000, 997, LEN] {
+        unsafe { kernel.cmd().global_work_size(gws).lws_auto().enq().unwrap(); }
+        local_size.read(&mut vec).enq().unwrap();
+        let lws = vec[0] as usize;
+
+        assert!(lws > 0 && lws <= max_wg_size, "gws: {}, lws: {}", gws, lws);
+        assert_eq!(gws % lws, 0, "gws: {}, lws: {}", gws, lws);
+
+        if gws % multiple == 0 && multiple <= max_wg_size {
+            assert_eq!(lws, multiple);
+        }
+    }
+
+    // Padded global work sizes always use the preferred multiple:
+    if multiple <= max_wg_size {
+        unsafe { kernel.cmd().gws_padded(1000).lws_auto().enq().unwrap(); }
+        local_size.read(&mut vec).enq().unwrap();
+        assert_eq!(vec[0] as usize, multiple);
+    }
+
+    // An explicit local work size is overridden:
+    unsafe { kernel.cmd().global_work_size(997).local_work_size(2).lws_auto().enq().unwrap(); }
+    local_size.read(&mut vec).enq().unwrap();
+    assert_eq!(997 % vec[0], 0);
+}
@@ -14,6 +14,11 @@ pub mod buffer_copy;
 pub mod buffer_ops_rect;
 pub mod image_ops;
 pub mod buffer_fill;
+pub mod buffer_map;
+pub mod buffer_chunks;
+pub mod buffer_pool;
+pub mod buffer_swap;
+pub mod buffer_flags;
 pub mod clear_completed;
 pub mod concurrent;
 pub mod kernel_arg;
@@ -21,6 +26,26 @@ pub mod vector_types;
 pub mod context_props;
 pub mod async;
 pub mod buffer_sink_stream_cycles;
+pub mod info;
+pub mod queue_cmds;
+pub mod queue_set;
+pub mod event_callback;
+pub mod user_event;
+pub mod buffer_async_rw;
+pub mod program_build;
+pub mod kernel_cmd;
+pub mod task_graph;
+pub mod reduce;
+pub mod clone_retain;
+pub mod device_version_check;
+#[cfg(feature = "opencl_version_2_0")]
+pub mod svm;
+#[cfg(feature = "opencl_version_2_0")]
+pub mod pipe;
+#[cfg(feature = "opencl_version_2_1")]
+pub mod device_timer;
+#[cfg(all(feature = "d3d11_interop", windows))]
+pub mod d3d11_interop;
 
 use self::rand::Rng;
 use core::OclScl;
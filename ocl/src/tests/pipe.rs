@@ -0,0 +1,68 @@
+//! Pipes (OpenCL 2.0+).
+
+use std::mem;
+use standard::{Context, Queue, Program, Kernel, Buffer, Pipe};
+
+static SRC: &'static str = r#"
+    __kernel void produce(__write_only pipe int out) {
+        int val = (int)get_global_id(0);
+        write_pipe(out, &val);
+    }
+
+    __kernel void consume(__read_only pipe int in, __global int* result) {
+        int val = 0;
+        if (read_pipe(in, &val) == 0) {
+            atomic_add(result, val);
+        }
+    }
+"#;
+
+const LEN: usize = 1 << 8;
+
+
+/// Streams values from a producer kernel to a consumer kernel through a pipe.
+#[test]
+fn pipe_producer_consumer() {
+    let context = Context::builder().build().unwrap();
+
+    if context.devices().iter().any(|d| d.version().unwrap() < [2, 0].into()) {
+        println!("Skipping 'tests::pipe::pipe_producer_consumer': OpenCL 2.0 or higher is \
+            required.");
+        return;
+    }
+
+    let device = context.devices()[0];
+    let queue = Queue::new(&context, device, None).unwrap();
+    let program = Program::builder().src(SRC).devices(device)
+        .cmplr_opt("-cl-std=CL2.0").build(&context).unwrap();
+
+    let pipe = Pipe::<i32>::new(&context, LEN as u32, None).unwrap();
+    assert_eq!(pipe.packet_size().unwrap() as usize, mem::size_of::<i32>());
+    assert!(pipe.max_packets().unwrap() as usize >= LEN);
+
+    let result = Buffer::<i32>::builder().queue(queue.clone()).len(1).fill_val(0i32)
+        .build().unwrap();
+
+    let produce = Kernel::builder().program(&program).name("produce").queue(queue.clone())
+        .global_work_size(LEN)
+        .arg(&pipe)
+        .build().unwrap();
+
+    let consume = Kernel::builder().program(&program).name("consume").queue(queue.clone())
+        .global_work_size(LEN)
+        .arg(&pipe)
+        .arg(&result)
+        .build().unwrap();
+
+    // Re-setting by index must also work:
+    consume.set_arg_pipe(0, &pipe).unwrap();
+
+    unsafe {
+        produce.enq().unwrap();
+        consume.enq().unwrap();
+    }
+
+    let mut total = vec![0i32];
+    result.read(&mut total).enq().unwrap();
+    assert_eq!(total[0], (0..LEN as i32).sum::<i32>());
+}
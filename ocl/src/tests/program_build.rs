@@ -0,0 +1,228 @@
+//! Program building and compiler options.
+
+use std::{env, fs, process};
+use core::{ProgramInfo, ProgramInfoResult, ProgramBuildStatus};
+use standard::{Platform, Context, Program, BuildOpts, OptLevel, Queue, Buffer, Kernel};
+
+#[test]
+fn build_opts_string() {
+    let opts = BuildOpts::new()
+        .define("N", 256)
+        .define("SCALE", 0.5f32)
+        .include_dir("kernels/include")
+        .opt_level(OptLevel::MadEnable)
+        .fast_relaxed_math(true)
+        .std("CL1.2");
+
+    let expected = "-D N=256 -D SCALE=0.5 -I kernels/include -cl-mad-enable \
+        -cl-fast-relaxed-math -cl-std=CL1.2";
+    assert_eq!(opts.to_string(), expected);
+
+    let mut prog_bldr = Program::builder();
+    prog_bldr.cmplr_opt("-g").build_opts(opts);
+    assert_eq!(prog_bldr.get_compiler_options().unwrap().to_str().unwrap(),
+        format!("-g {}", expected));
+
+    assert_eq!(BuildOpts::new().opt_level(OptLevel::Default).fast_relaxed_math(false).to_string(), "");
+    assert_eq!(BuildOpts::new().opt_level(OptLevel::Disabled).to_string(), "-cl-opt-disable");
+}
+
+/// Returns the source of a program (empty when created from binaries).
+fn program_source(program: &Program) -> String {
+    match program.info(ProgramInfo::Source).unwrap() {
+        ProgramInfoResult::Source(src) => src,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn program_binary_cache() {
+    let src = r#"
+        __kernel void add(__global float* buffer, float addend) {
+            buffer[get_global_id(0)] += addend;
+        }
+    "#;
+
+    let cache_dir = env::temp_dir().join(format!("ocl_program_binary_cache_{}", process::id()));
+    let _ = fs::remove_dir_all(&cache_dir);
+
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+
+    let build = || {
+        Program::builder()
+            .source(src)
+            .devices(device)
+            .build_opts(BuildOpts::new().define("UNUSED", 1))
+            .cache_dir(&cache_dir)
+            .build(&context)
+            .unwrap()
+    };
+
+    // First build compiles from source and populates the cache:
+    let program_0 = build();
+    assert!(!program_source(&program_0).is_empty());
+
+    if fs::read_dir(&cache_dir).map(|d| d.count()).unwrap_or(0) == 0 {
+        println!("Skipping 'tests::program_build::program_binary_cache': Device provides no \
+            program binaries.");
+        return;
+    }
+
+    // Second build loads the binary (and therefore has no source):
+    let program_1 = build();
+    assert!(program_source(&program_1).is_empty());
+
+    // Changing the options changes the key:
+    let program_2 = Program::builder()
+        .source(src)
+        .devices(device)
+        .cmplr_def("UNUSED", 2)
+        .cache_dir(&cache_dir)
+        .build(&context)
+        .unwrap();
+    assert!(!program_source(&program_2).is_empty());
+    assert_eq!(fs::read_dir(&cache_dir).unwrap().count(), 2);
+
+    // No temporary files are left behind:
+    for entry in fs::read_dir(&cache_dir).unwrap() {
+        let path = entry.unwrap().path();
+        assert_eq!(path.extension().and_then(|ext| ext.to_str()), Some("bin"), "{:?}", path);
+    }
+
+    fs::remove_dir_all(&cache_dir).unwrap();
+}
+
+#[test]
+fn program_binary_cache_unwritable() {
+    let src = r#"
+        __kernel void add(__global float* buffer, float addend) {
+            buffer[get_global_id(0)] += addend;
+        }
+    "#;
+
+    // A regular file in place of the cache directory cannot be written to:
+    let cache_dir = env::temp_dir().join(format!("ocl_program_binary_cache_file_{}",
+        process::id()));
+    fs::write(&cache_dir, b"not a directory").unwrap();
+
+    let context = Context::builder().build().unwrap();
+    let program = Program::builder()
+        .source(src)
+        .devices(context.devices()[0])
+        .cache_dir(&cache_dir)
+        .build(&context);
+    fs::remove_file(&cache_dir).unwrap();
+
+    assert!(!program_source(&program.unwrap()).is_empty());
+}
+
+#[test]
+fn program_build_status_and_log() {
+    let src = r#"
+        __kernel void add(__global float* buffer, float addend) {
+            buffer[get_global_id(0)] += addend;
+        }
+    "#;
+
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+    let program = Program::builder()
+        .source(src)
+        .devices(device)
+        .build_opts(BuildOpts::new().define("N", 4))
+        .build(&context)
+        .unwrap();
+
+    assert_eq!(program.build_status(device).unwrap(), ProgramBuildStatus::Success);
+    // The log may be empty but must be retrievable after a successful build:
+    program.build_log(device).unwrap();
+}
+
+#[test]
+fn program_unload_compiler() {
+    let src = r#"
+        __kernel void add(__global float* buffer, float addend) {
+            buffer[get_global_id(0)] += addend;
+        }
+    "#;
+
+    let platform = Platform::default();
+    let context = Context::builder().platform(platform).build().unwrap();
+    let device = context.devices()[0];
+
+    if device.version().unwrap() < [1, 2].into() {
+        println!("Skipping 'tests::program_build::program_unload_compiler': \
+            OpenCL 1.2+ is required.");
+        return;
+    }
+
+    let build = |unload: bool| {
+        let mut prog_bldr = Program::builder();
+        prog_bldr.source(src).devices(device);
+        if unload { prog_bldr.unload_compiler(); }
+        prog_bldr.build(&context).unwrap()
+    };
+
+    // Unload manually:
+    build(false);
+    platform.unload_compiler().unwrap();
+    let program = build(false);
+    assert_eq!(program.build_status(device).unwrap(), ProgramBuildStatus::Success);
+
+    // Unload after building:
+    build(true);
+    let program = build(false);
+    assert_eq!(program.build_status(device).unwrap(), ProgramBuildStatus::Success);
+}
+
+/// Builds one program for two devices using different options for each and
+/// ensures each device's kernel reflects its own options.
+#[test]
+fn program_device_opts() {
+    let src = r#"
+        __kernel void add(__global int* buffer) {
+            buffer[get_global_id(0)] += ADDEND * SCALE;
+        }
+    "#;
+
+    let context = Context::builder().build().unwrap();
+    let devices = context.devices();
+
+    if devices.len() < 2 {
+        println!("Skipping 'tests::program_build::program_device_opts': \
+            At least two devices within a single context are required.");
+        return;
+    }
+
+    let program = Program::builder()
+        .source(src)
+        .devices(&devices[..2])
+        .build_opts(BuildOpts::new().define("SCALE", 10))
+        .device_opts(devices[0], BuildOpts::new().define("ADDEND", 1))
+        .device_opts(devices[1], BuildOpts::new().define("ADDEND", 2).opt_level(OptLevel::MadEnable))
+        .build(&context).unwrap();
+
+    for (i, &device) in devices[..2].iter().enumerate() {
+        assert_eq!(program.build_status(device).unwrap(), ProgramBuildStatus::Success);
+
+        let queue = Queue::new(&context, device, None).unwrap();
+        let buffer = Buffer::<i32>::builder().queue(queue.clone()).len(64).fill_val(0i32)
+            .build().unwrap();
+        let kernel = Kernel::builder().program(&program).name("add").queue(queue)
+            .global_work_size(64)
+            .arg(&buffer)
+            .build().unwrap();
+        unsafe { kernel.enq().unwrap(); }
+
+        let mut vec = vec![0i32; 64];
+        buffer.read(&mut vec).enq().unwrap();
+        assert!(vec.iter().all(|&v| v == (i as i32 + 1) * 10));
+    }
+
+    // Per-device options are only valid for source builds:
+    let bins: &[&[u8]] = &[&[0u8][..]];
+    assert!(Program::builder().binaries(bins).devices(devices[0])
+        .device_opts(devices[0], BuildOpts::new().define("ADDEND", 1))
+        .build(&context).is_err());
+}
@@ -0,0 +1,537 @@
+//! Tests for queue-level commands (migration, markers, barriers, native
+//! kernels, GL sharing).
+
+extern crate futures_cpupool;
+
+use futures::Future;
+use self::futures_cpupool::CpuPool;
+use flags;
+use core::{self, DeviceInfo, DeviceInfoResult, ProfilingInfo, ProfilingInfoResult};
+use standard::{Context, Queue, Buffer, Program, Kernel, Event, EventList};
+use error::{Result as OclResult};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+
+    __kernel void sum(__global float* dst, __global float const* src) {
+        dst[get_global_id(0)] += src[get_global_id(0)];
+    }
+"#;
+
+const DATASET_SIZE: usize = 1 << 12;
+
+
+#[test]
+fn migrate_between_devices() {
+    let context = Context::builder().build().unwrap();
+    let devices = context.devices();
+
+    if devices.len() < 2 {
+        println!("Skipping 'tests::queue_cmds::migrate_between_devices': \
+            At least two devices within a single context are required.");
+        return;
+    }
+
+    if devices.iter().any(|d| d.version().unwrap() < [1, 2].into()) {
+        println!("Skipping 'tests::queue_cmds::migrate_between_devices': \
+            OpenCL 1.2 or higher is required.");
+        return;
+    }
+
+    let program = Program::builder().src(SRC).devices(&devices).build(&context).unwrap();
+    let queue_src = Queue::new(&context, devices[0], None).unwrap();
+    let queue_dst = Queue::new(&context, devices[1], None).unwrap();
+
+    let buffer = Buffer::<f32>::builder()
+        .queue(queue_src.clone())
+        .len(DATASET_SIZE)
+        .fill_val(5.0f32)
+        .build().unwrap();
+
+    let migrate_event = queue_dst.enqueue_migrate(&[&buffer], flags::MemMigrationFlags::empty(),
+        None::<()>).unwrap();
+
+    let kernel = Kernel::builder()
+        .program(&program)
+        .name("add")
+        .queue(queue_dst.clone())
+        .global_work_size(DATASET_SIZE)
+        .arg(&buffer)
+        .arg(10.0f32)
+        .build().unwrap();
+
+    unsafe { kernel.cmd().ewait(&migrate_event).enq().unwrap(); }
+
+    let mut vec = vec![0.0f32; DATASET_SIZE];
+    buffer.read(&mut vec).queue(&queue_dst).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 15.0f32));
+}
+
+#[test]
+fn barrier_orders_dependent_kernel() {
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+
+    if device.version().unwrap() < [1, 2].into() {
+        println!("Skipping 'tests::queue_cmds::barrier_orders_dependent_kernel': \
+            OpenCL 1.2 or higher is required.");
+        return;
+    }
+
+    let program = Program::builder().src(SRC).devices(device).build(&context).unwrap();
+    let queue = Queue::new(&context, device, None).unwrap();
+
+    let buffer_a = Buffer::<f32>::builder().queue(queue.clone()).len(DATASET_SIZE)
+        .fill_val(0.0f32).build().unwrap();
+    let buffer_b = Buffer::<f32>::builder().queue(queue.clone()).len(DATASET_SIZE)
+        .fill_val(0.0f32).build().unwrap();
+
+    let kern_a = Kernel::builder().program(&program).name("add").queue(queue.clone())
+        .global_work_size(DATASET_SIZE).arg(&buffer_a).arg(1.0f32).build().unwrap();
+    let kern_b = Kernel::builder().program(&program).name("add").queue(queue.clone())
+        .global_work_size(DATASET_SIZE).arg(&buffer_b).arg(2.0f32).build().unwrap();
+    let kern_sum = Kernel::builder().program(&program).name("sum").queue(queue.clone())
+        .global_work_size(DATASET_SIZE).arg(&buffer_a).arg(&buffer_b).build().unwrap();
+
+    // Two independent kernels:
+    let mut independent = EventList::new();
+    unsafe {
+        kern_a.cmd().enew(&mut independent).enq().unwrap();
+        kern_b.cmd().enew(&mut independent).enq().unwrap();
+    }
+
+    // A barrier fencing both, followed by a kernel depending on each:
+    let barrier_event = queue.enqueue_barrier(None::<()>).unwrap();
+    let mut sum_event = Event::empty();
+    unsafe { kern_sum.cmd().enew(&mut sum_event).enq().unwrap(); }
+
+    sum_event.wait_for().unwrap();
+    assert!(barrier_event.is_complete().unwrap());
+    for event in independent.iter() {
+        assert!(event.is_complete().unwrap());
+    }
+
+    let mut vec = vec![0.0f32; DATASET_SIZE];
+    buffer_a.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 3.0f32));
+}
+
+#[test]
+fn event_wait_and_is_complete() {
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+    let program = Program::builder().src(SRC).devices(device).build(&context).unwrap();
+    let queue = Queue::new(&context, device, None).unwrap();
+
+    let buffer = Buffer::<f32>::builder().queue(queue.clone()).len(DATASET_SIZE)
+        .fill_val(0.0f32).build().unwrap();
+    let kernel = Kernel::builder().program(&program).name("add").queue(queue.clone())
+        .global_work_size(DATASET_SIZE).arg(&buffer).arg(1.0f32).build().unwrap();
+
+    let mut event = Event::empty();
+    assert!(event.wait().is_err());
+    assert!(event.is_complete().is_err());
+
+    unsafe { kernel.cmd().enew(&mut event).enq().unwrap(); }
+    event.wait().unwrap();
+    assert!(event.is_complete().unwrap());
+}
+
+#[test]
+fn event_list_collect_and_wait() {
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+    let program = Program::builder().src(SRC).devices(device).build(&context).unwrap();
+    let queue = Queue::new(&context, device, None).unwrap();
+
+    let buffer = Buffer::<f32>::builder().queue(queue.clone()).len(DATASET_SIZE)
+        .fill_val(0.0f32).build().unwrap();
+    let kernel = Kernel::builder().program(&program).name("add").queue(queue.clone())
+        .global_work_size(DATASET_SIZE).arg(&buffer).arg(1.0f32).build().unwrap();
+
+    let events: EventList = (0..5).map(|_| {
+        let mut event = Event::empty();
+        unsafe { kernel.cmd().enew(&mut event).enq().unwrap(); }
+        event
+    }).collect();
+
+    assert_eq!(events.len(), 5);
+    events.wait().unwrap();
+    assert!(events.iter().all(|e| e.is_complete().unwrap()));
+
+    // Extending past eight events moves the list to the heap:
+    let mut all = EventList::from_slice(&events);
+    all.extend(events.iter().cloned());
+    assert_eq!(all.len(), 10);
+    all.wait().unwrap();
+
+    let mut vec = vec![0.0f32; DATASET_SIZE];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 5.0));
+}
+
+#[test]
+fn queue_out_of_order_round_trip() {
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+
+    let supports_ooo = match device.info(DeviceInfo::QueueProperties).unwrap() {
+        DeviceInfoResult::QueueProperties(props) =>
+            props.contains(flags::QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE),
+        _ => unreachable!(),
+    };
+
+    let queue = Queue::new(&context, device, None).unwrap();
+    assert!(!queue.is_out_of_order().unwrap());
+
+    if !supports_ooo {
+        println!("Skipping 'tests::queue_cmds::queue_out_of_order_round_trip': \
+            Out-of-order execution is not supported by the device.");
+        return;
+    }
+
+    let queue = Queue::new(&context, device,
+        Some(flags::QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE)).unwrap();
+    assert!(queue.is_out_of_order().unwrap());
+}
+
+#[test]
+fn native_kernel_increments_counter() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+
+    let supports_native = match device.info(DeviceInfo::ExecutionCapabilities).unwrap() {
+        DeviceInfoResult::ExecutionCapabilities(caps) => caps.contains(flags::EXEC_NATIVE_KERNEL),
+        _ => unreachable!(),
+    };
+
+    if !supports_native {
+        println!("Skipping 'tests::queue_cmds::native_kernel_increments_counter': \
+            Native kernels are not supported by the device.");
+        return;
+    }
+
+    let queue = Queue::new(&context, device, None).unwrap();
+    let buffer = Buffer::<f32>::builder()
+        .queue(queue.clone())
+        .len(DATASET_SIZE)
+        .fill_val(0.0f32)
+        .build().unwrap();
+
+    let counter = Arc::new(AtomicUsize::new(0));
+    let counter_native = counter.clone();
+
+    let native_event = queue.enqueue_native(move || {
+        counter_native.fetch_add(1, Ordering::SeqCst);
+    }, &[&buffer], None::<&Event>).unwrap();
+
+    native_event.wait_for().unwrap();
+    assert_eq!(counter.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn queue_builder_properties() {
+    let context = Context::builder().build().unwrap();
+
+    let queue = Queue::builder().context(&context).build().unwrap();
+    assert!(!queue.properties().unwrap().contains(flags::QUEUE_PROFILING_ENABLE));
+
+    let queue = Queue::builder()
+        .context(&context)
+        .device(context.devices()[0])
+        .properties(flags::QUEUE_PROFILING_ENABLE)
+        .build().unwrap();
+    assert!(queue.properties().unwrap().contains(flags::QUEUE_PROFILING_ENABLE));
+
+    assert!(Queue::builder().build().is_err());
+}
+
+#[test]
+fn queue_device_and_context() {
+    let context = Context::builder().build().unwrap();
+    let context_ptr = context.as_core().as_ptr();
+
+    let queues: Vec<Queue> = context.devices().into_iter().map(|device| {
+        let queue = Queue::new(&context, device, None).unwrap();
+        assert_eq!(queue.device(), device);
+        assert_eq!(queue.context().as_core().as_ptr(), context_ptr);
+        queue
+    }).collect();
+
+    // The recovered context is retained and remains usable after the
+    // original has been dropped:
+    drop(context);
+
+    let data = vec![1.0f32; 64];
+
+    for queue in &queues {
+        let buffer = Buffer::<f32>::builder()
+            .context(&queue.context())
+            .len(data.len())
+            .copy_host_slice(&data)
+            .build().unwrap();
+        let mut vec = vec![0.0f32; buffer.len()];
+        buffer.read(&mut vec).queue(queue).enq().unwrap();
+        assert!(vec.iter().all(|&v| v == 1.0));
+    }
+}
+
+#[test]
+fn queue_profiling_opt_in() {
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+
+    let queue = Queue::new(&context, device, None).unwrap();
+    assert!(!queue.properties().unwrap().contains(flags::QUEUE_PROFILING_ENABLE));
+
+    let queue = Queue::new(&context, device, Some(flags::QUEUE_PROFILING_ENABLE)).unwrap();
+    assert!(queue.properties().unwrap().contains(flags::QUEUE_PROFILING_ENABLE));
+
+    let program = Program::builder().src(SRC).devices(device).build(&context).unwrap();
+    let buffer = Buffer::<f32>::builder()
+        .queue(queue.clone())
+        .len(DATASET_SIZE)
+        .fill_val(0.0f32)
+        .build().unwrap();
+
+    let kernel = Kernel::builder()
+        .program(&program)
+        .name("add")
+        .queue(queue.clone())
+        .global_work_size(DATASET_SIZE)
+        .arg(&buffer)
+        .arg(1.0f32)
+        .build().unwrap();
+
+    let mut event = Event::empty();
+    unsafe { kernel.cmd().enew(&mut event).enq().unwrap(); }
+    event.wait_for().unwrap();
+
+    let start = match event.profiling_info(ProfilingInfo::Start).unwrap() {
+        ProfilingInfoResult::Start(t) => t,
+        _ => unreachable!(),
+    };
+    let end = match event.profiling_info(ProfilingInfo::End).unwrap() {
+        ProfilingInfoResult::End(t) => t,
+        _ => unreachable!(),
+    };
+    assert!(end >= start);
+    assert_eq!(event.profiling_start_end().unwrap(), (start, end));
+}
+
+#[test]
+fn event_list_profiling_summary() {
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+    let queue = Queue::new(&context, device, Some(flags::QUEUE_PROFILING_ENABLE)).unwrap();
+
+    let program = Program::builder().src(SRC).devices(device).build(&context).unwrap();
+    let buffer = Buffer::<f32>::builder()
+        .queue(queue.clone())
+        .len(DATASET_SIZE)
+        .fill_val(0.0f32)
+        .build().unwrap();
+
+    let kernel = Kernel::builder()
+        .program(&program)
+        .name("add")
+        .queue(queue.clone())
+        .global_work_size(DATASET_SIZE)
+        .arg(&buffer)
+        .arg(1.0f32)
+        .build().unwrap();
+
+    assert!(EventList::new().profiling_summary().is_err());
+
+    let mut events = EventList::new();
+    for _ in 0..3 {
+        unsafe { kernel.cmd().enew(&mut events).enq().unwrap(); }
+    }
+    events.wait_for().unwrap();
+
+    let summary = events.profiling_summary().unwrap();
+    assert_eq!(summary.count, 3);
+    assert!(summary.total >= summary.max);
+    assert!(summary.max >= summary.mean && summary.mean >= summary.min);
+    assert!(summary.span >= summary.max);
+}
+
+#[test]
+fn finish_async_on_pool() {
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+
+    if device.version().unwrap() < [1, 2].into() {
+        println!("Skipping 'tests::queue_cmds::finish_async_on_pool': \
+            OpenCL 1.2 or higher is required.");
+        return;
+    }
+
+    let queue = Queue::new(&context, device, None).unwrap();
+    let program = Program::builder().src(SRC).devices(device).build(&context).unwrap();
+    let buffer = Buffer::<f32>::builder()
+        .queue(queue.clone())
+        .len(DATASET_SIZE)
+        .fill_val(0.0f32)
+        .build().unwrap();
+
+    let kernel = Kernel::builder()
+        .program(&program)
+        .name("add")
+        .queue(queue.clone())
+        .global_work_size(DATASET_SIZE)
+        .arg(&buffer)
+        .arg(5.0f32)
+        .build().unwrap();
+
+    unsafe { kernel.enq().unwrap(); }
+
+    let pool = CpuPool::new(1);
+    pool.spawn(queue.finish_async().unwrap()).wait().unwrap();
+
+    let mut vec = vec![0.0f32; DATASET_SIZE];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 5.0));
+}
+
+/// Creating a GL context is outside the scope of these tests. Ensure that
+/// the GL sharing commands reach the implementation and, with an empty
+/// object list on a context not created from a GL context, either succeed
+/// (doing nothing) or fail with `CL_INVALID_CONTEXT`.
+#[cfg(not(feature = "opencl_vendor_mesa"))]
+#[test]
+fn gl_sharing_commands_resolve() {
+    let context = Context::builder().build().unwrap();
+    let queue = Queue::new(&context, context.devices()[0], None).unwrap();
+    let no_objs: &[Buffer<f32>] = &[];
+
+    let results = vec![
+        queue.enqueue_acquire_gl_objects(no_objs, None::<Event>),
+        queue.enqueue_release_gl_objects(no_objs, None::<Event>),
+    ];
+
+    for result in results {
+        match result {
+            Ok(event) => event.wait_for().unwrap(),
+            Err(err) => assert_eq!(err.api_status(), Some(core::Status::CL_INVALID_CONTEXT),
+                "{}", err),
+        }
+    }
+}
+
+/// Returns an API error with the status `status`.
+fn api_err(status: core::Status) -> ::Error {
+    use core::error::{ApiError, Error as OclCoreError};
+    OclCoreError::from(ApiError::new(status as i32, "clEnqueueNDRangeKernel", None::<String>))
+        .into()
+}
+
+#[test]
+fn queue_retry_transient() {
+    use std::cell::Cell;
+    use std::time::Duration;
+    use core::Status;
+
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+    let queue = Queue::new(&context, device, None).unwrap()
+        .with_retry(3, Duration::from_millis(1));
+    assert_eq!(queue.retry_policy().unwrap().attempts(), 3);
+
+    let calls = Cell::new(0);
+
+    // Transient errors are retried (finishing the queue in between):
+    let res = queue.retry(|| {
+        calls.set(calls.get() + 1);
+        if calls.get() <= 2 { Err(api_err(Status::CL_OUT_OF_RESOURCES)) } else { Ok(7) }
+    });
+    assert_eq!(res.unwrap(), 7);
+    assert_eq!(calls.get(), 3);
+
+    // Non-transient errors are returned immediately:
+    calls.set(0);
+    let err = queue.retry(|| -> OclResult<()> {
+        calls.set(calls.get() + 1);
+        Err(api_err(Status::CL_INVALID_KERNEL_ARGS))
+    }).unwrap_err();
+    assert_eq!(err.api_status(), Some(Status::CL_INVALID_KERNEL_ARGS));
+    assert_eq!(calls.get(), 1);
+
+    // Retries are bounded:
+    calls.set(0);
+    let err = queue.retry(|| -> OclResult<()> {
+        calls.set(calls.get() + 1);
+        Err(api_err(Status::CL_MEM_OBJECT_ALLOCATION_FAILURE))
+    }).unwrap_err();
+    assert_eq!(err.api_status(), Some(Status::CL_MEM_OBJECT_ALLOCATION_FAILURE));
+    assert_eq!(calls.get(), 4);
+
+    // Without a policy, operations are attempted once:
+    let plain_queue = Queue::new(&context, device, None).unwrap();
+    calls.set(0);
+    assert!(plain_queue.retry(|| -> OclResult<()> {
+        calls.set(calls.get() + 1);
+        Err(api_err(Status::CL_OUT_OF_RESOURCES))
+    }).is_err());
+    assert_eq!(calls.get(), 1);
+
+    // Actual enqueues pass straight through:
+    let buffer = Buffer::<f32>::builder().queue(queue.clone()).len(64)
+        .fill_val(0.0f32).build().unwrap();
+    queue.retry(|| buffer.cmd().fill(1.0f32, None).enq()).unwrap();
+    let mut vec = vec![0.0f32; 64];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 1.0));
+}
+
+#[test]
+fn frame_marker_after_prior_work() {
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+
+    if device.version().unwrap() < [1, 2].into() {
+        println!("Skipping 'tests::queue_cmds::frame_marker_after_prior_work': \
+            OpenCL 1.2 or higher is required.");
+        return;
+    }
+
+    let queue = Queue::new(&context, device, None).unwrap();
+    let program = Program::builder().src(SRC).devices(device).build(&context).unwrap();
+    let buffer = Buffer::<f32>::builder()
+        .queue(queue.clone())
+        .len(DATASET_SIZE)
+        .fill_val(0.0f32)
+        .build().unwrap();
+
+    let kernel = Kernel::builder()
+        .program(&program)
+        .name("add")
+        .queue(queue.clone())
+        .global_work_size(DATASET_SIZE)
+        .arg(&buffer)
+        .arg(5.0f32)
+        .build().unwrap();
+
+    // Hold back the kernel until the user event is set:
+    let gate = Event::user(&context).unwrap();
+    unsafe { kernel.cmd().ewait(&gate).enq().unwrap(); }
+
+    let (frame_done, frame_future) = queue.frame_marker().unwrap();
+    assert!(!frame_done.is_complete().unwrap());
+
+    gate.set_complete().unwrap();
+
+    let pool = CpuPool::new(1);
+    pool.spawn(frame_future).wait().unwrap();
+    assert!(frame_done.is_complete().unwrap());
+
+    // The marker event can also be chained into later commands:
+    let mut vec = vec![0.0f32; DATASET_SIZE];
+    buffer.read(&mut vec).ewait(&frame_done).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 5.0));
+}
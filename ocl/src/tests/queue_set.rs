@@ -0,0 +1,89 @@
+//! Tests for distributing commands across the queues of a `QueueSet`.
+
+use standard::{Platform, Device, Context, Queue, Program, Kernel, Buffer, Event, QueueSet,
+    QueueSetPolicy};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+const DATASET_SIZE: usize = 1 << 10;
+const DISPATCHES_PER_QUEUE: usize = 3;
+
+/// Dispatches kernels (held back by a user event) using a least-busy policy
+/// and verifies that they are spread evenly across every device's queue.
+#[test]
+fn queue_set_distribution() {
+    let platform = Platform::default();
+    let devices = Device::list_all(&platform).unwrap();
+    let context = Context::builder().platform(platform).devices(&devices).build().unwrap();
+
+    let mut queues = QueueSet::new(&context, None).unwrap()
+        .with_policy(QueueSetPolicy::LeastBusy);
+    let queue_count = queues.queues().len();
+    assert_eq!(queue_count, devices.len());
+
+    let program = Program::builder().src(SRC).devices(&devices).build(&context).unwrap();
+    let gate = Event::user(&context).unwrap();
+    let mut buffers = Vec::new();
+
+    for _ in 0..(queue_count * DISPATCHES_PER_QUEUE) {
+        let queue = queues.next().unwrap().clone();
+        let buffer = Buffer::<f32>::builder()
+            .queue(queue.clone())
+            .len(DATASET_SIZE)
+            .fill_val(0.0f32)
+            .build().unwrap();
+
+        let kernel = Kernel::builder()
+            .program(&program)
+            .name("add")
+            .queue(queue)
+            .global_work_size(DATASET_SIZE)
+            .arg(&buffer)
+            .arg(1.0f32)
+            .build().unwrap();
+
+        let mut event = Event::empty();
+        unsafe { kernel.cmd().ewait(&gate).enew(&mut event).enq().unwrap(); }
+        queues.track_event(event).unwrap();
+        buffers.push(buffer);
+    }
+
+    assert_eq!(queues.outstanding().unwrap(), vec![DISPATCHES_PER_QUEUE; queue_count]);
+
+    gate.set_complete().unwrap();
+    queues.finish().unwrap();
+    assert_eq!(queues.outstanding().unwrap(), vec![0; queue_count]);
+
+    let mut vec = vec![0.0f32; DATASET_SIZE];
+    for buffer in buffers.iter() {
+        buffer.read(&mut vec).enq().unwrap();
+        assert!(vec.iter().all(|&v| v == 1.0));
+    }
+}
+
+#[test]
+fn queue_set_round_robin() {
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+    let queue_a = Queue::new(&context, device, None).unwrap();
+    let queue_b = Queue::new(&context, device, None).unwrap();
+
+    let mut queues = QueueSet::from_queues(vec![queue_a.clone(), queue_b.clone()]).unwrap();
+    assert_eq!(queues.policy(), QueueSetPolicy::RoundRobin);
+
+    for _ in 0..2 {
+        assert_eq!(queues.next().unwrap().as_core().as_ptr(), queue_a.as_core().as_ptr());
+        assert_eq!(queues.next().unwrap().as_core().as_ptr(), queue_b.as_core().as_ptr());
+    }
+
+    // Events from queues outside of the set are rejected:
+    let other = Queue::new(&context, device, None).unwrap();
+    let marker = other.enqueue_marker(None::<&Event>).unwrap();
+    assert!(queues.track_event(marker).is_err());
+
+    assert!(QueueSet::from_queues(Vec::new()).is_err());
+}
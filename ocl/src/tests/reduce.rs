@@ -0,0 +1,153 @@
+//! Tests `Buffer::reduce`.
+
+use core::{DeviceInfo, DeviceInfoResult};
+use flags;
+use standard::{ProQue, Buffer, Context, Queue};
+use ::ReduceOp;
+
+// Deliberately not a power of two nor a multiple of any work group size.
+const LEN: usize = 100_003;
+
+#[test]
+fn reduce_i32() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims(LEN)
+        .build().unwrap();
+
+    let vec: Vec<i32> = (0..LEN as i32).map(|i| (i % 1000) - 300).collect();
+    let buffer = Buffer::<i32>::builder()
+        .queue(pro_que.queue().clone())
+        .len(LEN)
+        .copy_host_slice(&vec)
+        .build().unwrap();
+
+    assert_eq!(buffer.reduce(None, ReduceOp::Sum).unwrap(), vec.iter().sum::<i32>());
+    assert_eq!(buffer.reduce(None, ReduceOp::Min).unwrap(), -300);
+    assert_eq!(buffer.reduce(None, ReduceOp::Max).unwrap(), 699);
+
+    // Reducing again uses the cached program:
+    assert_eq!(buffer.reduce(Some(pro_que.queue()), ReduceOp::Max).unwrap(), 699);
+
+    // Evicted programs are rebuilt as needed:
+    ReduceOp::evict_programs(pro_que.context());
+    assert_eq!(buffer.reduce(None, ReduceOp::Max).unwrap(), 699);
+    ReduceOp::clear_programs();
+    assert_eq!(buffer.reduce(None, ReduceOp::Min).unwrap(), -300);
+
+    let vec: Vec<i32> = (0..LEN).map(|i| if i % 10_000 == 7 { 2 } else { 1 }).collect();
+    buffer.write(&vec).enq().unwrap();
+    assert_eq!(buffer.reduce(None, ReduceOp::Product).unwrap(), 1 << 10);
+}
+
+#[test]
+fn reduce_f32() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims(LEN)
+        .build().unwrap();
+
+    let vec: Vec<f32> = (0..LEN).map(|i| ((i % 7) as f32) * 0.5 - 1.0).collect();
+    let buffer = Buffer::<f32>::builder()
+        .queue(pro_que.queue().clone())
+        .len(LEN)
+        .copy_host_slice(&vec)
+        .build().unwrap();
+
+    let sum: f64 = vec.iter().map(|&v| v as f64).sum();
+    let reduced_sum = buffer.reduce(None, ReduceOp::Sum).unwrap() as f64;
+    assert!((reduced_sum - sum).abs() < sum.abs() * 1e-4, "{} != {}", reduced_sum, sum);
+    assert_eq!(buffer.reduce(None, ReduceOp::Min).unwrap(), -1.0);
+    assert_eq!(buffer.reduce(None, ReduceOp::Max).unwrap(), 2.0);
+
+    let vec: Vec<f32> = (0..LEN).map(|i| if i % 20_000 == 3 { 0.5 } else { 1.0 }).collect();
+    buffer.write(&vec).enq().unwrap();
+    assert_eq!(buffer.reduce(None, ReduceOp::Product).unwrap(), 1.0 / 32.0);
+}
+
+#[test]
+fn reduce_short_and_unsupported() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims(3)
+        .build().unwrap();
+
+    let buffer = Buffer::<u32>::builder()
+        .queue(pro_que.queue().clone())
+        .len(3)
+        .copy_host_slice(&[4, 9, 2])
+        .build().unwrap();
+
+    assert_eq!(buffer.reduce(None, ReduceOp::Sum).unwrap(), 15);
+    assert_eq!(buffer.reduce(None, ReduceOp::Min).unwrap(), 2);
+    assert_eq!(buffer.reduce(None, ReduceOp::Max).unwrap(), 9);
+    assert_eq!(buffer.reduce(None, ReduceOp::Product).unwrap(), 72);
+
+    let vectors = Buffer::<::prm::Float4>::builder()
+        .queue(pro_que.queue().clone())
+        .len(3)
+        .build().unwrap();
+    assert!(vectors.reduce(None, ReduceOp::Sum).is_err());
+}
+
+#[test]
+fn reduce_out_of_order_queue() {
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+
+    let supports_ooo = match device.info(DeviceInfo::QueueProperties).unwrap() {
+        DeviceInfoResult::QueueProperties(props) =>
+            props.contains(flags::QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE),
+        _ => unreachable!(),
+    };
+
+    if !supports_ooo {
+        println!("Skipping 'tests::reduce::reduce_out_of_order_queue': \
+            Out-of-order execution is not supported by the device.");
+        return;
+    }
+
+    let queue = Queue::new(&context, device,
+        Some(flags::QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE)).unwrap();
+
+    let vec: Vec<i32> = (0..LEN as i32).map(|i| (i % 100) - 50).collect();
+    let buffer = Buffer::<i32>::builder()
+        .queue(queue)
+        .len(LEN)
+        .copy_host_slice(&vec)
+        .build().unwrap();
+
+    // Both passes and the read must be ordered by their events:
+    for _ in 0..4 {
+        assert_eq!(buffer.reduce(None, ReduceOp::Sum).unwrap(), vec.iter().sum::<i32>());
+        assert_eq!(buffer.reduce(None, ReduceOp::Max).unwrap(), 49);
+    }
+}
+
+#[test]
+fn reduce_f64() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims(LEN)
+        .build().unwrap();
+
+    let vec: Vec<f64> = (0..LEN).map(|i| (i % 5) as f64 - 2.0).collect();
+    let buffer = Buffer::<f64>::builder()
+        .queue(pro_que.queue().clone())
+        .len(LEN)
+        .copy_host_slice(&vec)
+        .build().unwrap();
+
+    let supports_fp64 = match pro_que.queue().device().info(DeviceInfo::Extensions).unwrap() {
+        DeviceInfoResult::Extensions(exts) => exts.split_whitespace().any(|e| e == "cl_khr_fp64"),
+        _ => unreachable!(),
+    };
+
+    if supports_fp64 {
+        assert_eq!(buffer.reduce(None, ReduceOp::Min).unwrap(), -2.0);
+        assert_eq!(buffer.reduce(None, ReduceOp::Max).unwrap(), 2.0);
+    } else {
+        let err = buffer.reduce(None, ReduceOp::Min).unwrap_err();
+        assert!(err.to_string().contains("cl_khr_fp64"), "{}", err);
+    }
+}
@@ -0,0 +1,62 @@
+//! Shared virtual memory (OpenCL 2.0+).
+
+use flags;
+use standard::{Context, Queue, Program, Kernel, Buffer};
+
+static SRC: &'static str = r#"
+    __kernel void sum(__global int const* svm, uint len, __global int* result) {
+        int total = 0;
+        for (uint i = 0; i < len; i++) {
+            total += svm[i];
+        }
+        result[0] = total;
+    }
+"#;
+
+const LEN: usize = 1 << 10;
+
+
+/// Fills an SVM vector on the host and sums it within a kernel.
+#[test]
+fn svm_fill_and_sum() {
+    let context = Context::builder().build().unwrap();
+
+    if context.devices().iter().any(|d| d.version().unwrap() < [2, 0].into()) {
+        println!("Skipping 'tests::svm::svm_fill_and_sum': OpenCL 2.0 or higher is required.");
+        return;
+    }
+
+    let device = context.devices()[0];
+    let queue = Queue::new(&context, device, None).unwrap();
+    let program = Program::builder().src(SRC).devices(device)
+        .cmplr_opt("-cl-std=CL2.0").build(&context).unwrap();
+
+    let mut svm = unsafe { context.svm_alloc::<i32>(LEN, flags::MEM_READ_ONLY).unwrap() };
+    assert_eq!(svm.len(), LEN);
+
+    // Coarse-grained buffers must be mapped for host access. Elements are
+    // written through the raw pointer as they are not yet initialized:
+    queue.svm_map(&svm, flags::MAP_WRITE, None::<()>).unwrap();
+    for i in 0..LEN {
+        unsafe { *svm.as_mut_ptr().offset(i as isize) = i as i32; }
+    }
+    unsafe { assert_eq!(svm.as_slice()[LEN - 1], LEN as i32 - 1); }
+    queue.svm_unmap(&svm, None::<()>).unwrap();
+
+    let result = Buffer::<i32>::builder().queue(queue.clone()).len(1).fill_val(0i32)
+        .build().unwrap();
+
+    let kernel = Kernel::builder().program(&program).name("sum").queue(queue.clone())
+        .global_work_size(1)
+        .arg(None::<&Buffer<i32>>)
+        .arg(LEN as u32)
+        .arg(&result)
+        .build().unwrap();
+    kernel.set_arg_svm(0, &svm).unwrap();
+
+    unsafe { kernel.enq().unwrap(); }
+
+    let mut total = vec![0i32];
+    result.read(&mut total).enq().unwrap();
+    assert_eq!(total[0], (0..LEN as i32).sum::<i32>());
+}
@@ -0,0 +1,78 @@
+//! Command dependency graphs.
+
+use standard::{ProQue, TaskGraph};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global int const* src, __global int* dst, int addend) {
+        dst[get_global_id(0)] = src[get_global_id(0)] + addend;
+    }
+"#;
+
+const LEN: usize = 1 << 12;
+const ITERS: i32 = 3;
+
+
+/// Reproduces a cyclical Write -> Kernel -> Read dependency chain over
+/// several iterations, where the write also depends on the previous
+/// iteration's kernel and the kernel on the previous iteration's read.
+#[test]
+fn task_graph_write_kernel_read() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(LEN)
+        .build().unwrap();
+
+    let src_buf = pro_que.create_buffer::<i32>().unwrap();
+    let dst_buf = pro_que.create_buffer::<i32>().unwrap();
+
+    let kernel = pro_que.kernel_builder("add")
+        .arg(&src_buf)
+        .arg(&dst_buf)
+        .arg(100i32)
+        .build().unwrap();
+
+    let mut graph = TaskGraph::new();
+    let write = graph.add_node(&[]).unwrap();
+    let kern = graph.add_node(&[write]).unwrap();
+    let read = graph.add_node(&[kern]).unwrap();
+    graph.add_predecessor(write, kern).unwrap();
+    graph.add_predecessor(kern, read).unwrap();
+
+    assert_eq!(graph.predecessors(kern).unwrap(), &[write, read]);
+    let node_count = graph.len();
+    assert!(graph.add_node(&[node_count]).is_err());
+    assert!(graph.add_predecessor(write, 99).is_err());
+
+    // Neither predecessor of the write has been enqueued yet:
+    assert_eq!(graph.wait_list(write).unwrap().len(), 0);
+
+    for i in 0..ITERS {
+        let src_vec = vec![i; LEN];
+        let mut dst_vec = vec![0i32; LEN];
+
+        graph.enq(write, |ewait, enew| {
+            src_buf.write(&src_vec).ewait(ewait).enew(enew).enq()
+        }).unwrap();
+
+        // The current write and (after the first iteration) the previous
+        // read:
+        let expected_kern_deps = if i == 0 { 1 } else { 2 };
+        assert_eq!(graph.wait_list(kern).unwrap().len(), expected_kern_deps);
+        assert_eq!(graph.wait_list(kern).unwrap()[0], *graph.event(write).unwrap().unwrap());
+
+        graph.enq(kern, |ewait, enew| unsafe {
+            kernel.cmd().ewait(ewait).enew(enew).enq()
+        }).unwrap();
+
+        graph.enq(read, |ewait, enew| {
+            dst_buf.read(&mut dst_vec).ewait(ewait).enew(enew).enq()
+        }).unwrap();
+
+        graph.event(read).unwrap().unwrap().wait().unwrap();
+        assert!(dst_vec.iter().all(|&v| v == i + 100));
+    }
+
+    graph.wait().unwrap();
+    graph.clear_events();
+    assert!(graph.event(read).unwrap().is_none());
+}
@@ -0,0 +1,75 @@
+//! User events gating enqueued commands.
+
+use std::thread;
+use std::time::Duration;
+use standard::{ProQue, Buffer, Event, UserEvent};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+const LEN: usize = 1 << 10;
+
+
+/// Enqueues a kernel waiting on a user event which is set complete by
+/// another thread after a delay.
+#[test]
+fn user_event_gates_kernel() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(LEN)
+        .build().unwrap();
+
+    let buffer = Buffer::<f32>::builder()
+        .queue(pro_que.queue().clone())
+        .len(LEN)
+        .fill_val(1.0f32)
+        .build().unwrap();
+
+    let kernel = pro_que.kernel_builder("add")
+        .arg(&buffer)
+        .arg(5.0f32)
+        .build().unwrap();
+
+    let gate = UserEvent::new(pro_que.context()).unwrap();
+
+    let mut kernel_event = Event::empty();
+    unsafe { kernel.cmd().ewait(&gate).enew(&mut kernel_event).enq().unwrap(); }
+    pro_que.queue().flush().unwrap();
+
+    let gate_clone = gate.clone();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(100));
+        gate_clone.set_complete().unwrap();
+    });
+
+    // Still waiting on the gate:
+    assert!(!kernel_event.is_complete().unwrap());
+
+    kernel_event.wait_for().unwrap();
+    handle.join().unwrap();
+    assert!(gate.is_complete().unwrap());
+
+    let mut vec = vec![0.0f32; LEN];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 6.0));
+
+    // The status may only be set once:
+    assert!(gate.set_complete().is_err());
+}
+
+/// Sets an error status on a user event.
+#[test]
+fn user_event_error() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(LEN)
+        .build().unwrap();
+
+    let gate = UserEvent::new(pro_que.context()).unwrap();
+    assert!(gate.set_error(0).is_err());
+    gate.set_error(-1).unwrap();
+    assert!(gate.set_complete().is_err());
+}
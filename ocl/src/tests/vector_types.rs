@@ -2,7 +2,7 @@
 //!   operations.
 //!
 
-use standard::ProQue;
+use standard::{ProQue, Buffer};
 use prm::Int4;
 
 const DATASET_SIZE: usize = 1 << 20;
@@ -55,3 +55,127 @@ fn test_vector_types() {
         i += 1;
     }
 }
+
+/// Writes a known pattern of half-precision values and reads it back.
+#[test]
+fn half_buffer_round_trip() {
+    use standard::Buffer;
+    use prm::{f16, Half4};
+
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims(1024)
+        .build().unwrap();
+
+    let src: Vec<Half4> = (0..pro_que.dims().to_len()).map(|i| {
+        let v = (i % 2048) as f32 * 0.5;
+        Half4::new(f16::from_f32(v), f16::from_f32(-v), f16::from_f32(v * 0.25),
+            f16::from_f32(1.0))
+    }).collect();
+
+    let buffer = Buffer::<Half4>::builder()
+        .queue(pro_que.queue().clone())
+        .len(src.len())
+        .copy_host_slice(&src)
+        .build().unwrap();
+
+    let mut dst = vec![Half4::default(); src.len()];
+    buffer.read(&mut dst).enq().unwrap();
+
+    assert_eq!(dst, src);
+    assert_eq!(dst[3][0].to_f32(), 1.5);
+    assert_eq!(dst[3][1].to_f32(), -1.5);
+    assert_eq!(dst[3][3].to_f32(), 1.0);
+}
+
+/// Ensures host-side vector arithmetic is per-component, matching kernel
+/// semantics.
+#[test]
+fn vector_ops_per_lane() {
+    use prm::Float4;
+
+    let a = Int4::new(1, -2, 3, i32::max_value());
+    let b = Int4::new(10, 20, -30, 1);
+    let (sum, diff, prod) = (a + b, a - b, a * b);
+
+    for i in 0..4 {
+        assert_eq!(sum[i], a[i].wrapping_add(b[i]));
+        assert_eq!(diff[i], a[i].wrapping_sub(b[i]));
+        assert_eq!(prod[i], a[i].wrapping_mul(b[i]));
+    }
+
+    let c = Float4::new(0.5, -1.25, 8.0, 3.0);
+    let d = Float4::splat(2.0);
+    let (sum, prod) = (c + d, c * d);
+
+    for i in 0..4 {
+        assert_eq!(sum[i], c[i] + 2.0);
+        assert_eq!(prod[i], c[i] * 2.0);
+    }
+}
+
+#[test]
+fn buffer_reinterpret() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims(16)
+        .build().unwrap();
+
+    let bytes = pro_que.buffer_builder::<u8>().fill_val(0u8).build().unwrap();
+    assert_eq!(bytes.len(), 16);
+
+    let vectors: Buffer<Int4> = bytes.clone().reinterpret().unwrap();
+    assert_eq!(vectors.len(), 1);
+
+    let mut vec = vec![Int4::new(1, 1, 1, 1)];
+    vectors.read(&mut vec).enq().unwrap();
+    assert_eq!(vec[0], Int4::new(0, 0, 0, 0));
+
+    let odd = pro_que.buffer_builder::<u8>().len(15).build().unwrap();
+    assert!(odd.reinterpret::<Int4>().is_err());
+}
+
+/// Ensures scalar operands are broadcast to each component and that
+/// `min`/`max` are component-wise.
+#[test]
+fn vector_scalar_ops_and_min_max() {
+    use prm::{Float3, Uchar8};
+
+    let a = Int4::new(7, -2, 3, i32::max_value());
+    let (sum, diff, prod, quot, rem) = (a + 5, a - 5, a * 3, a / 2, a % 4);
+
+    for i in 0..4 {
+        assert_eq!(sum[i], a[i].wrapping_add(5));
+        assert_eq!(diff[i], a[i].wrapping_sub(5));
+        assert_eq!(prod[i], a[i].wrapping_mul(3));
+        assert_eq!(quot[i], a[i] / 2);
+        assert_eq!(rem[i], a[i] % 4);
+    }
+
+    let mut b = a;
+    b *= 3;
+    b -= 1;
+    assert_eq!(b, a * 3 - 1);
+
+    let c = Int4::new(0, 5, -5, 1);
+    let (min, max) = (a.min(c), a.max(c));
+
+    for i in 0..4 {
+        assert_eq!(min[i], ::std::cmp::min(a[i], c[i]));
+        assert_eq!(max[i], ::std::cmp::max(a[i], c[i]));
+    }
+
+    let d = Uchar8::splat(250) + 10;
+    assert_eq!(d, Uchar8::splat(4));
+
+    let e = Float3::new(0.5, -1.25, 8.0);
+    let f = Float3::new(1.0, -2.0, ::std::f32::NAN);
+    let (scaled, min, max) = (e * 2.0, e.min(f), e.max(f));
+
+    for i in 0..3 {
+        assert_eq!(scaled[i], e[i] * 2.0);
+        assert_eq!(min[i], e[i].min(f[i]));
+        assert_eq!(max[i], e[i].max(f[i]));
+    }
+    assert_eq!(min[2], 8.0);
+}